@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use mongodb::Database;
+
+use crate::{
+    api::api_key::{
+        data::{
+            datasources::{
+                api_key_datasource::ApiKeyDatasource,
+                api_key_in_memory::ApiKeyInMemoryDatasourceImpl,
+                api_key_mongo_db::ApiKeyMongoDatasourceImpl,
+            },
+            repositories::api_key_repository_impl::ApiKeyRepositoryImpl,
+        },
+        domain::usecases::*,
+    },
+    core::AppError,
+};
+
+pub struct ApiKeyDi {
+    pub create_api_key: Arc<CreateApiKey>,
+    pub get_api_keys: Arc<GetApiKeys>,
+    pub revoke_api_key: Arc<RevokeApiKey>,
+    pub delete_many_api_keys: Arc<DeleteManyApiKeys>,
+    pub resolve_api_key: Arc<ResolveApiKey>,
+}
+
+impl ApiKeyDi {
+    pub fn new(db: &Database) -> Self {
+        /* ························································ [ Datasource Implementation ] */
+        Self::with_datasource(Arc::new(ApiKeyMongoDatasourceImpl::new(db)))
+    }
+
+    /// Prepares the collections of the feature, to be run once at startup before serving
+    pub async fn prepare_database(db: &Database) -> Result<(), AppError> {
+        ApiKeyMongoDatasourceImpl::new(db).prepare().await
+    }
+
+    /// Wires the feature against an in-memory datasource (tests and local development)
+    pub fn in_memory() -> Self {
+        Self::with_datasource(Arc::new(ApiKeyInMemoryDatasourceImpl::new()))
+    }
+
+    pub fn with_datasource(datasource: Arc<dyn ApiKeyDatasource>) -> Self {
+        /* ························································ [ Repository Implementation ] */
+        let repository = Arc::new(ApiKeyRepositoryImpl::new(datasource));
+
+        /* ········································································· [ Usecases ] */
+        let create_api_key = Arc::new(CreateApiKey::new(repository.clone()));
+        let get_api_keys = Arc::new(GetApiKeys::new(repository.clone()));
+        let revoke_api_key = Arc::new(RevokeApiKey::new(repository.clone()));
+        let delete_many_api_keys = Arc::new(DeleteManyApiKeys::new(repository.clone()));
+        let resolve_api_key = Arc::new(ResolveApiKey::new(repository.clone()));
+
+        Self {
+            create_api_key,
+            get_api_keys,
+            revoke_api_key,
+            delete_many_api_keys,
+            resolve_api_key,
+        }
+    }
+}
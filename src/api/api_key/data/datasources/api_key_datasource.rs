@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use crate::{
+    api::api_key::{
+        data::datasources::api_key_mongo_db::ApiKeyMongoModel, domain::entities::api_key::ApiKey,
+    },
+    core::{datasource::crud_datasource::CrudDataSource, AppError},
+};
+
+#[async_trait]
+pub trait ApiKeyDatasource:
+    CrudDataSource<ApiKey, ApiKeyMongoModel, AppError> + Send + Sync
+{
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::api_key::{
+        data::datasources::{
+            api_key_datasource::ApiKeyDatasource, api_key_mongo_db::ApiKeyMongoModel,
+        },
+        domain::entities::api_key::ApiKey,
+    },
+    core::{
+        datasource::{crud_datasource::CrudDataSource, in_memory::InMemoryStore},
+        pagination::{PaginatedParams, PaginatedResponse},
+        AppError,
+    },
+};
+
+/// API keys datasource backed by an `InMemoryStore`. Used for tests and local development.
+#[derive(Default)]
+pub struct ApiKeyInMemoryDatasourceImpl {
+    store: InMemoryStore<ApiKey, ApiKeyMongoModel>,
+}
+
+impl ApiKeyInMemoryDatasourceImpl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CrudDataSource<ApiKey, ApiKeyMongoModel, AppError> for ApiKeyInMemoryDatasourceImpl {
+    async fn create(&self, item: &ApiKey) -> Result<ApiKey, AppError> {
+        self.store.create(item).await
+    }
+    async fn find_one_by_id(&self, id: &str) -> Result<ApiKey, AppError> {
+        self.store.find_one_by_id(id).await
+    }
+    async fn find_one(&self, query: HashMap<String, String>) -> Result<ApiKey, AppError> {
+        self.store.find_one(query).await
+    }
+    async fn find(&self, params: PaginatedParams) -> Result<PaginatedResponse<ApiKey>, AppError> {
+        self.store.find(params).await
+    }
+    async fn update_one(&self, item: &ApiKey) -> Result<ApiKey, AppError> {
+        self.store.update_one(item).await
+    }
+    async fn delete_by_id(&self, id: &str) -> Result<ApiKey, AppError> {
+        self.store.delete_by_id(id).await
+    }
+    async fn delete_one(&self, query: HashMap<String, String>) -> Result<ApiKey, AppError> {
+        self.store.delete_one(query).await
+    }
+    async fn delete_many(&self, query: HashMap<String, String>) -> Result<u64, AppError> {
+        self.store.delete_many(query).await
+    }
+}
+
+#[async_trait]
+impl ApiKeyDatasource for ApiKeyInMemoryDatasourceImpl {}
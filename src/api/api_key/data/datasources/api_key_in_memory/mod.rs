@@ -0,0 +1,2 @@
+pub mod api_key_datasource_in_memory_impl;
+pub use api_key_datasource_in_memory_impl::*;
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+
+use bson::doc;
+use mongodb::{options::IndexOptions, Collection, Database, IndexModel};
+
+use crate::{
+    api::api_key::{
+        data::datasources::{
+            api_key_datasource::ApiKeyDatasource, api_key_mongo_db::ApiKeyMongoModel,
+        },
+        domain::entities::api_key::ApiKey,
+    },
+    core::{datasource::mongo_db::crud_datasource_mongodb_impl::CrudDatasourceMongoImpl, AppError},
+};
+
+pub struct ApiKeyMongoDatasourceImpl {
+    collection: Collection<ApiKeyMongoModel>,
+}
+
+impl ApiKeyMongoDatasourceImpl {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("api_keys");
+        Self { collection }
+    }
+
+    /// Indexes the API keys, run once at startup
+    pub async fn prepare(&self) -> Result<(), AppError> {
+        // The keys are looked up by prefix on every request they authenticate, no two keys may
+        // share one. They are listed by user
+        let unique = IndexOptions::builder().unique(true).build();
+        let indexes = [
+            IndexModel::builder()
+                .keys(doc! { "prefix": 1 })
+                .options(unique)
+                .build(),
+            IndexModel::builder().keys(doc! { "user_id": 1 }).build(),
+        ];
+        self.collection
+            .create_indexes(indexes)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Could not create the indexes: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CrudDatasourceMongoImpl<ApiKey, ApiKeyMongoModel> for ApiKeyMongoDatasourceImpl {
+    fn get_collection(&self) -> &Collection<ApiKeyMongoModel> {
+        &self.collection
+    }
+}
+#[async_trait]
+impl ApiKeyDatasource for ApiKeyMongoDatasourceImpl {}
@@ -0,0 +1,92 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::api_key::domain::entities::api_key::ApiKey,
+    core::{crud_model::CrudModel, AppError, Validators},
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiKeyMongoModel {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    #[serde(default)]
+    pub user_id: ObjectId,
+
+    #[serde(default)]
+    pub name: String,
+
+    #[serde(default)]
+    pub prefix: String,
+
+    #[serde(default)]
+    pub key_hash: String,
+
+    #[serde(default)]
+    pub scopes: Vec<String>,
+
+    #[serde(default)]
+    pub expires_at: Option<BsonDateTime>,
+
+    #[serde(default)]
+    pub last_used_at: Option<BsonDateTime>,
+
+    pub created_at: BsonDateTime,
+}
+
+impl TryFrom<ApiKey> for ApiKeyMongoModel {
+    type Error = AppError;
+
+    fn try_from(entity: ApiKey) -> Result<Self, Self::Error> {
+        let id = if entity.id.is_empty() {
+            None
+        } else {
+            let id_or_err = Validators::validate_object_id(&entity.id)?;
+            Some(id_or_err)
+        };
+
+        let user_id = Validators::validate_object_id(&entity.user_id)?;
+
+        Ok(Self {
+            id,
+            user_id,
+            name: entity.name,
+            prefix: entity.prefix,
+            key_hash: entity.key_hash,
+            scopes: entity.scopes,
+            expires_at: entity.expires_at.map(BsonDateTime::from_chrono),
+            last_used_at: entity.last_used_at.map(BsonDateTime::from_chrono),
+            created_at: BsonDateTime::from_chrono(entity.created_at),
+        })
+    }
+}
+
+impl From<ApiKeyMongoModel> for ApiKey {
+    fn from(model: ApiKeyMongoModel) -> Self {
+        Self {
+            id: model.id.unwrap().to_string(),
+            user_id: model.user_id.to_string(),
+            name: model.name,
+            prefix: model.prefix,
+            key_hash: model.key_hash,
+            scopes: model.scopes,
+            expires_at: model.expires_at.map(|expires_at| expires_at.to_chrono()),
+            last_used_at: model
+                .last_used_at
+                .map(|last_used_at| last_used_at.to_chrono()),
+            created_at: model.created_at.to_chrono(),
+        }
+    }
+}
+
+impl CrudModel<ApiKey> for ApiKeyMongoModel {
+    fn try_from_entity(api_key: ApiKey) -> Result<Self, AppError> {
+        api_key.try_into()
+    }
+
+    fn to_entity(self) -> ApiKey {
+        self.into()
+    }
+}
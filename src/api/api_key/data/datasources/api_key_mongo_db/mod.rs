@@ -0,0 +1,5 @@
+pub mod api_key_datasource_mongodb_impl;
+pub use api_key_datasource_mongodb_impl::*;
+
+pub mod api_key_mongo_model;
+pub use api_key_mongo_model::*;
@@ -0,0 +1,3 @@
+pub mod api_key_datasource;
+pub mod api_key_in_memory;
+pub mod api_key_mongo_db;
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::api::api_key::domain::entities::api_key::ApiKey;
+
+/// An API key without its hash
+#[derive(Debug, Serialize)]
+pub struct ApiKeyResponseDto {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ApiKey> for ApiKeyResponseDto {
+    fn from(api_key: ApiKey) -> Self {
+        Self {
+            id: api_key.id,
+            user_id: api_key.user_id,
+            name: api_key.name,
+            prefix: api_key.prefix,
+            scopes: api_key.scopes,
+            expires_at: api_key.expires_at,
+            last_used_at: api_key.last_used_at,
+            created_at: api_key.created_at,
+        }
+    }
+}
+
+/// A new API key, the only response holding the key itself
+#[derive(Debug, Serialize)]
+pub struct CreatedApiKeyResponseDto {
+    #[serde(flatten)]
+    pub api_key: ApiKeyResponseDto,
+    /// To be sent in the `X-API-KEY` header, it cannot be retrieved later on
+    pub key: String,
+}
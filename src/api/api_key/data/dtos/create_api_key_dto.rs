@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::{
+    api::api_key::domain::entities::api_key::ApiKey,
+    core::{AppError, Validators},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyDto {
+    /// What the key is for, e.g. `CI deployments`
+    pub name: String,
+    /// e.g. `["storage:read"]`, the key may do everything its user may do when empty
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// The key never expires when missing
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl CreateApiKeyDto {
+    /// The API key of the user, along with the key itself. Its scopes must be among
+    /// `known_scopes`, see `Authenticator::known_scopes`
+    pub fn into_api_key(
+        self,
+        user_id: String,
+        known_scopes: &[&str],
+    ) -> Result<(ApiKey, String), AppError> {
+        let name =
+            Validators::validate_text_len(self.name, Some("Name".to_string()), Some(1), Some(125))?;
+
+        let scopes = self
+            .scopes
+            .into_iter()
+            .map(|scope| {
+                Validators::validate_text_len(scope, Some("Scope".to_string()), Some(1), Some(64))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // A typo would otherwise give a key that cannot reach any route
+        if let Some(scope) = scopes.iter().find(|s| !known_scopes.contains(&s.as_str())) {
+            let reason = format!(
+                "Unknown scope {}, expected one of {}",
+                scope,
+                known_scopes.join(", ")
+            );
+            return Err(AppError::InvalidInput(reason));
+        }
+
+        if self
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+        {
+            let reason = "The expiry date of the key must be in the future".to_string();
+            return Err(AppError::InvalidInput(reason));
+        }
+
+        Ok(ApiKey::generate(user_id, name, scopes, self.expires_at))
+    }
+}
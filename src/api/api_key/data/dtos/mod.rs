@@ -0,0 +1,5 @@
+mod create_api_key_dto;
+pub use create_api_key_dto::*;
+
+mod api_key_response_dto;
+pub use api_key_response_dto::*;
@@ -0,0 +1,3 @@
+pub mod datasources;
+pub mod dtos;
+pub mod repositories;
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::api_key::{
+        data::datasources::{
+            api_key_datasource::ApiKeyDatasource, api_key_mongo_db::ApiKeyMongoModel,
+        },
+        domain::{entities::api_key::ApiKey, repositories::api_key_repository::ApiKeyRepository},
+    },
+    core::CrudRepositoryImpl,
+};
+
+pub struct ApiKeyRepositoryImpl {
+    datasource: Arc<dyn ApiKeyDatasource>,
+}
+
+impl ApiKeyRepositoryImpl {
+    pub fn new(datasource: Arc<dyn ApiKeyDatasource>) -> Self {
+        Self { datasource }
+    }
+}
+
+#[async_trait]
+impl CrudRepositoryImpl<ApiKey, ApiKeyMongoModel, dyn ApiKeyDatasource> for ApiKeyRepositoryImpl {
+    fn get_datasource(&self) -> Arc<dyn ApiKeyDatasource> {
+        self.datasource.clone()
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for ApiKeyRepositoryImpl {}
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Duration, Utc};
+use rand::{rngs::OsRng, RngCore};
+use serde::Serialize;
+
+use crate::core::rand_token_service::TokenService;
+
+// Every key starts with it, which helps secret scanners spot the leaked ones
+const API_KEY_PREFIX: &str = "ak";
+// `last_used_at` is refreshed at most this often, rather than on every request
+const LAST_USED_AT_PRECISION_SECONDS: i64 = 60;
+
+/// A long-lived credential of a user for the scripts and the other servers, sent in the
+/// `X-API-KEY` header. It reads `ak_<prefix>_<secret>`: the prefix finds the key, the whole of it
+/// is checked against its hash
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiKey {
+    pub id: String,
+    pub user_id: String,
+    /// What the key is for, e.g. `CI deployments`
+    pub name: String,
+    /// Public part of the key, enough to tell the keys apart
+    pub prefix: String,
+    // Only the hash of the key is kept, the key itself is handed to the user once
+    pub key_hash: String,
+    /// What the key may do, see `Claims::has_scope`. Everything the user may do when empty
+    pub scopes: Vec<String>,
+    /// Never expires when `None`
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    /// A new key of the user, along with the key itself
+    pub fn generate(
+        user_id: String,
+        name: String,
+        scopes: Vec<String>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> (Self, String) {
+        let mut prefix = [0u8; 6];
+        OsRng.fill_bytes(&mut prefix);
+        let prefix = hex::encode(prefix);
+        let key = format!(
+            "{}_{}_{}",
+            API_KEY_PREFIX,
+            prefix,
+            TokenService::generate_secret_token()
+        );
+
+        let api_key = Self {
+            id: "".to_string(),
+            user_id,
+            name,
+            prefix,
+            key_hash: TokenService::hash_token(&key),
+            scopes,
+            expires_at,
+            last_used_at: None,
+            created_at: Utc::now(),
+        };
+
+        (api_key, key)
+    }
+
+    /// The prefix of a key, `None` if it is not shaped like one
+    pub fn parse_prefix(key: &str) -> Option<&str> {
+        let mut parts = key.splitn(3, '_');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(API_KEY_PREFIX), Some(prefix), Some(secret))
+                if !prefix.is_empty() && !secret.is_empty() =>
+            {
+                Some(prefix)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn verify(&self, key: &str) -> bool {
+        TokenService::verify_token(key, &self.key_hash)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+
+    /// Records that the key was just used, returns `false` if it was already recently
+    pub fn touch(&mut self) -> bool {
+        let now = Utc::now();
+        let recently_used = self.last_used_at.is_some_and(|last_used_at| {
+            now - last_used_at < Duration::seconds(LAST_USED_AT_PRECISION_SECONDS)
+        });
+        if recently_used {
+            return false;
+        }
+
+        self.last_used_at = Some(now);
+        true
+    }
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use crate::{
+    api::api_key::{
+        data::datasources::{
+            api_key_datasource::ApiKeyDatasource, api_key_mongo_db::ApiKeyMongoModel,
+        },
+        domain::entities::api_key::ApiKey,
+    },
+    core::{AppError, CrudRepository},
+};
+
+#[async_trait]
+pub trait ApiKeyRepository:
+    CrudRepository<ApiKey, ApiKeyMongoModel, AppError, dyn ApiKeyDatasource>
+{
+}
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::api_key::domain::{
+        entities::api_key::ApiKey, repositories::api_key_repository::ApiKeyRepository,
+    },
+    core::{AppError, UseCase},
+};
+
+pub struct CreateApiKey {
+    repository: Arc<dyn ApiKeyRepository>,
+}
+
+impl CreateApiKey {
+    pub fn new(repository: Arc<dyn ApiKeyRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<ApiKey, ApiKey> for CreateApiKey {
+    async fn execute(&self, api_key: ApiKey) -> Result<ApiKey, AppError> {
+        self.repository.create_one(&api_key).await
+    }
+}
@@ -0,0 +1,30 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    api::api_key::domain::repositories::api_key_repository::ApiKeyRepository,
+    core::{AppError, CommandUseCase},
+};
+
+/// Deletes the API keys matching the query, e.g. those of the deleted users
+pub struct DeleteManyApiKeys {
+    repository: Arc<dyn ApiKeyRepository>,
+}
+
+impl DeleteManyApiKeys {
+    pub fn new(repository: Arc<dyn ApiKeyRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl CommandUseCase<HashMap<String, String>> for DeleteManyApiKeys {
+    async fn execute(&self, query: HashMap<String, String>) -> Result<(), AppError> {
+        match self.repository.delete_many(query).await {
+            // Most users have no API key
+            Ok(_) | Err(AppError::NotFound(_)) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+}
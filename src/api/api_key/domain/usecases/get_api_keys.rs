@@ -0,0 +1,36 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    api::api_key::domain::{
+        entities::api_key::ApiKey, repositories::api_key_repository::ApiKeyRepository,
+    },
+    core::{pagination::PaginatedParams, AppError, UseCase},
+};
+
+/// Every API key of a user
+pub struct GetApiKeys {
+    repository: Arc<dyn ApiKeyRepository>,
+}
+
+impl GetApiKeys {
+    pub fn new(repository: Arc<dyn ApiKeyRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<String, Vec<ApiKey>> for GetApiKeys {
+    async fn execute(&self, user_id: String) -> Result<Vec<ApiKey>, AppError> {
+        let mut query = HashMap::new();
+        query.insert("user_id".to_string(), user_id);
+
+        let api_keys = self
+            .repository
+            .find(PaginatedParams::all_with_filter(query))
+            .await?;
+
+        Ok(api_keys.records)
+    }
+}
@@ -0,0 +1,14 @@
+pub mod create_api_key;
+pub use create_api_key::*;
+
+pub mod get_api_keys;
+pub use get_api_keys::*;
+
+pub mod revoke_api_key;
+pub use revoke_api_key::*;
+
+pub mod delete_many_api_keys;
+pub use delete_many_api_keys::*;
+
+pub mod resolve_api_key;
+pub use resolve_api_key::*;
@@ -0,0 +1,62 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    api::api_key::domain::{
+        entities::api_key::ApiKey, repositories::api_key_repository::ApiKeyRepository,
+    },
+    core::{AppError, MsgBuilder, UseCase},
+};
+
+/// The API key matching a key sent by a client, provided it has not expired. Its
+/// `last_used_at` is updated along the way
+pub struct ResolveApiKey {
+    repository: Arc<dyn ApiKeyRepository>,
+}
+
+impl ResolveApiKey {
+    pub fn new(repository: Arc<dyn ApiKeyRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<String, ApiKey> for ResolveApiKey {
+    async fn execute(&self, key: String) -> Result<ApiKey, AppError> {
+        let invalid = || AppError::Unauthorized(MsgBuilder::custom("Invalid API key!"));
+
+        /* ····························································· [ Lookup by the prefix ] */
+        let prefix = ApiKey::parse_prefix(&key).ok_or_else(invalid)?;
+        let mut query = HashMap::new();
+        query.insert("prefix".to_string(), prefix.to_string());
+
+        let mut api_key = match self.repository.find_one(query).await {
+            Ok(api_key) => api_key,
+            Err(AppError::NotFound(_)) => return Err(invalid()),
+            Err(err) => return Err(err),
+        };
+
+        /* ··································································· [ Verify the key ] */
+        if !api_key.verify(&key) {
+            return Err(invalid());
+        }
+        if api_key.is_expired() {
+            let msg = MsgBuilder::custom("This API key has expired");
+            return Err(AppError::Unauthorized(msg));
+        }
+
+        /* ····································································· [ Last used at ] */
+        // A failed update should not fail the request
+        if api_key.touch() {
+            if let Err(e) = self.repository.update_one(&api_key).await {
+                eprintln!(
+                    "Could not update the last use of API key {}: {}",
+                    api_key.id, e
+                );
+            }
+        }
+
+        Ok(api_key)
+    }
+}
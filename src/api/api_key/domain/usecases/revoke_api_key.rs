@@ -0,0 +1,34 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    api::api_key::domain::{
+        entities::api_key::ApiKey, repositories::api_key_repository::ApiKeyRepository,
+    },
+    core::{AppError, MsgBuilder, UseCase},
+};
+
+/// Deletes the API key matching the query, it is rejected from then on
+pub struct RevokeApiKey {
+    repository: Arc<dyn ApiKeyRepository>,
+}
+
+impl RevokeApiKey {
+    pub fn new(repository: Arc<dyn ApiKeyRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<HashMap<String, String>, ApiKey> for RevokeApiKey {
+    async fn execute(&self, query: HashMap<String, String>) -> Result<ApiKey, AppError> {
+        self.repository
+            .delete_one(query)
+            .await
+            .map_err(|e| match e {
+                AppError::NotFound(_) => AppError::NotFound(MsgBuilder::not_found("API key")),
+                e => e,
+            })
+    }
+}
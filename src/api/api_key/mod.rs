@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use presentation::handlers::*;
+use warp::{reject::Rejection, reply::Reply, Filter};
+
+use crate::di::ServiceLocator;
+
+pub mod api_key_di;
+pub mod data;
+pub mod domain;
+pub mod presentation;
+
+/// The API keys of the users, sent in the `X-API-KEY` header instead of an access token (see
+/// `Authenticator`). They are managed from a user session only, not with another API key.
+pub struct ApiKeyFeature {
+    /// [POST] /api-keys
+    create_api_key_handler: Arc<CreateApiKeyHandler>,
+    /// [GET] /api-keys
+    get_api_keys_handler: Arc<GetApiKeysHandler>,
+    /// [DELETE] /api-keys/[String]
+    revoke_api_key_handler: Arc<RevokeApiKeyHandler>,
+}
+
+impl ApiKeyFeature {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self {
+            create_api_key_handler: Arc::new(CreateApiKeyHandler::new(sl.clone())),
+            get_api_keys_handler: Arc::new(GetApiKeysHandler::new(sl.clone())),
+            revoke_api_key_handler: Arc::new(RevokeApiKeyHandler::new(sl.clone())),
+        }
+    }
+
+    pub fn routes(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        // [POST] api/api-keys
+        Arc::clone(&self.create_api_key_handler)
+            .route()
+            // [GET] api/api-keys
+            .or(Arc::clone(&self.get_api_keys_handler).route())
+            // [DELETE] api/api-keys/<String>
+            .or(Arc::clone(&self.revoke_api_key_handler).route())
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::{
+        api_key::data::dtos::{ApiKeyResponseDto, CreateApiKeyDto, CreatedApiKeyResponseDto},
+        auth::domain::entities::Claims,
+    },
+    core::{
+        middleware::{auth_middleware, session_middleware},
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// Creates an API key of the logged in user. The key is only returned here
+pub struct CreateApiKeyHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl CreateApiKeyHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        dto: CreateApiKeyDto,
+        claims: Claims,
+    ) -> Result<impl Reply, Rejection> {
+        let known_scopes = self.sl.authenticator().known_scopes();
+        let (api_key, key) = dto.into_api_key(claims.user_id, &known_scopes)?;
+        let api_key = self.sl.create_api_key().execute(api_key).await?;
+
+        //* Success ············································································· */
+        let response_body = CreatedApiKeyResponseDto {
+            api_key: ApiKeyResponseDto::from(api_key),
+            key,
+        };
+        let msg = MsgBuilder::created_success("API key");
+        let response = ApiResponse::success(msg, Some(response_body));
+        Ok(with_status(json(&response), StatusCode::CREATED))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("api-keys")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(auth_middleware(self.sl.authenticator()).and_then(session_middleware))
+            .and_then(move |dto: CreateApiKeyDto, claims: Claims| {
+                let handler = self.clone();
+                async move { handler.handle(dto, claims).await }
+            })
+    }
+}
@@ -0,0 +1,72 @@
+use std::{collections::HashMap, sync::Arc};
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::{api_key::data::dtos::ApiKeyResponseDto, auth::domain::entities::Claims},
+    core::{
+        middleware::{auth_middleware, owner_or_admin_middleware, session_middleware},
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// The API keys of the logged in user. Admins may list those of another user with `?user_id=`
+pub struct GetApiKeysHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl GetApiKeysHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(self: Arc<Self>, user_id: String) -> Result<impl Reply, Rejection> {
+        let api_keys: Vec<ApiKeyResponseDto> = self
+            .sl
+            .get_api_keys()
+            .execute(user_id)
+            .await?
+            .into_iter()
+            .map(ApiKeyResponseDto::from)
+            .collect();
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::loaded_success("API keys");
+        let response = ApiResponse::success(msg, Some(api_keys));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("api-keys")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and(auth_middleware(self.sl.authenticator()).and_then(session_middleware))
+            .and_then(
+                |query: HashMap<String, String>, claims: Claims| async move {
+                    let user_id = match query.get("user_id") {
+                        Some(user_id) => user_id.clone(),
+                        None => claims.user_id.clone(),
+                    };
+                    owner_or_admin_middleware(user_id.clone(), claims).await?;
+                    Ok::<String, Rejection>(user_id)
+                },
+            )
+            .and_then(move |user_id: String| {
+                let handler = self.clone();
+                async move { handler.handle(user_id).await }
+            })
+    }
+}
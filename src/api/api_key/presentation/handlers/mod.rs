@@ -0,0 +1,7 @@
+mod create_api_key_handler;
+mod get_api_keys_handler;
+mod revoke_api_key_handler;
+
+pub use create_api_key_handler::*;
+pub use get_api_keys_handler::*;
+pub use revoke_api_key_handler::*;
@@ -0,0 +1,61 @@
+use std::{collections::HashMap, sync::Arc};
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::{api_key::data::dtos::ApiKeyResponseDto, auth::domain::entities::Claims},
+    core::{
+        middleware::{auth_middleware, session_middleware},
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// Revokes an API key of the logged in user, admins may revoke anybody's
+pub struct RevokeApiKeyHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl RevokeApiKeyHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(self: Arc<Self>, id: String, claims: Claims) -> Result<impl Reply, Rejection> {
+        let mut filter = HashMap::new();
+        filter.insert("_id".to_string(), id);
+        // The keys of another user are not found, rather than forbidden
+        if !claims.is_admin() {
+            filter.insert("user_id".to_string(), claims.user_id);
+        }
+
+        let api_key = self.sl.revoke_api_key().execute(filter).await?;
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::deleted_success("API key");
+        let response = ApiResponse::success(msg, Some(ApiKeyResponseDto::from(api_key)));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("api-keys" / String)
+            .and(warp::delete())
+            .and(auth_middleware(self.sl.authenticator()).and_then(session_middleware))
+            .and_then(move |id: String, claims: Claims| {
+                let handler = self.clone();
+                async move { handler.handle(id, claims).await }
+            })
+    }
+}
@@ -2,8 +2,9 @@ use std::sync::Arc;
 
 use mongodb::Database;
 
-use crate::api::auth::domain::usecases::{
-    add_one_user::AddOneUser, user_delete_many::DeleteManyUsers,
+use crate::{
+    api::auth::domain::usecases::{add_one_user::AddOneUser, user_delete_many::DeleteManyUsers},
+    core::AppError,
 };
 
 use super::{data::*, domain::usecases::*};
@@ -16,11 +17,26 @@ pub struct AuthDi {
     pub delete_many_users: Arc<DeleteManyUsers>,
     pub get_many_users: Arc<GetManyUsers>,
     pub add_one_user: Arc<AddOneUser>,
+    pub verify_second_factor: Arc<VerifySecondFactor>,
+    pub set_second_factors: Arc<SetSecondFactors>,
 }
 
 impl AuthDi {
     pub fn new(db: &Database) -> Self {
-        let datasource = Arc::new(UserDataSourceMongoDbImpl::new(db));
+        Self::with_datasource(Arc::new(UserDataSourceMongoDbImpl::new(db)))
+    }
+
+    /// Prepares the collections of the feature, to be run once at startup before serving
+    pub async fn prepare_database(db: &Database) -> Result<(), AppError> {
+        UserDataSourceMongoDbImpl::new(db).prepare().await
+    }
+
+    /// Wires the feature against an in-memory datasource (tests and local development)
+    pub fn in_memory() -> Self {
+        Self::with_datasource(Arc::new(UserDataSourceInMemoryImpl::new()))
+    }
+
+    pub fn with_datasource(datasource: Arc<dyn UserDataSource>) -> Self {
         let repository = Arc::new(UserRepositoryImpl::new(datasource));
 
         // usecases
@@ -34,6 +50,9 @@ impl AuthDi {
 
         let get_many_users = Arc::new(GetManyUsers::new(repository.clone()));
 
+        let verify_second_factor = Arc::new(VerifySecondFactor::new(repository.clone()));
+        let set_second_factors = Arc::new(SetSecondFactors::new(repository.clone()));
+
         Self {
             add_one_user,
             get_user_by_id,
@@ -42,6 +61,8 @@ impl AuthDi {
             delete_user,
             delete_many_users,
             get_many_users,
+            verify_second_factor,
+            set_second_factors,
         }
     }
 }
@@ -1,5 +1,7 @@
 pub mod user_datasource;
+pub mod user_in_memory;
 pub mod user_mongo_db;
 
 pub use user_datasource::*;
+pub use user_in_memory::*;
 pub use user_mongo_db::*;
@@ -1,9 +1,45 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::{
-    api::auth::{data::UserMongoModel, domain::entities::User},
+    api::auth::{
+        data::UserMongoModel,
+        domain::entities::{user::TwoFactorBackoff, User},
+    },
     core::{datasource::crud_datasource::CrudDataSource, AppError},
 };
 
+/// The two-factor fields of the users that concurrent requests race for (the used codes and the
+/// wrong attempts) are only written by the conditional updates below, see `UserMongoModel`
 #[async_trait]
-pub trait UserDataSource: CrudDataSource<User, UserMongoModel, AppError> + Send + Sync {}
+pub trait UserDataSource: CrudDataSource<User, UserMongoModel, AppError> + Send + Sync {
+    /// Atomically counts a try of a two-factor code by the user, unless locked out at `now`, and
+    /// locks the user out as `backoff` says. `None` while locked out, else the user as of then
+    async fn reserve_two_factor_attempt(
+        &self,
+        user_id: &str,
+        now: DateTime<Utc>,
+        backoff: &TwoFactorBackoff,
+    ) -> Result<Option<User>, AppError>;
+
+    /// Atomically records the time step of a code of the authenticator app as used, provided it
+    /// is later than the last one used. False when it is not, e.g. when another request used the
+    /// same code first
+    async fn use_totp_step(&self, user_id: &str, step: i64) -> Result<bool, AppError>;
+
+    /// Atomically removes the recovery code hashing to `code_hash`. False when the user does not
+    /// have it, e.g. when another request used it first
+    async fn use_recovery_code(&self, user_id: &str, code_hash: &str) -> Result<bool, AppError>;
+
+    /// Forgets the wrong two-factor codes tried in a row, once a right one is entered
+    async fn reset_two_factor_attempts(&self, user_id: &str) -> Result<(), AppError>;
+
+    /// Replaces the second factors of the user, i.e. the time step of the last code of the app
+    /// used and the hashes of the recovery codes, and forgets the wrong codes tried in a row
+    async fn set_second_factors(
+        &self,
+        user_id: &str,
+        totp_last_step: Option<i64>,
+        recovery_codes: &[String],
+    ) -> Result<(), AppError>;
+}
@@ -0,0 +1,2 @@
+pub mod user_datasource_in_memory_impl;
+pub use user_datasource_in_memory_impl::*;
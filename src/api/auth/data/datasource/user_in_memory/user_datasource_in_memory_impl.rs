@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use bson::{doc, Bson, DateTime as BsonDateTime};
+use chrono::{DateTime, Utc};
+
+use crate::{
+    api::auth::{
+        data::{UserDataSource, UserMongoModel},
+        domain::entities::{user::TwoFactorBackoff, User},
+    },
+    core::{
+        datasource::{crud_datasource::CrudDataSource, in_memory::InMemoryStore},
+        pagination::{PaginatedParams, PaginatedResponse},
+        AppError, Validators,
+    },
+};
+
+/// Users datasource backed by an `InMemoryStore`. Used for tests and local development.
+#[derive(Default)]
+pub struct UserDataSourceInMemoryImpl {
+    store: InMemoryStore<User, UserMongoModel>,
+}
+
+impl UserDataSourceInMemoryImpl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CrudDataSource<User, UserMongoModel, AppError> for UserDataSourceInMemoryImpl {
+    async fn create(&self, item: &User) -> Result<User, AppError> {
+        self.store.create(item).await
+    }
+    async fn find_one_by_id(&self, id: &str) -> Result<User, AppError> {
+        self.store.find_one_by_id(id).await
+    }
+    async fn find_one(&self, query: HashMap<String, String>) -> Result<User, AppError> {
+        self.store.find_one(query).await
+    }
+    async fn find(&self, params: PaginatedParams) -> Result<PaginatedResponse<User>, AppError> {
+        self.store.find(params).await
+    }
+    async fn update_one(&self, item: &User) -> Result<User, AppError> {
+        self.store.update_one(item).await
+    }
+    async fn delete_by_id(&self, id: &str) -> Result<User, AppError> {
+        self.store.delete_by_id(id).await
+    }
+    async fn delete_one(&self, query: HashMap<String, String>) -> Result<User, AppError> {
+        self.store.delete_one(query).await
+    }
+    async fn delete_many(&self, query: HashMap<String, String>) -> Result<u64, AppError> {
+        self.store.delete_many(query).await
+    }
+}
+
+#[async_trait]
+impl UserDataSource for UserDataSourceInMemoryImpl {
+    async fn reserve_two_factor_attempt(
+        &self,
+        user_id: &str,
+        now: DateTime<Utc>,
+        backoff: &TwoFactorBackoff,
+    ) -> Result<Option<User>, AppError> {
+        let filter = doc! {
+            "_id": Validators::validate_object_id(user_id)?,
+            "$or": [
+                { "two_factor_locked_until": null },
+                { "two_factor_locked_until": { "$lte": BsonDateTime::from_chrono(now) } },
+            ],
+        };
+
+        self.store
+            .update_first(&filter, |user| {
+                let attempts = user.get_i32("two_factor_attempts").unwrap_or(0) + 1;
+                let locked_until = backoff
+                    .locked_until(attempts, now)
+                    .map_or(Bson::Null, |until| BsonDateTime::from_chrono(until).into());
+                user.insert("two_factor_attempts", attempts);
+                user.insert("two_factor_locked_until", locked_until);
+                true
+            })
+            .await
+    }
+
+    async fn use_totp_step(&self, user_id: &str, step: i64) -> Result<bool, AppError> {
+        let filter = doc! {
+            "_id": Validators::validate_object_id(user_id)?,
+            "$or": [
+                { "totp_last_step": null },
+                { "totp_last_step": { "$lt": step } },
+            ],
+        };
+
+        let updated = self
+            .store
+            .update_first(&filter, |user| {
+                user.insert("totp_last_step", step);
+                true
+            })
+            .await?;
+
+        Ok(updated.is_some())
+    }
+
+    async fn use_recovery_code(&self, user_id: &str, code_hash: &str) -> Result<bool, AppError> {
+        let filter = doc! { "_id": Validators::validate_object_id(user_id)? };
+
+        let updated = self
+            .store
+            .update_first(&filter, |user| {
+                let Ok(codes) = user.get_array_mut("recovery_codes") else {
+                    return false;
+                };
+                let Some(index) = codes
+                    .iter()
+                    .position(|code| code.as_str() == Some(code_hash))
+                else {
+                    return false;
+                };
+                codes.remove(index);
+                true
+            })
+            .await?;
+
+        Ok(updated.is_some())
+    }
+
+    async fn reset_two_factor_attempts(&self, user_id: &str) -> Result<(), AppError> {
+        let filter = doc! { "_id": Validators::validate_object_id(user_id)? };
+
+        self.store
+            .update_first(&filter, |user| {
+                user.insert("two_factor_attempts", 0);
+                user.insert("two_factor_locked_until", Bson::Null);
+                true
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn set_second_factors(
+        &self,
+        user_id: &str,
+        totp_last_step: Option<i64>,
+        recovery_codes: &[String],
+    ) -> Result<(), AppError> {
+        let filter = doc! { "_id": Validators::validate_object_id(user_id)? };
+
+        self.store
+            .update_first(&filter, |user| {
+                user.insert("totp_last_step", totp_last_step);
+                user.insert("recovery_codes", recovery_codes);
+                user.insert("two_factor_attempts", 0);
+                user.insert("two_factor_locked_until", Bson::Null);
+                true
+            })
+            .await?;
+
+        Ok(())
+    }
+}
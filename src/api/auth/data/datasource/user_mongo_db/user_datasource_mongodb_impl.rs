@@ -1,13 +1,26 @@
 use async_trait::async_trait;
 
-use mongodb::{Collection, Database};
+use bson::{doc, DateTime as BsonDateTime, Document};
+use chrono::{DateTime, Utc};
+use mongodb::{
+    action::Action,
+    options::{IndexOptions, ReturnDocument},
+    Collection, Database, IndexModel,
+};
 
 use crate::{
     api::auth::{
         data::{UserDataSource, UserMongoModel},
-        domain::entities::User,
+        domain::entities::{user::TwoFactorBackoff, User},
+    },
+    core::{
+        crud_model::CrudModel,
+        datasource::{
+            mongo_db::crud_datasource_mongodb_impl::CrudDatasourceMongoImpl,
+            transaction::Transaction,
+        },
+        AppError, Validators,
     },
-    core::datasource::mongo_db::crud_datasource_mongodb_impl::CrudDatasourceMongoImpl,
 };
 
 pub struct UserDataSourceMongoDbImpl {
@@ -19,6 +32,43 @@ impl UserDataSourceMongoDbImpl {
         let collection = db.collection("users");
         Self { collection }
     }
+
+    /// Indexes the users, run once at startup
+    pub async fn prepare(&self) -> Result<(), AppError> {
+        // The pending two-factor challenges are looked up by token, few users have one
+        let options = IndexOptions::builder()
+            .partial_filter_expression(doc! { "two_factor_challenge": { "$type": "string" } })
+            .build();
+        let index = IndexModel::builder()
+            .keys(doc! { "two_factor_challenge": 1 })
+            .options(options)
+            .build();
+        self.collection
+            .create_index(index)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Could not create the indexes: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Updates the user matching `filter`, within the current transaction if any. False when no
+    // user matches
+    async fn update_user(
+        &self,
+        filter: Document,
+        update: impl Into<mongodb::options::UpdateModifications> + Send,
+    ) -> Result<bool, AppError> {
+        let transaction = Transaction::current();
+        let mut session = Transaction::session_of(&transaction).await;
+        let result = self
+            .collection
+            .update_one(filter, update)
+            .optional(session.as_deref_mut(), |a, s| a.session(s))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Update failed: {}", e)))?;
+
+        Ok(result.matched_count > 0)
+    }
 }
 
 #[async_trait]
@@ -29,4 +79,117 @@ impl CrudDatasourceMongoImpl<User, UserMongoModel> for UserDataSourceMongoDbImpl
 }
 
 #[async_trait]
-impl UserDataSource for UserDataSourceMongoDbImpl {}
+impl UserDataSource for UserDataSourceMongoDbImpl {
+    async fn reserve_two_factor_attempt(
+        &self,
+        user_id: &str,
+        now: DateTime<Utc>,
+        backoff: &TwoFactorBackoff,
+    ) -> Result<Option<User>, AppError> {
+        let now = BsonDateTime::from_chrono(now);
+        let filter = doc! {
+            "_id": Validators::validate_object_id(user_id)?,
+            "$or": [
+                { "two_factor_locked_until": null },
+                { "two_factor_locked_until": { "$lte": now } },
+            ],
+        };
+        // `TwoFactorBackoff::locked_until` of the new count, worked out by the database so that
+        // the count and the lockout are updated together
+        let lockout_ms = doc! {
+            "$min": [
+                backoff.max_seconds * 1000,
+                { "$multiply": [
+                    backoff.base_seconds * 1000,
+                    { "$pow": [2, { "$subtract": ["$two_factor_attempts", backoff.free_attempts] }] },
+                ] },
+            ],
+        };
+        let update = vec![
+            doc! { "$set": {
+                "two_factor_attempts": { "$add": [{ "$ifNull": ["$two_factor_attempts", 0] }, 1] },
+            } },
+            doc! { "$set": {
+                "two_factor_locked_until": { "$cond": [
+                    { "$gte": ["$two_factor_attempts", backoff.free_attempts] },
+                    { "$add": [now, lockout_ms] },
+                    null,
+                ] },
+                "updated_at": "$$NOW",
+            } },
+        ];
+
+        let transaction = Transaction::current();
+        let mut session = Transaction::session_of(&transaction).await;
+        let user = self
+            .collection
+            .find_one_and_update(filter, update)
+            .return_document(ReturnDocument::After)
+            .optional(session.as_deref_mut(), |a, s| a.session(s))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Update failed: {}", e)))?;
+
+        Ok(user.map(|model| model.to_entity()))
+    }
+
+    async fn use_totp_step(&self, user_id: &str, step: i64) -> Result<bool, AppError> {
+        let filter = doc! {
+            "_id": Validators::validate_object_id(user_id)?,
+            "$or": [
+                { "totp_last_step": null },
+                { "totp_last_step": { "$lt": step } },
+            ],
+        };
+        let update = doc! {
+            "$set": { "totp_last_step": step },
+            "$currentDate": { "updated_at": true },
+        };
+
+        self.update_user(filter, update).await
+    }
+
+    async fn use_recovery_code(&self, user_id: &str, code_hash: &str) -> Result<bool, AppError> {
+        let filter = doc! {
+            "_id": Validators::validate_object_id(user_id)?,
+            "recovery_codes": code_hash,
+        };
+        let update = doc! {
+            "$pull": { "recovery_codes": code_hash },
+            "$currentDate": { "updated_at": true },
+        };
+
+        self.update_user(filter, update).await
+    }
+
+    async fn reset_two_factor_attempts(&self, user_id: &str) -> Result<(), AppError> {
+        let filter = doc! { "_id": Validators::validate_object_id(user_id)? };
+        let update = doc! {
+            "$set": { "two_factor_attempts": 0, "two_factor_locked_until": null },
+            "$currentDate": { "updated_at": true },
+        };
+
+        self.update_user(filter, update).await?;
+        Ok(())
+    }
+
+    async fn set_second_factors(
+        &self,
+        user_id: &str,
+        totp_last_step: Option<i64>,
+        recovery_codes: &[String],
+    ) -> Result<(), AppError> {
+        let filter = doc! { "_id": Validators::validate_object_id(user_id)? };
+        let update = doc! {
+            "$set": {
+                "totp_last_step": totp_last_step,
+                "recovery_codes": recovery_codes,
+                "two_factor_attempts": 0,
+                "two_factor_locked_until": null,
+            },
+            "$currentDate": { "updated_at": true },
+        };
+
+        self.update_user(filter, update).await?;
+        Ok(())
+    }
+}
@@ -34,6 +34,28 @@ pub struct UserMongoModel {
     pub verified: bool,
     #[serde(default)]
     pub banned: bool,
+    #[serde(default)]
+    pub locale: Option<String>,
+    #[serde(default)]
+    pub tokens_valid_after: Option<BsonDateTime>,
+    #[serde(default)]
+    pub totp_secret: Option<String>,
+    #[serde(default)]
+    pub totp_enabled: bool,
+    // The used codes and the wrong attempts are only written by the conditional updates of
+    // `UserDataSource`, never by a whole user saved after them
+    #[serde(default, skip_serializing)]
+    pub totp_last_step: Option<i64>,
+    #[serde(default, skip_serializing)]
+    pub recovery_codes: Vec<String>,
+    #[serde(default)]
+    pub two_factor_challenge: Option<String>,
+    #[serde(default)]
+    pub two_factor_challenge_expires_at: Option<BsonDateTime>,
+    #[serde(default, skip_serializing)]
+    pub two_factor_attempts: i32,
+    #[serde(default, skip_serializing)]
+    pub two_factor_locked_until: Option<BsonDateTime>,
 
     pub created_at: BsonDateTime,
 }
@@ -63,6 +85,18 @@ impl TryFrom<User> for UserMongoModel {
             is_logged_out: user.is_logged_out,
             verified: user.verified,
             banned: user.banned,
+            locale: user.locale,
+            tokens_valid_after: user.tokens_valid_after.map(BsonDateTime::from_chrono),
+            totp_secret: user.totp_secret,
+            totp_enabled: user.totp_enabled,
+            totp_last_step: user.totp_last_step,
+            recovery_codes: user.recovery_codes,
+            two_factor_challenge: user.two_factor_challenge,
+            two_factor_challenge_expires_at: user
+                .two_factor_challenge_expires_at
+                .map(BsonDateTime::from_chrono),
+            two_factor_attempts: user.two_factor_attempts,
+            two_factor_locked_until: user.two_factor_locked_until.map(BsonDateTime::from_chrono),
             created_at: BsonDateTime::from_chrono(user.created_at),
         })
     }
@@ -84,6 +118,22 @@ impl From<UserMongoModel> for User {
             is_logged_out: model.is_logged_out,
             verified: model.verified,
             banned: model.banned,
+            locale: model.locale,
+            tokens_valid_after: model
+                .tokens_valid_after
+                .map(|valid_after| valid_after.to_chrono()),
+            totp_secret: model.totp_secret,
+            totp_enabled: model.totp_enabled,
+            totp_last_step: model.totp_last_step,
+            recovery_codes: model.recovery_codes,
+            two_factor_challenge: model.two_factor_challenge,
+            two_factor_challenge_expires_at: model
+                .two_factor_challenge_expires_at
+                .map(|expires_at| expires_at.to_chrono()),
+            two_factor_attempts: model.two_factor_attempts,
+            two_factor_locked_until: model
+                .two_factor_locked_until
+                .map(|locked_until| locked_until.to_chrono()),
             created_at: model.created_at.to_chrono(),
         }
     }
@@ -7,6 +7,9 @@ use crate::api::auth::data::user_response_dto::UserResponseDto;
 pub struct LoginDto {
     pub email: String,
     pub password: String,
+    // Names the session of the login, e.g. `Jane's iPhone`
+    #[serde(default)]
+    pub device_name: Option<String>,
 }
 
 // Response
@@ -2,5 +2,6 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct LogoutDto {
-    pub email: String,
+    // The refresh token of the session to end, the other sessions of the user are left alone
+    pub refresh_token: String,
 }
@@ -8,6 +8,7 @@ pub mod logout_dto;
 pub mod register_dto;
 pub mod reset_pass_dto;
 pub mod send_token_dto;
+pub mod two_factor_dto;
 pub mod update_user_dto;
 pub mod user_response_dto;
 mod verify_reset_pwd_pin_dto;
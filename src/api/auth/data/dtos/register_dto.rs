@@ -6,4 +6,7 @@ pub struct RegisterDto {
     pub password: String,
     pub first_name: String,
     pub last_name: String,
+    /// e.g. `fr`, taken from `Accept-Language` when missing
+    #[serde(default)]
+    pub locale: Option<String>,
 }
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Request
+#[derive(Debug, Deserialize)]
+pub struct EnrollTotpDto {
+    // The password is asked again, a stolen access token is not enough to set up an app
+    pub password: String,
+}
+
+// Request
+#[derive(Debug, Deserialize)]
+pub struct TotpCodeDto {
+    // A code of the authenticator app, or a recovery code where accepted
+    pub code: String,
+}
+
+// Request
+#[derive(Debug, Deserialize)]
+pub struct TwoFactorLoginDto {
+    pub challenge_token: String,
+    pub code: String,
+    #[serde(default)]
+    pub device_name: Option<String>,
+}
+
+// Response
+#[derive(Debug, Serialize)]
+pub struct TotpEnrollmentResponseDto {
+    pub secret: String,
+    pub provisioning_uri: String,
+}
+
+// Response
+#[derive(Debug, Serialize)]
+pub struct RecoveryCodesResponseDto {
+    // Shown once, only their hashes are stored
+    pub recovery_codes: Vec<String>,
+}
+
+// Response
+#[derive(Debug, Serialize)]
+pub struct TwoFactorChallengeResponseDto {
+    pub two_factor_required: bool,
+    pub challenge_token: String,
+    pub expires_at: DateTime<Utc>,
+}
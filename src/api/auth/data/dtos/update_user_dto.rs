@@ -18,6 +18,7 @@ pub struct UpdateUserDto {
     pub activation_token: Option<String>,
     pub activation_count: Option<i32>,
     pub banned: Option<bool>,
+    pub locale: Option<String>,
 }
 
 impl UpdateUserDto {
@@ -45,9 +46,15 @@ impl UpdateUserDto {
                 Some(125),
             )?;
         }
+        if let Some(locale) = &self.locale {
+            user.locale = Some(Validators::validate_locale(locale)?);
+        }
         if let Some(role) = self.role.clone() {
             user.role = role;
         }
+        if let Some(banned) = self.banned {
+            user.banned = banned;
+        }
 
         Ok(user)
     }
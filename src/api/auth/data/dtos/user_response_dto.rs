@@ -18,6 +18,9 @@ pub struct UserResponseDto {
     pub activation_count: i32,
     pub is_logged_out: bool,
     pub banned: bool,
+    pub locale: Option<String>,
+    #[serde(default)]
+    pub totp_enabled: bool,
 }
 
 impl From<User> for UserResponseDto {
@@ -35,6 +38,8 @@ impl From<User> for UserResponseDto {
             activation_count: user.activation_count,
             is_logged_out: user.is_logged_out,
             banned: user.banned,
+            locale: user.locale,
+            totp_enabled: user.totp_enabled,
         }
     }
 }
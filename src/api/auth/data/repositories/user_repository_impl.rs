@@ -1,13 +1,17 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::{
     api::auth::{
         data::{datasource::user_datasource::UserDataSource, UserMongoModel},
-        domain::{entities::User, repositories::user_repository::UserRepository},
+        domain::{
+            entities::{user::TwoFactorBackoff, User},
+            repositories::user_repository::UserRepository,
+        },
     },
-    core::CrudRepositoryImpl,
+    core::{AppError, CrudRepositoryImpl},
 };
 
 pub struct UserRepositoryImpl {
@@ -30,4 +34,38 @@ impl CrudRepositoryImpl<User, UserMongoModel, dyn UserDataSource> for UserReposi
 }
 
 #[async_trait]
-impl UserRepository for UserRepositoryImpl {}
+impl UserRepository for UserRepositoryImpl {
+    async fn reserve_two_factor_attempt(
+        &self,
+        user_id: &str,
+        now: DateTime<Utc>,
+        backoff: &TwoFactorBackoff,
+    ) -> Result<Option<User>, AppError> {
+        self.datasource
+            .reserve_two_factor_attempt(user_id, now, backoff)
+            .await
+    }
+
+    async fn use_totp_step(&self, user_id: &str, step: i64) -> Result<bool, AppError> {
+        self.datasource.use_totp_step(user_id, step).await
+    }
+
+    async fn use_recovery_code(&self, user_id: &str, code_hash: &str) -> Result<bool, AppError> {
+        self.datasource.use_recovery_code(user_id, code_hash).await
+    }
+
+    async fn reset_two_factor_attempts(&self, user_id: &str) -> Result<(), AppError> {
+        self.datasource.reset_two_factor_attempts(user_id).await
+    }
+
+    async fn set_second_factors(
+        &self,
+        user_id: &str,
+        totp_last_step: Option<i64>,
+        recovery_codes: &[String],
+    ) -> Result<(), AppError> {
+        self.datasource
+            .set_second_factors(user_id, totp_last_step, recovery_codes)
+            .await
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::api::auth::domain::entities::user_role::UserRole;
+use crate::api::{
+    api_key::domain::entities::api_key::ApiKey,
+    auth::domain::entities::{user_role::UserRole, User},
+};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
@@ -10,10 +13,33 @@ pub struct Claims {
     pub last_name: String,
     pub email: String,
     pub exp: usize,
-    // The api_key will only be used when api calls are used.
-    // It will be used to compare against the stored hash version in the user's record.
+    /* ··········································································· [ Registered ] */
+    // Issuer and audience, checked against `Config::jwt_issuer` and `Config::jwt_audience`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    // Issued at and not before, the tokens issued before these were added have neither
     #[serde(default)]
+    pub iat: usize,
+    #[serde(default)]
+    pub nbf: usize,
+    // Unique id of the token
+    #[serde(default)]
+    pub jti: String,
+    // Session of the access token, see `Session`. The tokens issued before the sessions were
+    // introduced, and the API keys, have none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+    // Id of the API key the request was authenticated with, `None` for the access tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
+    // Scopes of the API key, see `Claims::has_scope`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+    // The user's locale, so that responses are translated without loading the user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
 }
 
 impl Claims {
@@ -25,6 +51,8 @@ impl Claims {
         email: String,
         expiration: usize,
     ) -> Self {
+        let now = chrono::Utc::now().timestamp() as usize;
+
         Self {
             user_id,
             user_role,
@@ -32,11 +60,47 @@ impl Claims {
             last_name,
             email,
             exp: expiration,
+            iss: None,
+            aud: None,
+            iat: now,
+            nbf: now,
+            jti: uuid::Uuid::new_v4().to_string(),
+            sid: None,
             api_key: None,
+            scopes: vec![],
+            locale: None,
         }
     }
 
+    /// The claims of a request authenticated with an API key of the user
+    pub fn for_api_key(user: &User, api_key: &ApiKey) -> Self {
+        let expiration = api_key.expires_at.map(|expires_at| expires_at.timestamp());
+
+        let mut claims = Self::new(
+            user.id.clone(),
+            user.role.clone(),
+            user.first_name.clone(),
+            user.last_name.clone(),
+            user.email.clone(),
+            expiration.unwrap_or(0) as usize,
+        );
+        claims.iat = api_key.created_at.timestamp() as usize;
+        claims.nbf = claims.iat;
+        // API keys are revoked on their own, not through the access tokens denylist
+        claims.jti = "".to_string();
+        claims.api_key = Some(api_key.id.clone());
+        claims.scopes = api_key.scopes.clone();
+        claims.locale = user.locale.clone();
+        claims
+    }
+
     pub fn is_admin(&self) -> bool {
         self.user_role.is_admin()
     }
+
+    /// Whether the request may reach a route requiring `scope`. Access tokens hold every scope,
+    /// and so do the API keys created without any
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.api_key.is_none() || self.scopes.is_empty() || self.scopes.iter().any(|s| s == scope)
+    }
 }
@@ -1,12 +1,50 @@
 use bcrypt::{hash, verify, DEFAULT_COST};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 
 use crate::{
     api::auth::domain::entities::user_role::UserRole,
-    core::{rand_token_service::TokenService, AppError, MsgBuilder},
+    core::{rand_token_service::TokenService, AppError, MsgBuilder, TotpService},
 };
 const MAX_RESET_PWD_ATTEMPTS: i32 = 5;
 const MAX_RESEND_ATTEMPTS: i32 = 5;
+const TWO_FACTOR_CHALLENGE_TTL_SECONDS: i64 = 300;
+const RECOVERY_CODES_COUNT: usize = 10;
+
+/// 5 two-factor codes may be tried in a row, then the user is locked out for 30 seconds, doubled
+/// with each further try up to an hour
+pub const TWO_FACTOR_BACKOFF: TwoFactorBackoff = TwoFactorBackoff {
+    free_attempts: 5,
+    base_seconds: 30,
+    max_seconds: 3600,
+};
+
+/// Lockout of the users who enter too many wrong two-factor codes, whatever the login they are
+/// entered for. Once `free_attempts` codes are tried in a row without success, each further one
+/// doubles the lockout, from `base_seconds` up to `max_seconds`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TwoFactorBackoff {
+    pub free_attempts: i32,
+    pub base_seconds: i64,
+    pub max_seconds: i64,
+}
+
+impl TwoFactorBackoff {
+    /// Until when the codes are refused once `attempts` of them were tried in a row, if at all
+    pub fn locked_until(&self, attempts: i32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if attempts < self.free_attempts {
+            return None;
+        }
+        let seconds = 2i64
+            .checked_pow((attempts - self.free_attempts) as u32)
+            .map_or(self.max_seconds, |factor| {
+                factor
+                    .saturating_mul(self.base_seconds)
+                    .min(self.max_seconds)
+            });
+
+        Some(now + Duration::seconds(seconds))
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct User {
@@ -23,6 +61,26 @@ pub struct User {
     pub is_logged_out: bool,
     pub verified: bool,
     pub banned: bool,
+    /// Language of the emails sent to the user, e.g. `fr` or `ar`
+    pub locale: Option<String>,
+    /// The access tokens issued before are rejected, see `User::revoke_access_tokens`
+    pub tokens_valid_after: Option<DateTime<Utc>>,
+    /* ···························································· [ Two-factor authentication ] */
+    /// Base32 secret of the authenticator app, pending until `totp_enabled` (see
+    /// `User::confirm_totp`)
+    pub totp_secret: Option<String>,
+    pub totp_enabled: bool,
+    /// Time step of the last code accepted, the codes cannot be replayed
+    pub totp_last_step: Option<i64>,
+    /// Hashes of the recovery codes not used yet
+    pub recovery_codes: Vec<String>,
+    /// Hash of the token of a pending two-step login, see `User::start_two_factor_challenge`
+    pub two_factor_challenge: Option<String>,
+    pub two_factor_challenge_expires_at: Option<DateTime<Utc>>,
+    /// Two-factor codes tried in a row without success, across the logins (see
+    /// `TwoFactorBackoff`)
+    pub two_factor_attempts: i32,
+    pub two_factor_locked_until: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -43,6 +101,16 @@ impl User {
             activation_token: None,
             activation_count: 0,
             is_logged_out: true,
+            locale: None,
+            tokens_valid_after: None,
+            totp_secret: None,
+            totp_enabled: false,
+            totp_last_step: None,
+            recovery_codes: vec![],
+            two_factor_challenge: None,
+            two_factor_challenge_expires_at: None,
+            two_factor_attempts: 0,
+            two_factor_locked_until: None,
             created_at: now,
         }
     }
@@ -137,10 +205,131 @@ impl User {
 
     pub fn log_out(&mut self) -> () {
         self.is_logged_out = true;
+        self.revoke_access_tokens();
         ()
     }
+
+    /// Rejects every access token issued so far, e.g. once the user is banned or changes role or
+    /// email
+    pub fn revoke_access_tokens(&mut self) {
+        self.tokens_valid_after = Some(Utc::now());
+    }
     pub fn log_in(&mut self) -> () {
         self.is_logged_out = false;
         ()
     }
+
+    /* ···························································· [ Two-factor authentication ] */
+    /// Starts the enrollment of an authenticator app, returns its secret. Two-factor
+    /// authentication is enabled once a first code is confirmed, see `User::confirm_totp`
+    pub fn enroll_totp(&mut self) -> Result<String, AppError> {
+        if self.totp_enabled {
+            let msg = MsgBuilder::custom("Two-factor authentication is already enabled");
+            return Err(AppError::Forbidden(msg));
+        }
+
+        let secret = TotpService::generate_secret();
+        self.totp_secret = Some(secret.clone());
+
+        Ok(secret)
+    }
+
+    /// Enables two-factor authentication with a first code of the app, returns the recovery codes
+    pub fn confirm_totp(&mut self, code: &str) -> Result<Vec<String>, AppError> {
+        if self.totp_enabled {
+            let msg = MsgBuilder::custom("Two-factor authentication is already enabled");
+            return Err(AppError::Forbidden(msg));
+        }
+        let Some(secret) = &self.totp_secret else {
+            let msg = MsgBuilder::custom("Two-factor authentication has not been set up yet");
+            return Err(AppError::Forbidden(msg));
+        };
+
+        let Some(step) = TotpService::verify(secret, code, Utc::now(), None) else {
+            return Err(Self::invalid_two_factor_code());
+        };
+        self.totp_enabled = true;
+        self.totp_last_step = Some(step);
+
+        Ok(self.reset_recovery_codes())
+    }
+
+    /// Disables two-factor authentication, once a code was checked (see `VerifySecondFactor`)
+    pub fn disable_totp(&mut self) {
+        self.totp_secret = None;
+        self.totp_enabled = false;
+        self.totp_last_step = None;
+        self.recovery_codes = vec![];
+        self.two_factor_attempts = 0;
+        self.two_factor_locked_until = None;
+        self.clear_two_factor_challenge();
+    }
+
+    /// The time step of a code of the authenticator app, unless it is wrong or was used already
+    pub fn totp_step(&self, code: &str, now: DateTime<Utc>) -> Option<i64> {
+        let secret = self.totp_secret.as_ref().filter(|_| self.totp_enabled)?;
+        TotpService::verify(secret, code, now, self.totp_last_step)
+    }
+
+    /// Starts a two-step login once the password checks out, returns the challenge token to
+    /// exchange along with a code (see `TwoFactorLoginHandler`)
+    pub fn start_two_factor_challenge(&mut self) -> String {
+        let token = TokenService::generate_secret_token();
+        self.two_factor_challenge = Some(TokenService::hash_token(&token));
+        self.two_factor_challenge_expires_at =
+            Some(Utc::now() + Duration::seconds(TWO_FACTOR_CHALLENGE_TTL_SECONDS));
+
+        token
+    }
+
+    /// Checks that the two-step login is still pending
+    pub fn check_two_factor_challenge(&self) -> Result<(), AppError> {
+        let expired = self
+            .two_factor_challenge_expires_at
+            .is_none_or(|expires_at| expires_at <= Utc::now());
+        if self.two_factor_challenge.is_none() || expired {
+            return Err(Self::expired_two_factor_challenge());
+        }
+
+        Ok(())
+    }
+
+    pub fn clear_two_factor_challenge(&mut self) {
+        self.two_factor_challenge = None;
+        self.two_factor_challenge_expires_at = None;
+    }
+
+    pub fn expired_two_factor_challenge() -> AppError {
+        let msg = MsgBuilder::custom("This login attempt has expired. Please login to continue");
+        AppError::Unauthorized(msg)
+    }
+
+    pub fn invalid_two_factor_code() -> AppError {
+        let msg = MsgBuilder::custom("Invalid two-factor authentication code");
+        AppError::AuthenticationFailed(msg)
+    }
+
+    pub fn totp_not_enabled() -> AppError {
+        let msg = MsgBuilder::custom("Two-factor authentication is not enabled");
+        AppError::Forbidden(msg)
+    }
+
+    pub fn two_factor_locked() -> AppError {
+        let msg = MsgBuilder::custom(
+            "Too many wrong two-factor authentication codes. Please try again later",
+        );
+        AppError::Forbidden(msg)
+    }
+
+    /* ····································································· [ Helper functions ] */
+    // New recovery codes replacing the previous ones, only their hashes are kept
+    fn reset_recovery_codes(&mut self) -> Vec<String> {
+        let codes = TotpService::generate_recovery_codes(RECOVERY_CODES_COUNT);
+        self.recovery_codes = codes
+            .iter()
+            .map(|code| TotpService::hash_recovery_code(code))
+            .collect();
+
+        codes
+    }
 }
@@ -1,9 +1,10 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::{
     api::auth::{
         data::{datasource::user_datasource::UserDataSource, UserMongoModel},
-        domain::entities::User,
+        domain::entities::{user::TwoFactorBackoff, User},
     },
     core::{AppError, CrudRepository},
 };
@@ -12,4 +13,28 @@ use crate::{
 pub trait UserRepository:
     CrudRepository<User, UserMongoModel, AppError, dyn UserDataSource>
 {
+    /// See `UserDataSource::reserve_two_factor_attempt`
+    async fn reserve_two_factor_attempt(
+        &self,
+        user_id: &str,
+        now: DateTime<Utc>,
+        backoff: &TwoFactorBackoff,
+    ) -> Result<Option<User>, AppError>;
+
+    /// See `UserDataSource::use_totp_step`
+    async fn use_totp_step(&self, user_id: &str, step: i64) -> Result<bool, AppError>;
+
+    /// See `UserDataSource::use_recovery_code`
+    async fn use_recovery_code(&self, user_id: &str, code_hash: &str) -> Result<bool, AppError>;
+
+    /// See `UserDataSource::reset_two_factor_attempts`
+    async fn reset_two_factor_attempts(&self, user_id: &str) -> Result<(), AppError>;
+
+    /// See `UserDataSource::set_second_factors`
+    async fn set_second_factors(
+        &self,
+        user_id: &str,
+        totp_last_step: Option<i64>,
+        recovery_codes: &[String],
+    ) -> Result<(), AppError>;
 }
@@ -10,3 +10,7 @@ pub mod user_get;
 pub use user_get::*;
 pub mod add_one_user;
 pub mod user_delete_many;
+pub mod verify_second_factor_usecase;
+pub use verify_second_factor_usecase::*;
+pub mod set_second_factors_usecase;
+pub use set_second_factors_usecase::*;
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::auth::domain::repositories::user_repository::UserRepository,
+    core::{AppError, UseCase},
+};
+
+pub struct SetSecondFactorsParams {
+    pub user_id: String,
+    pub totp_last_step: Option<i64>,
+    /// Hashes of the recovery codes
+    pub recovery_codes: Vec<String>,
+}
+
+/// Saves the second factors of the user once two-factor authentication is enabled or disabled,
+/// see `UserDataSource::set_second_factors`
+pub struct SetSecondFactors {
+    repository: Arc<dyn UserRepository>,
+}
+
+impl SetSecondFactors {
+    pub fn new(repository: Arc<dyn UserRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<SetSecondFactorsParams, ()> for SetSecondFactors {
+    async fn execute(&self, params: SetSecondFactorsParams) -> Result<(), AppError> {
+        self.repository
+            .set_second_factors(
+                &params.user_id,
+                params.totp_last_step,
+                &params.recovery_codes,
+            )
+            .await
+    }
+}
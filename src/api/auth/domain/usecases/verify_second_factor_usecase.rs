@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api::auth::domain::{
+        entities::{user::TWO_FACTOR_BACKOFF, User},
+        repositories::user_repository::UserRepository,
+    },
+    core::{AppError, TotpService, UseCase},
+};
+
+pub struct VerifySecondFactorParams {
+    pub user: User,
+    /// A code of the authenticator app or a recovery code
+    pub code: String,
+}
+
+/// Checks a second factor of the user and uses it up: a code of the authenticator app cannot be
+/// replayed and a recovery code is removed. Every try counts towards the lockout of the user
+/// (see `TwoFactorBackoff`), until a right code is entered
+pub struct VerifySecondFactor {
+    repository: Arc<dyn UserRepository>,
+}
+
+impl VerifySecondFactor {
+    pub fn new(repository: Arc<dyn UserRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<VerifySecondFactorParams, ()> for VerifySecondFactor {
+    async fn execute(&self, params: VerifySecondFactorParams) -> Result<(), AppError> {
+        if !params.user.totp_enabled {
+            return Err(User::totp_not_enabled());
+        }
+
+        // The try is counted before the code is checked, so that concurrent tries cannot get
+        // past the lockout. The user is read again along with it, with the codes used so far
+        let now = Utc::now();
+        let user = self
+            .repository
+            .reserve_two_factor_attempt(&params.user.id, now, &TWO_FACTOR_BACKOFF)
+            .await?
+            .ok_or_else(User::two_factor_locked)?;
+
+        let used = match user.totp_step(&params.code, now) {
+            Some(step) => self.repository.use_totp_step(&user.id, step).await?,
+            None => {
+                let hash = TotpService::hash_recovery_code(&params.code);
+                self.repository.use_recovery_code(&user.id, &hash).await?
+            }
+        };
+        if !used {
+            return Err(User::invalid_two_factor_code());
+        }
+
+        self.repository.reset_two_factor_attempts(&user.id).await
+    }
+}
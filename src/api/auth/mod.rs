@@ -34,6 +34,14 @@ pub struct UserFeature {
     /// [GET] /user
     get_many_users_handler: Arc<GetManyUsersHandler>,
     verify_reset_pwd_token: Arc<VerifyResetPwdTokenHandler>,
+    /// [POST] /login/two-factor
+    two_factor_login_handler: Arc<TwoFactorLoginHandler>,
+    /// [POST] /two-factor/enroll
+    enroll_totp_handler: Arc<EnrollTotpHandler>,
+    /// [POST] /two-factor/confirm
+    confirm_totp_handler: Arc<ConfirmTotpHandler>,
+    /// [POST] /two-factor/disable
+    disable_totp_handler: Arc<DisableTotpHandler>,
 }
 
 impl UserFeature {
@@ -58,6 +66,10 @@ impl UserFeature {
             get_many_users_handler: Arc::new(GetManyUsersHandler::new(sl.clone())),
             get_many_users_emails_handler: Arc::new(GetManyUsersEmailsHandler::new(sl.clone())),
             verify_reset_pwd_token: Arc::new(VerifyResetPwdTokenHandler::new(sl.clone())),
+            two_factor_login_handler: Arc::new(TwoFactorLoginHandler::new(sl.clone())),
+            enroll_totp_handler: Arc::new(EnrollTotpHandler::new(sl.clone())),
+            confirm_totp_handler: Arc::new(ConfirmTotpHandler::new(sl.clone())),
+            disable_totp_handler: Arc::new(DisableTotpHandler::new(sl.clone())),
         }
     }
 
@@ -65,9 +77,19 @@ impl UserFeature {
         self: Arc<Self>,
         event_handler: Arc<E>,
     ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        // [POST] api/login/two-factor, ahead of `login` which matches its prefix
+        let login_routes = Arc::clone(&self.two_factor_login_handler)
+            .route()
+            .or(Arc::clone(&self.login_handler).route());
+        // [POST] api/two-factor/...
+        let two_factor_routes = Arc::clone(&self.enroll_totp_handler)
+            .route()
+            .or(Arc::clone(&self.confirm_totp_handler).route())
+            .or(Arc::clone(&self.disable_totp_handler).route());
+
         Arc::clone(&self.register_user_handler)
             .route(event_handler.clone())
-            .or(Arc::clone(&self.login_handler).route())
+            .or(login_routes)
             .or(Arc::clone(&self.activate_account_handler).route())
             .or(Arc::clone(&self.forgot_password_handler).route())
             .or(Arc::clone(&self.reset_password_handler).route())
@@ -90,5 +112,10 @@ impl UserFeature {
             // [DELETE] api/users
             .or(Arc::clone(&self.delete_many_users_handler).route())
             .or(Arc::clone(&self.verify_reset_pwd_token).route())
+            .or(two_factor_routes)
+            // Boxed to erase the type of the chain, too deep for the compiler to combine it with
+            // the other features otherwise
+            .map(|reply| Box::new(reply) as Box<dyn Reply>)
+            .boxed()
     }
 }
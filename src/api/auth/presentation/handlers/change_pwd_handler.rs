@@ -7,7 +7,11 @@ use crate::{
         data::dtos::change_pwd_dto::ChangePwdRDto,
         domain::entities::{Claims, User},
     },
-    core::{middleware::auth_middleware, response::ApiResponse, AppError, MsgBuilder, UseCase},
+    core::{
+        middleware::{auth_middleware, session_middleware},
+        response::ApiResponse,
+        AppError, MsgBuilder, UseCase,
+    },
     di::ServiceLocator,
 };
 
@@ -60,7 +64,7 @@ impl ChangePwdHandler {
             .and(warp::post())
             .and(warp::path::end())
             .and(warp::body::json())
-            .and(auth_middleware(self.sl.jwt_service()))
+            .and(auth_middleware(self.sl.authenticator()).and_then(session_middleware))
             .and_then(move |dto: ChangePwdRDto, claims: Claims| {
                 let handler = self.clone();
                 async move { handler.handle(dto, claims).await }
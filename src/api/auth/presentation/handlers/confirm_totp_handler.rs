@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::auth::{
+        data::dtos::two_factor_dto::{RecoveryCodesResponseDto, TotpCodeDto},
+        domain::{
+            entities::{Claims, User},
+            usecases::SetSecondFactorsParams,
+        },
+    },
+    core::{
+        middleware::{auth_middleware, session_middleware},
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// Enables two-factor authentication with a first code of the enrolled app, returns the recovery
+/// codes
+pub struct ConfirmTotpHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl ConfirmTotpHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        dto: TotpCodeDto,
+        claims: Claims,
+    ) -> Result<impl Reply, Rejection> {
+        let mut user: User = self
+            .sl
+            .get_user_by_id_usecase()
+            .execute(claims.user_id)
+            .await?;
+        user.is_allowed()?;
+
+        let recovery_codes = user.confirm_totp(&dto.code)?;
+        // The second factors are saved before two-factor authentication is enabled
+        let params = SetSecondFactorsParams {
+            user_id: user.id.clone(),
+            totp_last_step: user.totp_last_step,
+            recovery_codes: user.recovery_codes.clone(),
+        };
+        self.sl.set_second_factors().execute(params).await?;
+        self.sl.update_user_usecase().execute(user).await?;
+
+        //* Success ············································································· */
+        let response_body = RecoveryCodesResponseDto { recovery_codes };
+        let msg = MsgBuilder::custom(
+            "Two-factor authentication enabled successfully! Keep your recovery codes somewhere safe",
+        );
+        let response = ApiResponse::success(msg, Some(response_body));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("two-factor" / "confirm")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(auth_middleware(self.sl.authenticator()).and_then(session_middleware))
+            .and_then(move |dto: TotpCodeDto, claims: Claims| {
+                let handler = self.clone();
+                async move { handler.handle(dto, claims).await }
+            })
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::auth::{
+        data::dtos::two_factor_dto::TotpCodeDto,
+        domain::{
+            entities::{Claims, User},
+            usecases::{SetSecondFactorsParams, VerifySecondFactorParams},
+        },
+    },
+    core::{
+        middleware::{auth_middleware, session_middleware},
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// Disables two-factor authentication, with a code of the app or a recovery code
+pub struct DisableTotpHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl DisableTotpHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        dto: TotpCodeDto,
+        claims: Claims,
+    ) -> Result<impl Reply, Rejection> {
+        let mut user: User = self
+            .sl
+            .get_user_by_id_usecase()
+            .execute(claims.user_id)
+            .await?;
+        user.is_allowed()?;
+
+        let params = VerifySecondFactorParams {
+            user: user.clone(),
+            code: dto.code,
+        };
+        self.sl.verify_second_factor().execute(params).await?;
+
+        user.disable_totp();
+        let params = SetSecondFactorsParams {
+            user_id: user.id.clone(),
+            totp_last_step: user.totp_last_step,
+            recovery_codes: user.recovery_codes.clone(),
+        };
+        self.sl.update_user_usecase().execute(user).await?;
+        self.sl.set_second_factors().execute(params).await?;
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::custom("Two-factor authentication disabled successfully!");
+        let response = ApiResponse::<()>::success(msg, None);
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("two-factor" / "disable")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(auth_middleware(self.sl.authenticator()).and_then(session_middleware))
+            .and_then(move |dto: TotpCodeDto, claims: Claims| {
+                let handler = self.clone();
+                async move { handler.handle(dto, claims).await }
+            })
+    }
+}
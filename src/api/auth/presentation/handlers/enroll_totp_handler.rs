@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::auth::{
+        data::dtos::two_factor_dto::{EnrollTotpDto, TotpEnrollmentResponseDto},
+        domain::entities::{Claims, User},
+    },
+    core::{
+        middleware::{auth_middleware, session_middleware},
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// Sets up an authenticator app for the logged in user. Two-factor authentication is enabled once
+/// a first code is confirmed (see `ConfirmTotpHandler`)
+pub struct EnrollTotpHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl EnrollTotpHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        dto: EnrollTotpDto,
+        claims: Claims,
+    ) -> Result<impl Reply, Rejection> {
+        let mut user: User = self
+            .sl
+            .get_user_by_id_usecase()
+            .execute(claims.user_id)
+            .await?;
+        user.is_allowed()?;
+        user.verify_pwd(dto.password)?;
+
+        // Enrolling again replaces a secret that was never confirmed
+        let secret = user.enroll_totp()?;
+        self.sl.update_user_usecase().execute(user.clone()).await?;
+
+        //* Success ············································································· */
+        let response_body = TotpEnrollmentResponseDto {
+            provisioning_uri: self
+                .sl
+                .totp_service()
+                .provisioning_uri(&secret, &user.email),
+            secret,
+        };
+        let msg = MsgBuilder::custom(
+            "Add this account to your authenticator app, then confirm it with a first code",
+        );
+        let response = ApiResponse::success(msg, Some(response_body));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("two-factor" / "enroll")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(auth_middleware(self.sl.authenticator()).and_then(session_middleware))
+            .and_then(move |dto: EnrollTotpDto, claims: Claims| {
+                let handler = self.clone();
+                async move { handler.handle(dto, claims).await }
+            })
+    }
+}
@@ -4,7 +4,7 @@ use warp::{reject::Rejection, reply::Reply, Filter};
 
 use crate::{
     api::auth::{data::forgot_pwd_dto::ForgotPwdDto, domain::entities::User},
-    core::{response::ApiResponse, MsgBuilder, UseCase},
+    core::{middleware::locale_middleware, response::ApiResponse, MsgBuilder, UseCase},
     di::ServiceLocator,
 };
 
@@ -17,7 +17,7 @@ impl ForgotPassHandler {
         Self { sl }
     }
 
-    async fn handle(&self, dto: ForgotPwdDto) -> Result<impl Reply, Rejection> {
+    async fn handle(&self, dto: ForgotPwdDto, locale: String) -> Result<impl Reply, Rejection> {
         let mut filter = HashMap::new();
         filter.insert("email".to_string(), dto.email);
 
@@ -28,11 +28,20 @@ impl ForgotPassHandler {
         /* ··························································· [ Update Reset Pwd Token ] */
         user.set_reset_pwd_token()?;
 
-        self.sl.update_user_usecase().execute(user.clone()).await?;
-
+        // The token is only saved along with the email carrying it
         self.sl
-            .email_service()
-            .send_reset_pwd_email(&user.email, &user.reset_pwd_token.unwrap())
+            .transactions()
+            .run(|| async {
+                self.sl.update_user_usecase().execute(user.clone()).await?;
+                self.sl
+                    .email_service()
+                    .send_reset_pwd_email_localized(
+                        &user.email,
+                        user.reset_pwd_token.as_deref().unwrap_or_default(),
+                        Some(user.locale.as_deref().unwrap_or(&locale)),
+                    )
+                    .await
+            })
             .await?;
 
         let msg = MsgBuilder::custom("A reset password PIN has been sent to your email");
@@ -49,13 +58,14 @@ impl ForgotPassHandler {
             .and(warp::post())
             .and(warp::path::end())
             .and(warp::body::json())
-            .and_then(move |dto: ForgotPwdDto| {
+            .and(locale_middleware(self.sl.authenticator(), self.sl.i18n()))
+            .and_then(move |dto: ForgotPwdDto, locale: String| {
                 // Create a new Arc pointer that this closure owns
                 let handler = self.clone();
                 // This async block needs to own its data because it might run in the future
                 async move {
                     // Now we can use handler.handle() safely because we own this Arc
-                    handler.handle(dto).await
+                    handler.handle(dto, locale).await
                 }
             })
     }
@@ -8,7 +8,7 @@ use warp::{
 };
 
 use crate::core::errors::early_err_response;
-use crate::core::middleware::auth_middleware;
+use crate::core::middleware::scoped_auth_middleware;
 use crate::core::pagination::PaginatedParams;
 use crate::core::response::ApiResponse;
 use crate::core::UseCase;
@@ -71,7 +71,10 @@ impl GetManyUsersEmailsHandler {
     pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
         warp::path!("user" / "search" / "email")
             .and(warp::get())
-            .and(auth_middleware(self.sl.jwt_service()))
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "users:read",
+            ))
             .and(warp::query::<PaginatedParams>())
             .and_then(move |claims: Claims, params: PaginatedParams| {
                 let handler = self.clone();
@@ -3,19 +3,26 @@ use std::{collections::HashMap, sync::Arc};
 use warp::{
     http::StatusCode,
     reject::Rejection,
-    reply::{with_header, Reply},
+    reply::{with_header, Reply, Response},
     Filter,
 };
 
 use crate::{
     api::{
         auth::{
-            data::dtos::login_dto::{LoginDto, LoginResponseDto},
+            data::dtos::{
+                login_dto::{LoginDto, LoginResponseDto},
+                two_factor_dto::TwoFactorChallengeResponseDto,
+            },
             domain::entities::User,
         },
         auth_token::domain::entities::refresh_token::RefreshToken,
     },
-    core::{AppError, MsgBuilder, UseCase},
+    core::{
+        middleware::{client_middleware, ClientInfo},
+        rand_token_service::TokenService,
+        AppError, MsgBuilder, UseCase, Validators,
+    },
     di::ServiceLocator,
 };
 
@@ -28,7 +35,7 @@ impl LoginHandler {
         Self { sl }
     }
 
-    async fn handle(&self, params: LoginDto) -> Result<impl Reply, Rejection> {
+    async fn handle(&self, params: LoginDto, client: ClientInfo) -> Result<Response, Rejection> {
         /* ····························································· [ Check If User Exists ] */
         let mut filter = HashMap::new();
         filter.insert("email".to_string(), params.email.clone());
@@ -44,6 +51,36 @@ impl LoginHandler {
         /* ······························································ [ Is Password Correct ] */
         user.verify_pwd(params.password)?;
         user.is_allowed()?;
+
+        /* ························································ [ Two-factor authentication ] */
+        // The password alone is not enough, the client exchanges the challenge token together with
+        // a code at `login/two-factor` (see `TwoFactorLoginHandler`)
+        if user.totp_enabled {
+            let challenge_token = user.start_two_factor_challenge();
+            self.sl.update_user_usecase().execute(user.clone()).await?;
+
+            let response_data = TwoFactorChallengeResponseDto {
+                two_factor_required: true,
+                challenge_token,
+                expires_at: user.two_factor_challenge_expires_at.unwrap_or_default(),
+            };
+            let response = warp::reply::json(&response_data);
+            let response = warp::reply::with_status(response, StatusCode::OK);
+
+            return Ok(response.into_response());
+        }
+
+        Self::start_session(&self.sl, user, client, params.device_name).await
+    }
+
+    /// Issues the refresh and access tokens of a new session, once the user has entered all the
+    /// required credentials
+    pub async fn start_session(
+        sl: &ServiceLocator,
+        mut user: User,
+        client: ClientInfo,
+        device_name: Option<String>,
+    ) -> Result<Response, Rejection> {
         user.log_in();
 
         /* ······················································································ */
@@ -51,54 +88,62 @@ impl LoginHandler {
         // should generate a new refresh token and access token.
         /* ······················································································ */
         /* ··························································· [ Generate refresh token ] */
-        let refresh_token_value = self.sl.jwt_service().generate_jwt(&user)?;
+        // An opaque secret rather than a jwt, so that it can never be used as an access token. Only
+        // its hash is stored. It starts a new session on the client's device
+        let device_name = device_name
+            .map(|name| {
+                Validators::validate_text_len(name, Some("Device name".into()), Some(1), Some(125))
+            })
+            .transpose()?;
+        let refresh_token_value = TokenService::generate_secret_token();
 
         let refresh_token = RefreshToken::new(
             user.id.clone(),
-            refresh_token_value,
+            &refresh_token_value,
             user.role.clone(),
             None,
-        );
+        )
+        .with_client(client, device_name);
 
-        let refresh_token: RefreshToken =
-            match self.sl.create_refresh_token().execute(refresh_token).await {
-                Ok(result) => result,
-                Err(e) => {
-                    return Err(warp::reject::custom(e));
-                }
-            };
+        let refresh_token = match sl.create_refresh_token().execute(refresh_token).await {
+            Ok(refresh_token) => refresh_token,
+            Err(e) => return Err(warp::reject::custom(e)),
+        };
 
         /* ···························································· [ Generate access token ] */
-        let access_token = self.sl.jwt_service().generate_jwt(&user)?;
+        let access_token = sl
+            .jwt_service()
+            .generate_session_jwt(&user, &refresh_token.family_id)?;
 
         /* ······································································ [ Update User ] */
-        self.sl.update_user_usecase().execute(user.clone()).await?;
+        sl.update_user_usecase().execute(user.clone()).await?;
 
         /* ···························································· [ Prepare http response ] */
         // Construct the http response with auth jwt
         let response_data = LoginResponseDto {
             user: user.into(),
-            refresh_token: refresh_token.token,
+            refresh_token: refresh_token_value,
         };
 
         let response = warp::reply::json(&response_data);
         let response = with_header(response, "x-auth-token", &access_token);
         let response = warp::reply::with_status(response, StatusCode::OK);
 
-        Ok(response)
+        Ok(response.into_response())
     }
 
     pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
         warp::path("login")
             .and(warp::post())
             .and(warp::body::json())
-            .and_then(move |dto: LoginDto| {
+            .and(client_middleware())
+            .and_then(move |dto: LoginDto, client: ClientInfo| {
                 // Create a new Arc pointer that this closure owns
                 let handler = self.clone();
                 // This async block needs to own its data because it might run in the future
                 async move {
                     // Now we can use handler.handle() safely because we own this Arc
-                    handler.handle(dto).await
+                    handler.handle(dto, client).await
                 }
             })
     }
@@ -1,12 +1,21 @@
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 
 use crate::{
-    api::auth::{data::dtos::logout_dto::LogoutDto, domain::entities::User},
-    core::{response::ApiResponse, CommandUseCase, MsgBuilder, UseCase},
+    api::{
+        auth::{data::dtos::logout_dto::LogoutDto, domain::entities::Claims},
+        auth_token::domain::usecases::RevokeRefreshTokenParams,
+    },
+    core::{
+        middleware::{auth_middleware, session_middleware},
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
     di::ServiceLocator,
 };
 use warp::{reject::Rejection, reply::Reply, Filter};
 
+/// Ends the session of the request: its refresh token and the access token of the request are
+/// revoked. See `RevokeSessionsHandler` to log out everywhere else
 pub struct LogoutHandler {
     sl: Arc<ServiceLocator>,
 }
@@ -16,20 +25,66 @@ impl LogoutHandler {
         Self { sl }
     }
 
-    async fn handle(&self, params: LogoutDto) -> Result<impl Reply, Rejection> {
-        let mut filter = HashMap::new();
-        filter.insert("email".to_string(), params.email.clone());
-
-        let mut user: User = self.sl.get_user().execute(filter).await?;
+    async fn handle(&self, params: LogoutDto, claims: Claims) -> Result<impl Reply, Rejection> {
+        /* ····························································· [ Revoke Refresh Token ] */
+        // Only a refresh token of the session of the request is accepted
+        let refresh_token = self
+            .sl
+            .revoke_refresh_token()
+            .execute(RevokeRefreshTokenParams {
+                user_id: claims.user_id.clone(),
+                session_id: claims.sid.clone(),
+                refresh_token: params.refresh_token,
+            })
+            .await;
 
-        user.log_out();
+        /* ······························································ [ Revoke Access Token ] */
+        // The session of the request ends even if the refresh token was not accepted, and so do
+        // its other access tokens
+        self.sl.authenticator().revoke(&claims).await?;
+        let session_id = match (&claims.sid, &refresh_token) {
+            (Some(session_id), _) => Some(session_id.clone()),
+            // The tokens issued before the sessions were introduced have none
+            (None, Ok(refresh_token)) => Some(refresh_token.family_id.clone()),
+            (None, Err(_)) => None,
+        };
+        if let Some(session_id) = &session_id {
+            self.sl
+                .revoke_refresh_token_family()
+                .execute(session_id.clone())
+                .await?;
+            self.sl.authenticator().forget_session(session_id).await;
+        }
 
-        self.sl
-            .update_user_usecase()
-            .execute(user.clone().into())
+        /* ······································································ [ Update User ] */
+        // The user is logged out once its last session ends
+        let sessions = self
+            .sl
+            .get_sessions()
+            .execute(claims.user_id.clone())
             .await?;
+        if sessions.is_empty() {
+            let mut user = self
+                .sl
+                .get_user_by_id_usecase()
+                .execute(claims.user_id.clone())
+                .await?;
+            user.log_out();
+            self.sl.update_user_usecase().execute(user).await?;
+            self.sl.authenticator().forget_user(&claims.user_id).await;
+        }
 
-        self.sl.delete_refresh_tokens().execute(user.id).await?;
+        // The session ended all the same, a refresh token of another one is only worth a log. The
+        // legacy tokens without a session are not logged out without the right one
+        if let Err(err) = refresh_token {
+            match session_id {
+                Some(session_id) => eprintln!(
+                    "Logout of session {} with a refresh token not of it: {}",
+                    session_id, err
+                ),
+                None => return Err(err.into()),
+            }
+        }
 
         let msg = MsgBuilder::custom("Logout Success");
         let response = ApiResponse::<()>::success(msg, None);
@@ -45,14 +100,14 @@ impl LogoutHandler {
         warp::path("logout")
             .and(warp::post())
             .and(warp::body::json())
-            .and_then(move |dto: LogoutDto| {
-                // we should check if user can continue here ...
+            .and(auth_middleware(self.sl.authenticator()).and_then(session_middleware))
+            .and_then(move |dto: LogoutDto, claims: Claims| {
                 // Create a new Arc pointer that this closure owns
                 let handler = self.clone();
                 // This async block needs to own its data because it might run in the future
                 async move {
                     // Now we can use handler.handle() safely because we own this Arc
-                    handler.handle(dto).await
+                    handler.handle(dto, claims).await
                 }
             })
     }
@@ -36,3 +36,15 @@ pub use user_delete_many_handler::*;
 
 mod verify_rest_pwd_token_handler;
 pub use verify_rest_pwd_token_handler::*;
+
+mod enroll_totp_handler;
+pub use enroll_totp_handler::*;
+
+mod confirm_totp_handler;
+pub use confirm_totp_handler::*;
+
+mod disable_totp_handler;
+pub use disable_totp_handler::*;
+
+mod two_factor_login_handler;
+pub use two_factor_login_handler::*;
@@ -8,8 +8,8 @@ use crate::{
         domain::entities::User,
     },
     core::{
-        rand_token_service::TokenService, response::ApiResponse, AppError, CoreEventHandler,
-        MsgBuilder, UseCase, UserRegisteredEvent,
+        middleware::locale_middleware, rand_token_service::TokenService, response::ApiResponse,
+        AppError, CoreEventHandler, MsgBuilder, UseCase, UserRegisteredEvent, Validators,
     },
     di::ServiceLocator,
 };
@@ -27,6 +27,7 @@ impl RegisterUserHandler {
     async fn handle<E: CoreEventHandler + 'static>(
         &self,
         params: RegisterDto,
+        locale: String,
         event_handler: Arc<E>,
     ) -> Result<impl Reply, Rejection> {
         /* ····················································· [ Account Already Exists Check ] */
@@ -42,13 +43,33 @@ impl RegisterUserHandler {
         /* ································································ [ Create a new user ] */
         let mut user = User::new(params.email.clone(), params.first_name, params.last_name);
         user.set_pwd(params.password)?;
+        user.locale = Some(match &params.locale {
+            Some(locale) => Validators::validate_locale(locale)?,
+            None => locale,
+        });
 
         /* ························································· [ Account activation token ] */
         let activation_token = TokenService::generate_token();
         user.activation_token = Some(activation_token.clone());
 
-        /* ········································································· [ Add user ] */
-        user = self.sl.add_one_user().execute(user).await?;
+        /* ················································ [ Add user and queue activation email ] */
+        // Both or neither, a user never waits for an activation email that was not queued
+        let user = self
+            .sl
+            .transactions()
+            .run(|| async {
+                let user = self.sl.add_one_user().execute(user).await?;
+                self.sl
+                    .email_service()
+                    .send_activation_email_localized(
+                        &user.email,
+                        &activation_token,
+                        user.locale.as_deref(),
+                    )
+                    .await?;
+                Ok(user)
+            })
+            .await?;
 
         /* ······························································ [ Auth Event (if any) ] */
         let event = UserRegisteredEvent {
@@ -60,13 +81,6 @@ impl RegisterUserHandler {
             let _ = event_handler_clone.on_user_registered(&event_clone).await;
         });
 
-        /* ···························································· [ Send Activation Email ] */
-
-        self.sl
-            .email_service()
-            .send_activation_email(&user.email, &activation_token)
-            .await?;
-
         /* ································································· [ Success Response ] */
         let msg = MsgBuilder::created_success("Account");
         let response = ApiResponse::success(msg, Some(UserResponseDto::from(user)));
@@ -85,14 +99,15 @@ impl RegisterUserHandler {
         warp::path("register")
             .and(warp::post())
             .and(warp::body::json())
-            .and_then(move |dto: RegisterDto| {
+            .and(locale_middleware(self.sl.authenticator(), self.sl.i18n()))
+            .and_then(move |dto: RegisterDto, locale: String| {
                 // Create a new Arc pointer that this closure owns
                 let handler = self.clone();
                 let event_handler = Arc::clone(&event_handler);
                 // This async block needs to own its data because it might run in the future
                 async move {
                     // Now we can use handler.handle() safely because we own this Arc
-                    handler.handle(dto, event_handler).await
+                    handler.handle(dto, locale, event_handler).await
                 }
             })
     }
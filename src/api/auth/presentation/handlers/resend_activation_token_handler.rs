@@ -1,6 +1,6 @@
 use crate::{
     api::auth::{data::dtos::send_token_dto::SendTokenDto, domain::entities::User},
-    core::{response::ApiResponse, MsgBuilder, UseCase},
+    core::{middleware::locale_middleware, response::ApiResponse, MsgBuilder, UseCase},
     di::ServiceLocator,
 };
 use std::{collections::HashMap, sync::Arc};
@@ -14,7 +14,7 @@ impl ResendActivationTokenHandler {
         Self { sl }
     }
 
-    async fn handle(&self, params: SendTokenDto) -> Result<impl Reply, Rejection> {
+    async fn handle(&self, params: SendTokenDto, locale: String) -> Result<impl Reply, Rejection> {
         let mut filter = HashMap::new();
         filter.insert("email".to_string(), params.email.clone());
 
@@ -22,12 +22,21 @@ impl ResendActivationTokenHandler {
         user.set_activation_token()?;
 
         /* ······································································· [ Update User ] */
-        self.sl.update_user_usecase().execute(user.clone()).await?;
-
         /* ···························································· [ Send Email With Token ] */
+        // The token is only saved along with the email carrying it
         self.sl
-            .email_service()
-            .send_activation_email(&user.email, &user.clone().activation_token.unwrap())
+            .transactions()
+            .run(|| async {
+                self.sl.update_user_usecase().execute(user.clone()).await?;
+                self.sl
+                    .email_service()
+                    .send_activation_email_localized(
+                        &user.email,
+                        user.activation_token.as_deref().unwrap_or_default(),
+                        Some(user.locale.as_deref().unwrap_or(&locale)),
+                    )
+                    .await
+            })
             .await?;
 
         let msg = MsgBuilder::custom("An account verification PIN was sent to your email");
@@ -45,13 +54,14 @@ impl ResendActivationTokenHandler {
             .and(warp::post())
             .and(warp::path::end())
             .and(warp::body::json())
-            .and_then(move |dto: SendTokenDto| {
+            .and(locale_middleware(self.sl.authenticator(), self.sl.i18n()))
+            .and_then(move |dto: SendTokenDto, locale: String| {
                 // Create a new Arc pointer that this closure owns
                 let handler = self.clone();
                 // This async block needs to own its data because it might run in the future
                 async move {
                     // Now we can use handler.handle() safely because we own this Arc
-                    handler.handle(dto).await
+                    handler.handle(dto, locale).await
                 }
             })
     }
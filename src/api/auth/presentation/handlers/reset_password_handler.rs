@@ -3,7 +3,7 @@ use warp::{reject::Rejection, reply::Reply, Filter};
 
 use crate::{
     api::auth::{data::dtos::reset_pass_dto::ResetPasswordDto, domain::entities::User},
-    core::{response::ApiResponse, MsgBuilder, UseCase},
+    core::{middleware::locale_middleware, response::ApiResponse, MsgBuilder, UseCase},
     di::ServiceLocator,
 };
 
@@ -16,7 +16,11 @@ impl ResetPasswordHandler {
         Self { sl }
     }
 
-    async fn handle(self: Arc<Self>, params: ResetPasswordDto) -> Result<impl Reply, Rejection> {
+    async fn handle(
+        self: Arc<Self>,
+        params: ResetPasswordDto,
+        locale: String,
+    ) -> Result<impl Reply, Rejection> {
         let mut filter = HashMap::new();
         filter.insert("email".to_string(), params.email.clone());
 
@@ -26,13 +30,21 @@ impl ResetPasswordHandler {
         /* ········································································ [ Reset Pwd ] */
         user.re_set_pwd(params.new_password, params.token)?;
 
+        // The confirmation is only queued along with the new password
         self.sl
-            .email_service()
-            .send_pwd_reset_confirmation_email(&params.email)
+            .transactions()
+            .run(|| async {
+                self.sl
+                    .email_service()
+                    .send_pwd_reset_confirmation_email_localized(
+                        &params.email,
+                        Some(user.locale.as_deref().unwrap_or(&locale)),
+                    )
+                    .await?;
+                self.sl.update_user_usecase().execute(user.clone()).await
+            })
             .await?;
 
-        self.sl.update_user_usecase().execute(user).await?;
-
         let msg = MsgBuilder::custom("Password reset successfully");
         let response = ApiResponse::<()>::success(msg, None);
 
@@ -48,13 +60,14 @@ impl ResetPasswordHandler {
             .and(warp::post())
             .and(warp::path::end())
             .and(warp::body::json())
-            .and_then(move |dto: ResetPasswordDto| {
+            .and(locale_middleware(self.sl.authenticator(), self.sl.i18n()))
+            .and_then(move |dto: ResetPasswordDto, locale: String| {
                 // Create a new Arc pointer that this closure owns
                 let handler = self.clone();
                 // This async block needs to own its data because it might run in the future
                 async move {
                     // Now we can use handler.handle() safely because we own this Arc
-                    handler.handle(dto).await
+                    handler.handle(dto, locale).await
                 }
             })
     }
@@ -0,0 +1,80 @@
+use std::{collections::HashMap, sync::Arc};
+
+use warp::{
+    reject::Rejection,
+    reply::{Reply, Response},
+    Filter,
+};
+
+use crate::{
+    api::auth::{
+        data::dtos::two_factor_dto::TwoFactorLoginDto,
+        domain::{entities::User, usecases::VerifySecondFactorParams},
+        presentation::handlers::LoginHandler,
+    },
+    core::{
+        middleware::{client_middleware, ClientInfo},
+        rand_token_service::TokenService,
+        UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// Second step of the login of the users with two-factor authentication: the challenge token
+/// returned by `LoginHandler` is exchanged, together with a code, for the access and refresh tokens
+pub struct TwoFactorLoginHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl TwoFactorLoginHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        dto: TwoFactorLoginDto,
+        client: ClientInfo,
+    ) -> Result<Response, Rejection> {
+        // Only the hash of the challenge token is stored
+        let mut filter = HashMap::new();
+        filter.insert(
+            "two_factor_challenge".to_string(),
+            TokenService::hash_token(&dto.challenge_token),
+        );
+        let mut user: User = match self.sl.get_user().execute(filter).await {
+            Ok(user) => user,
+            Err(_) => return Err(warp::reject::custom(User::expired_two_factor_challenge())),
+        };
+        user.is_allowed()?;
+        user.check_two_factor_challenge()?;
+
+        // The wrong codes count towards the lockout of the user, whatever the challenge
+        let params = VerifySecondFactorParams {
+            user: user.clone(),
+            code: dto.code,
+        };
+        self.sl.verify_second_factor().execute(params).await?;
+        user.clear_two_factor_challenge();
+
+        //* Success ············································································· */
+        LoginHandler::start_session(&self.sl, user, client, dto.device_name).await
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("login" / "two-factor")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(client_middleware())
+            .and_then(move |dto: TwoFactorLoginDto, client: ClientInfo| {
+                let handler = self.clone();
+                async move { handler.handle(dto, client).await }
+            })
+    }
+}
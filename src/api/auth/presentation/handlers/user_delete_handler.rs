@@ -1,11 +1,11 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use warp::{reject::Rejection, reply::Reply, Filter};
 
 use crate::{
     api::auth::{data::dtos::delete_user_dto::DeleteUserDto, domain::entities::Claims},
     core::{
-        middleware::{auth_middleware, owner_or_admin_middleware},
+        middleware::{owner_or_admin_middleware, scoped_auth_middleware},
         response::ApiResponse,
         CommandUseCase, CoreEventHandler, MsgBuilder, UseCase, UserDeletedEvent,
     },
@@ -32,12 +32,34 @@ impl DeleteUserHandler {
             .execute(user_id.to_string())
             .await?;
 
+        // Its access tokens are rejected from now on
+        self.sl.authenticator().forget_user(&user_id).await;
+
+        /* ························································ [ Delete User's Storage ] */
+        // The user is gone already, leftovers are caught by the orphan sweeper
+        let storage = self.sl.storage_service();
+        let user_dirs = &storage.config().user_dirs;
+        if let Err(e) = storage
+            .delete_entity_storage_dirs(&user_id, user_dirs)
+            .await
+        {
+            eprintln!("Could not delete the storage of user {}: {}", user_id, e);
+        }
+
         /* ······················································ [ Delete User's Refresh Token ] */
         self.sl
             .delete_refresh_tokens()
             .execute(user_id.to_string())
             .await?;
 
+        /* ··························································· [ Delete User's API Keys ] */
+        let mut api_keys_filter = HashMap::new();
+        api_keys_filter.insert("user_id".to_string(), user_id.clone());
+        self.sl
+            .delete_many_api_keys()
+            .execute(api_keys_filter)
+            .await?;
+
         /* ······························································ [ Auth Event (if any) ] */
         let event = UserDeletedEvent { user_id };
 
@@ -65,7 +87,10 @@ impl DeleteUserHandler {
             .and(warp::delete())
             .and(warp::path::end())
             .and(warp::body::json())
-            .and(auth_middleware(self.sl.jwt_service()))
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "users:write",
+            ))
             .and_then(move |dto: DeleteUserDto, claims: Claims| async move {
                 owner_or_admin_middleware(dto.user_id.clone(), claims).await?;
                 Ok::<DeleteUserDto, warp::Rejection>(dto)
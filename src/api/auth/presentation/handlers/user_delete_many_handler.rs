@@ -5,7 +5,7 @@ use warp::{reject::Rejection, reply::Reply, Filter};
 use crate::{
     api::auth::{data::delete_many_user_dto::DeleteManyUsersDto, domain::entities::Claims},
     core::{
-        middleware::{admin_middleware, auth_middleware},
+        middleware::{admin_middleware, scoped_auth_middleware},
         response::ApiResponse,
         CommandUseCase, MsgBuilder, UseCase,
     },
@@ -29,6 +29,22 @@ impl DeleteManyUserHandler {
         /* ······································································· [ Delete User ] */
         self.sl.delete_many_users().execute(filter).await?;
 
+        // Their access tokens are rejected from now on
+        let authenticator = self.sl.authenticator();
+        for user_id in &dto.ids {
+            authenticator.forget_user(user_id).await;
+        }
+
+        /* ························································ [ Delete Users' Storage ] */
+        // The users are gone already, leftovers are caught by the orphan sweeper
+        let storage = self.sl.storage_service();
+        let user_dirs = &storage.config().user_dirs;
+        for user_id in &dto.ids {
+            if let Err(e) = storage.delete_entity_storage_dirs(user_id, user_dirs).await {
+                eprintln!("Could not delete the storage of user {}: {}", user_id, e);
+            }
+        }
+
         /* ······················································ [ Delete User's Refresh Token ] */
         let mut tokens_filter = HashMap::new();
         tokens_filter.insert("user_id.in".to_string(), dto.ids.join(","));
@@ -37,6 +53,14 @@ impl DeleteManyUserHandler {
             .execute(tokens_filter)
             .await?;
 
+        /* ··························································· [ Delete User's API Keys ] */
+        let mut api_keys_filter = HashMap::new();
+        api_keys_filter.insert("user_id.in".to_string(), dto.ids.join(","));
+        self.sl
+            .delete_many_api_keys()
+            .execute(api_keys_filter)
+            .await?;
+
         /* ································································· [ Success Response ] */
         let msg = MsgBuilder::deleted_success("User");
         let response = ApiResponse::<()>::success(msg, None);
@@ -53,7 +77,10 @@ impl DeleteManyUserHandler {
         warp::path!("users")
             .and(warp::delete())
             .and(warp::body::json())
-            .and(auth_middleware(self.sl.jwt_service()))
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "users:write",
+            ))
             .and_then(move |dto: DeleteManyUsersDto, claims: Claims| async move {
                 admin_middleware(claims).await?;
                 Ok::<DeleteManyUsersDto, warp::Rejection>(dto)
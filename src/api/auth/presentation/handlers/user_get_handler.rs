@@ -9,8 +9,8 @@ use warp::{
 use crate::{
     api::auth::{data::user_response_dto::UserResponseDto, domain::entities::Claims},
     core::{
-        errors::early_err_response, middleware::auth_middleware, response::ApiResponse, AppError,
-        MsgBuilder, UseCase,
+        errors::early_err_response, middleware::scoped_auth_middleware, response::ApiResponse,
+        AppError, MsgBuilder, UseCase,
     },
     di::ServiceLocator,
 };
@@ -65,7 +65,10 @@ impl GetOneUserHandler {
             .and(warp::get())
             .and(warp::query::<HashMap<String, String>>())
             .and(warp::path::end())
-            .and(auth_middleware(self.sl.jwt_service()))
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "users:read",
+            ))
             .and_then(move |query: HashMap<String, String>, _: Claims| {
                 let handler = self.clone();
                 async move { handler.handle(query).await }
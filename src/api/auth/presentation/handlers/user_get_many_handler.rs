@@ -5,7 +5,7 @@ use warp::{reject::Rejection, reply::Reply};
 
 use crate::api::auth::data::user_response_dto::UserResponseDto;
 use crate::api::auth::domain::entities::Claims;
-use crate::core::middleware::auth_middleware;
+use crate::core::middleware::scoped_auth_middleware;
 use crate::core::pagination::PaginatedParams;
 use crate::core::pagination::PaginatedResponse;
 use crate::core::response::ApiResponse;
@@ -59,7 +59,10 @@ impl GetManyUsersHandler {
     pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
         warp::path!("user")
             .and(warp::get())
-            .and(auth_middleware(self.sl.jwt_service()))
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "users:read",
+            ))
             .and(warp::query::<PaginatedParams>())
             .and_then(move |_: Claims, params: PaginatedParams| {
                 let handler = self.clone();
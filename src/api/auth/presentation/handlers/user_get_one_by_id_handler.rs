@@ -10,7 +10,7 @@ use warp::{
 use crate::{
     api::auth::{data::user_response_dto::UserResponseDto, domain::entities::Claims},
     core::{
-        middleware::{auth_middleware, owner_or_admin_middleware},
+        middleware::{owner_or_admin_middleware, scoped_auth_middleware},
         response::ApiResponse,
         MsgBuilder, UseCase, Validators,
     },
@@ -59,7 +59,10 @@ impl GetUserByIdHandler {
     pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
         warp::path!("user" / String)
             .and(warp::get())
-            .and(auth_middleware(self.sl.jwt_service()))
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "users:read",
+            ))
             .and_then(move |user_id: String, claims: Claims| {
                 let handler = self.clone();
                 async move { handler.handle(user_id, claims).await }
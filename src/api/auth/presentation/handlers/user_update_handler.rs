@@ -8,7 +8,7 @@ use crate::{
         domain::entities::Claims,
     },
     core::{
-        middleware::{auth_middleware, owner_or_admin_middleware},
+        middleware::{owner_or_admin_middleware, scoped_auth_middleware},
         response::ApiResponse,
         MsgBuilder, UseCase,
     },
@@ -32,8 +32,18 @@ impl UpdateUserHandler {
             .execute(dto.id.clone())
             .await?;
 
+        let previous = user.clone();
         user = dto.apply_to(user)?;
 
+        // The access tokens carry the role and the email, and a banned user must not keep its
+        // session. The refresh tokens of a user still allowed in get it access tokens up to date
+        if user.role != previous.role
+            || user.email != previous.email
+            || (user.banned && !previous.banned)
+        {
+            user.revoke_access_tokens();
+        }
+
         match self.sl.update_user_usecase().execute(user.clone()).await {
             Ok(result) => result,
             Err(e) => {
@@ -41,6 +51,9 @@ impl UpdateUserHandler {
             }
         };
 
+        // Whether the user is banned is cached along with its revoked tokens
+        self.sl.authenticator().forget_user(&user.id).await;
+
         let user_dto = UserResponseDto::from(user);
         let msg = MsgBuilder::updated_success("User");
         let response = ApiResponse::success(msg, Some(user_dto));
@@ -58,7 +71,10 @@ impl UpdateUserHandler {
         warp::path!("user")
             .and(warp::put())
             .and(warp::body::json())
-            .and(auth_middleware(self.sl.jwt_service()))
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "users:write",
+            ))
             .and_then(move |dto: UpdateUserDto, claims: Claims| async move {
                 owner_or_admin_middleware(dto.id.clone(), claims.clone()).await?;
                 let mut dto = dto;
@@ -2,12 +2,25 @@ use std::sync::Arc;
 
 use mongodb::Database;
 
-use crate::api::auth_token::{
-    data::{
-        datasources::refresh_token_mongo_db::RefreshTokenMongoDatasourceImpl,
-        repositories::refresh_token_repository_impl::RefreshTokenRepositoryImpl,
+use crate::{
+    api::auth_token::{
+        data::{
+            datasources::{
+                refresh_token_datasource::RefreshTokenDatasource,
+                refresh_token_in_memory::RefreshTokenInMemoryDatasourceImpl,
+                refresh_token_mongo_db::RefreshTokenMongoDatasourceImpl,
+                revoked_access_token_datasource::RevokedAccessTokenDatasource,
+                revoked_access_token_in_memory::RevokedAccessTokenInMemoryDatasourceImpl,
+                revoked_access_token_mongo_db::RevokedAccessTokenMongoDatasourceImpl,
+            },
+            repositories::{
+                refresh_token_repository_impl::RefreshTokenRepositoryImpl,
+                revoked_access_token_repository_impl::RevokedAccessTokenRepositoryImpl,
+            },
+        },
+        domain::usecases::*,
     },
-    domain::usecases::*,
+    core::AppError,
 };
 
 pub struct AuthTokenDi {
@@ -16,15 +29,51 @@ pub struct AuthTokenDi {
     pub update_one_refresh_token: Arc<UpdateOneRefreshToken>,
     pub delete_user_refresh_tokens: Arc<DeleteRefreshTokens>,
     pub delete_many_refresh_tokens: Arc<DeleteManyRefreshTokens>,
+    pub revoke_refresh_token: Arc<RevokeRefreshToken>,
+    pub revoke_refresh_token_family: Arc<RevokeRefreshTokenFamily>,
+    pub revoke_access_token: Arc<RevokeAccessToken>,
+    pub is_access_token_revoked: Arc<IsAccessTokenRevoked>,
+    pub get_sessions: Arc<GetSessions>,
+    pub revoke_sessions: Arc<RevokeSessions>,
+    pub is_session_revoked: Arc<IsSessionRevoked>,
+    pub claim_refresh_token: Arc<ClaimRefreshToken>,
 }
 
 impl AuthTokenDi {
     pub fn new(db: &Database) -> Self {
         /* ························································ [ Datasource Implementation ] */
-        let database = Arc::new(RefreshTokenMongoDatasourceImpl::new(&db));
+        Self::with_datasources(
+            Arc::new(RefreshTokenMongoDatasourceImpl::new(db)),
+            Arc::new(RevokedAccessTokenMongoDatasourceImpl::new(db)),
+        )
+    }
+
+    /// Brings the collections of the feature up to date and indexes them, to be run once at startup
+    /// before serving
+    pub async fn prepare_database(db: &Database) -> Result<(), AppError> {
+        RefreshTokenMongoDatasourceImpl::new(db).prepare().await?;
+        RevokedAccessTokenMongoDatasourceImpl::new(db)
+            .prepare()
+            .await
+    }
+
+    /// Wires the feature against in-memory datasources (tests and local development)
+    pub fn in_memory() -> Self {
+        Self::with_datasources(
+            Arc::new(RefreshTokenInMemoryDatasourceImpl::new()),
+            Arc::new(RevokedAccessTokenInMemoryDatasourceImpl::new()),
+        )
+    }
 
+    pub fn with_datasources(
+        datasource: Arc<dyn RefreshTokenDatasource>,
+        revoked_access_token_datasource: Arc<dyn RevokedAccessTokenDatasource>,
+    ) -> Self {
         /* ························································ [ Repository Implementation ] */
-        let repository = Arc::new(RefreshTokenRepositoryImpl::new(database));
+        let repository = Arc::new(RefreshTokenRepositoryImpl::new(datasource));
+        let revoked_access_token_repository = Arc::new(RevokedAccessTokenRepositoryImpl::new(
+            revoked_access_token_datasource,
+        ));
 
         /* ········································································· [ Usecases ] */
         let get_one_refresh_token = Arc::new(GetOneRefreshToken::new(repository.clone()));
@@ -32,6 +81,19 @@ impl AuthTokenDi {
         let delete_user_refresh_tokens = Arc::new(DeleteRefreshTokens::new(repository.clone()));
         let update_one_refresh_token = Arc::new(UpdateOneRefreshToken::new(repository.clone()));
         let delete_many_refresh_tokens = Arc::new(DeleteManyRefreshTokens::new(repository.clone()));
+        let revoke_refresh_token = Arc::new(RevokeRefreshToken::new(repository.clone()));
+        let revoke_refresh_token_family =
+            Arc::new(RevokeRefreshTokenFamily::new(repository.clone()));
+        let revoke_access_token = Arc::new(RevokeAccessToken::new(
+            revoked_access_token_repository.clone(),
+        ));
+        let is_access_token_revoked = Arc::new(IsAccessTokenRevoked::new(
+            revoked_access_token_repository.clone(),
+        ));
+        let get_sessions = Arc::new(GetSessions::new(repository.clone()));
+        let revoke_sessions = Arc::new(RevokeSessions::new(repository.clone()));
+        let is_session_revoked = Arc::new(IsSessionRevoked::new(repository.clone()));
+        let claim_refresh_token = Arc::new(ClaimRefreshToken::new(repository.clone()));
 
         Self {
             get_one_refresh_token,
@@ -39,6 +101,14 @@ impl AuthTokenDi {
             update_one_refresh_token,
             delete_user_refresh_tokens,
             delete_many_refresh_tokens,
+            revoke_refresh_token,
+            revoke_refresh_token_family,
+            revoke_access_token,
+            is_access_token_revoked,
+            get_sessions,
+            revoke_sessions,
+            is_session_revoked,
+            claim_refresh_token,
         }
     }
 }
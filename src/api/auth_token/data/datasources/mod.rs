@@ -1,2 +1,7 @@
 pub mod refresh_token_datasource;
+pub mod refresh_token_in_memory;
 pub mod refresh_token_mongo_db;
+
+pub mod revoked_access_token_datasource;
+pub mod revoked_access_token_in_memory;
+pub mod revoked_access_token_mongo_db;
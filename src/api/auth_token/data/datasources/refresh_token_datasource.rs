@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::{
     api::auth_token::{
@@ -12,4 +13,21 @@ use crate::{
 pub trait RefreshTokenDatasource:
     CrudDataSource<RefreshToken, RefreshTokenMongoModel, AppError> + Send + Sync
 {
+    /// Atomically marks the refresh token hashing to `token_hash` as used at `now`, provided it
+    /// is neither used, revoked nor expired. `None` when no such token exists, e.g. when another
+    /// request exchanged it first
+    async fn claim_unused(
+        &self,
+        token_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<RefreshToken>, AppError>;
+
+    /// The current refresh tokens, i.e. neither used, revoked nor expired at `now`, of the user
+    /// `user_id` and/or of the session `family_id`. A session has a single one
+    async fn find_current(
+        &self,
+        user_id: Option<&str>,
+        family_id: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<RefreshToken>, AppError>;
 }
@@ -0,0 +1,2 @@
+pub mod refresh_token_datasource_in_memory_impl;
+pub use refresh_token_datasource_in_memory_impl::*;
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use tokio::sync::Mutex;
+
+use crate::{
+    api::auth_token::{
+        data::datasources::{
+            refresh_token_datasource::RefreshTokenDatasource,
+            refresh_token_mongo_db::RefreshTokenMongoModel,
+        },
+        domain::entities::refresh_token::RefreshToken,
+    },
+    core::{
+        datasource::{crud_datasource::CrudDataSource, in_memory::InMemoryStore},
+        pagination::{PaginatedParams, PaginatedResponse},
+        AppError,
+    },
+};
+
+/// Refresh tokens datasource backed by an `InMemoryStore`. Used for tests and local development.
+#[derive(Default)]
+pub struct RefreshTokenInMemoryDatasourceImpl {
+    store: InMemoryStore<RefreshToken, RefreshTokenMongoModel>,
+    // Makes the find then update of `claim_unused` atomic
+    claim_lock: Mutex<()>,
+}
+
+impl RefreshTokenInMemoryDatasourceImpl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CrudDataSource<RefreshToken, RefreshTokenMongoModel, AppError>
+    for RefreshTokenInMemoryDatasourceImpl
+{
+    async fn create(&self, item: &RefreshToken) -> Result<RefreshToken, AppError> {
+        self.store.create(item).await
+    }
+    async fn find_one_by_id(&self, id: &str) -> Result<RefreshToken, AppError> {
+        self.store.find_one_by_id(id).await
+    }
+    async fn find_one(&self, query: HashMap<String, String>) -> Result<RefreshToken, AppError> {
+        self.store.find_one(query).await
+    }
+    async fn find(
+        &self,
+        params: PaginatedParams,
+    ) -> Result<PaginatedResponse<RefreshToken>, AppError> {
+        self.store.find(params).await
+    }
+    async fn update_one(&self, item: &RefreshToken) -> Result<RefreshToken, AppError> {
+        self.store.update_one(item).await
+    }
+    async fn delete_by_id(&self, id: &str) -> Result<RefreshToken, AppError> {
+        self.store.delete_by_id(id).await
+    }
+    async fn delete_one(&self, query: HashMap<String, String>) -> Result<RefreshToken, AppError> {
+        self.store.delete_one(query).await
+    }
+    async fn delete_many(&self, query: HashMap<String, String>) -> Result<u64, AppError> {
+        self.store.delete_many(query).await
+    }
+}
+
+#[async_trait]
+impl RefreshTokenDatasource for RefreshTokenInMemoryDatasourceImpl {
+    async fn claim_unused(
+        &self,
+        token_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<RefreshToken>, AppError> {
+        let _claim = self.claim_lock.lock().await;
+
+        let mut query = HashMap::new();
+        query.insert("token_hash".to_string(), token_hash.to_string());
+        query.insert("revoked".to_string(), "false".to_string());
+        query.insert(
+            "expires_at.gt".to_string(),
+            now.to_rfc3339_opts(SecondsFormat::Millis, true),
+        );
+
+        let mut token = match self.store.find_one(query).await {
+            Ok(token) if token.used_at.is_none() => token,
+            Ok(_) | Err(AppError::NotFound(_)) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        token.used_at = Some(now);
+
+        self.store.update_one(&token).await.map(Some)
+    }
+
+    async fn find_current(
+        &self,
+        user_id: Option<&str>,
+        family_id: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<RefreshToken>, AppError> {
+        let mut query = HashMap::new();
+        if let Some(user_id) = user_id {
+            query.insert("user_id".to_string(), user_id.to_string());
+        }
+        if let Some(family_id) = family_id {
+            query.insert("family_id".to_string(), family_id.to_string());
+        }
+        query.insert("revoked".to_string(), "false".to_string());
+        query.insert(
+            "expires_at.gt".to_string(),
+            now.to_rfc3339_opts(SecondsFormat::Millis, true),
+        );
+
+        let tokens = self
+            .store
+            .find(PaginatedParams::all_with_filter(query))
+            .await?;
+
+        Ok(tokens
+            .records
+            .into_iter()
+            .filter(|token| token.used_at.is_none())
+            .collect())
+    }
+}
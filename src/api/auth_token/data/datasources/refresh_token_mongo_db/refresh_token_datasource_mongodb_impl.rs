@@ -1,6 +1,9 @@
 use async_trait::async_trait;
 
-use mongodb::{Collection, Database};
+use bson::{doc, DateTime as BsonDateTime, Document};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use mongodb::{action::Action, options::ReturnDocument, Collection, Database, IndexModel};
 
 use crate::{
     api::auth_token::{
@@ -10,7 +13,15 @@ use crate::{
         },
         domain::entities::refresh_token::RefreshToken,
     },
-    core::datasource::mongo_db::crud_datasource_mongodb_impl::CrudDatasourceMongoImpl,
+    core::{
+        crud_model::CrudModel,
+        datasource::{
+            mongo_db::crud_datasource_mongodb_impl::CrudDatasourceMongoImpl,
+            transaction::Transaction,
+        },
+        rand_token_service::TokenService,
+        AppError, Validators,
+    },
 };
 
 pub struct RefreshTokenMongoDatasourceImpl {
@@ -22,6 +33,64 @@ impl RefreshTokenMongoDatasourceImpl {
         let collection = db.collection("refresh_tokens");
         Self { collection }
     }
+
+    /// Brings the stored refresh tokens up to date and indexes them, run once at startup
+    pub async fn prepare(&self) -> Result<(), AppError> {
+        self.hash_legacy_tokens().await?;
+
+        // The tokens are looked up by hash, and by session or user for their current one
+        let indexes = ["token_hash", "family_id", "user_id"]
+            .map(|field| IndexModel::builder().keys(doc! { field: 1 }).build());
+        self.collection
+            .create_indexes(indexes)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Could not create the indexes: {}", e)))?;
+
+        Ok(())
+    }
+
+    // The tokens issued before only their hash was stored hold it in plain text (`token`). Their
+    // hash is stored instead, so that they keep working. The tokens issued before the families
+    // were introduced are given their own, which `RevokeRefreshTokenFamily` then finds
+    async fn hash_legacy_tokens(&self) -> Result<(), AppError> {
+        let db_error = |e: mongodb::error::Error| {
+            AppError::DatabaseError(format!("Could not migrate the refresh tokens: {}", e))
+        };
+        let collection = self.collection.clone_with_type::<Document>();
+
+        let mut legacy_tokens = collection
+            .find(doc! { "token": { "$exists": true } })
+            .projection(doc! { "token": 1, "token_hash": 1 })
+            .await
+            .map_err(db_error)?;
+        while let Some(legacy) = legacy_tokens.try_next().await.map_err(db_error)? {
+            let Ok(id) = legacy.get_object_id("_id") else {
+                continue;
+            };
+            let mut update = doc! { "$unset": { "token": "" } };
+            if legacy.get_str("token_hash").unwrap_or_default().is_empty() {
+                let token_hash =
+                    TokenService::hash_token(legacy.get_str("token").unwrap_or_default());
+                update.insert("$set", doc! { "token_hash": token_hash });
+            }
+            collection
+                .update_one(doc! { "_id": id }, update)
+                .await
+                .map_err(db_error)?;
+        }
+
+        // Their id, as read by `RefreshTokenMongoModel`
+        let family_of_its_own = vec![doc! { "$set": { "family_id": { "$toString": "$_id" } } }];
+        collection
+            .update_many(
+                doc! { "family_id": { "$in": ["", null] } },
+                family_of_its_own,
+            )
+            .await
+            .map_err(db_error)?;
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -33,4 +102,65 @@ impl CrudDatasourceMongoImpl<RefreshToken, RefreshTokenMongoModel>
     }
 }
 #[async_trait]
-impl RefreshTokenDatasource for RefreshTokenMongoDatasourceImpl {}
+impl RefreshTokenDatasource for RefreshTokenMongoDatasourceImpl {
+    async fn claim_unused(
+        &self,
+        token_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<RefreshToken>, AppError> {
+        let now = BsonDateTime::from_chrono(now);
+        let filter = doc! {
+            "token_hash": token_hash,
+            "used_at": null,
+            "revoked": false,
+            "expires_at": { "$gt": now },
+        };
+        let update = doc! {
+            "$set": { "used_at": now },
+            "$currentDate": { "updated_at": true },
+        };
+
+        let transaction = Transaction::current();
+        let mut session = Transaction::session_of(&transaction).await;
+        let claimed = self
+            .collection
+            .find_one_and_update(filter, update)
+            .return_document(ReturnDocument::After)
+            .optional(session.as_deref_mut(), |a, s| a.session(s))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Claim failed: {}", e)))?;
+
+        Ok(claimed.map(|model| model.to_entity()))
+    }
+
+    async fn find_current(
+        &self,
+        user_id: Option<&str>,
+        family_id: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<RefreshToken>, AppError> {
+        let mut filter = doc! {
+            "used_at": null,
+            "revoked": false,
+            "expires_at": { "$gt": BsonDateTime::from_chrono(now) },
+        };
+        if let Some(user_id) = user_id {
+            filter.insert("user_id", Validators::validate_object_id(user_id)?);
+        }
+        if let Some(family_id) = family_id {
+            filter.insert("family_id", family_id);
+        }
+
+        let db_error = |e| AppError::DatabaseError(format!("Database error: {}", e));
+        let models: Vec<RefreshTokenMongoModel> = self
+            .collection
+            .find(filter)
+            .await
+            .map_err(db_error)?
+            .try_collect()
+            .await
+            .map_err(db_error)?;
+
+        Ok(models.into_iter().map(|model| model.to_entity()).collect())
+    }
+}
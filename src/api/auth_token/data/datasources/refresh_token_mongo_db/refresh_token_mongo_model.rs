@@ -19,7 +19,10 @@ pub struct RefreshTokenMongoModel {
     pub user_id: ObjectId,
 
     #[serde(default)]
-    pub token: String,
+    pub token_hash: String,
+
+    #[serde(default)]
+    pub family_id: String,
 
     #[serde(default)]
     pub user_role: UserRole,
@@ -29,6 +32,19 @@ pub struct RefreshTokenMongoModel {
     #[serde(default)]
     pub revoked: bool,
 
+    #[serde(default)]
+    pub used_at: Option<BsonDateTime>,
+
+    // Session Fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<String>,
+    #[serde(default)]
+    pub session_started_at: Option<BsonDateTime>,
+
     // Audit Fields
     pub created_by: ObjectId,
     pub created_at: BsonDateTime,
@@ -61,10 +77,18 @@ impl TryFrom<RefreshToken> for RefreshTokenMongoModel {
         Ok(Self {
             id,
             user_id,
-            token: entity.token,
+            token_hash: entity.token_hash,
+            family_id: entity.family_id,
             user_role: entity.user_role,
             expires_at: BsonDateTime::from_chrono(entity.expires_at),
             revoked: entity.revoked,
+            used_at: entity.used_at.map(BsonDateTime::from_chrono),
+
+            // Session fields
+            user_agent: entity.user_agent,
+            ip_address: entity.ip_address,
+            device_name: entity.device_name,
+            session_started_at: Some(BsonDateTime::from_chrono(entity.session_started_at)),
 
             // Audit fields
             created_at: BsonDateTime::from_chrono(entity.created_at),
@@ -77,13 +101,32 @@ impl TryFrom<RefreshToken> for RefreshTokenMongoModel {
 
 impl From<RefreshTokenMongoModel> for RefreshToken {
     fn from(model: RefreshTokenMongoModel) -> Self {
+        let id = model.id.unwrap().to_string();
+        // The tokens issued before the families were introduced are alone in their own
+        let family_id = match model.family_id.is_empty() {
+            true => id.clone(),
+            false => model.family_id,
+        };
+
         Self {
-            id: model.id.unwrap().to_string(),
+            id,
             user_id: model.user_id.to_string(),
-            token: model.token,
+            token_hash: model.token_hash,
+            family_id,
             user_role: model.user_role,
             expires_at: model.expires_at.to_chrono(),
             revoked: model.revoked,
+            used_at: model.used_at.map(|used_at| used_at.to_chrono()),
+
+            // Session Fields
+            user_agent: model.user_agent,
+            ip_address: model.ip_address,
+            device_name: model.device_name,
+            // The tokens issued before the sessions were introduced started theirs when created
+            session_started_at: model
+                .session_started_at
+                .unwrap_or(model.created_at)
+                .to_chrono(),
 
             // Audit Fields
             created_by: model.created_by.to_string(),
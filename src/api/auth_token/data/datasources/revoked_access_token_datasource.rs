@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::{
+    api::auth_token::{
+        data::datasources::revoked_access_token_mongo_db::RevokedAccessTokenMongoModel,
+        domain::entities::revoked_access_token::RevokedAccessToken,
+    },
+    core::{datasource::crud_datasource::CrudDataSource, AppError},
+};
+
+#[async_trait]
+pub trait RevokedAccessTokenDatasource:
+    CrudDataSource<RevokedAccessToken, RevokedAccessTokenMongoModel, AppError> + Send + Sync
+{
+}
@@ -0,0 +1,2 @@
+pub mod revoked_access_token_datasource_in_memory_impl;
+pub use revoked_access_token_datasource_in_memory_impl::*;
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::auth_token::{
+        data::datasources::{
+            revoked_access_token_datasource::RevokedAccessTokenDatasource,
+            revoked_access_token_mongo_db::RevokedAccessTokenMongoModel,
+        },
+        domain::entities::revoked_access_token::RevokedAccessToken,
+    },
+    core::{
+        datasource::{crud_datasource::CrudDataSource, in_memory::InMemoryStore},
+        pagination::{PaginatedParams, PaginatedResponse},
+        AppError,
+    },
+};
+
+/// Revoked access tokens datasource backed by an `InMemoryStore`. Used for tests and local
+/// development.
+#[derive(Default)]
+pub struct RevokedAccessTokenInMemoryDatasourceImpl {
+    store: InMemoryStore<RevokedAccessToken, RevokedAccessTokenMongoModel>,
+}
+
+impl RevokedAccessTokenInMemoryDatasourceImpl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CrudDataSource<RevokedAccessToken, RevokedAccessTokenMongoModel, AppError>
+    for RevokedAccessTokenInMemoryDatasourceImpl
+{
+    async fn create(&self, item: &RevokedAccessToken) -> Result<RevokedAccessToken, AppError> {
+        self.store.create(item).await
+    }
+    async fn find_one_by_id(&self, id: &str) -> Result<RevokedAccessToken, AppError> {
+        self.store.find_one_by_id(id).await
+    }
+    async fn find_one(
+        &self,
+        query: HashMap<String, String>,
+    ) -> Result<RevokedAccessToken, AppError> {
+        self.store.find_one(query).await
+    }
+    async fn find(
+        &self,
+        params: PaginatedParams,
+    ) -> Result<PaginatedResponse<RevokedAccessToken>, AppError> {
+        self.store.find(params).await
+    }
+    async fn update_one(&self, item: &RevokedAccessToken) -> Result<RevokedAccessToken, AppError> {
+        self.store.update_one(item).await
+    }
+    async fn delete_by_id(&self, id: &str) -> Result<RevokedAccessToken, AppError> {
+        self.store.delete_by_id(id).await
+    }
+    async fn delete_one(
+        &self,
+        query: HashMap<String, String>,
+    ) -> Result<RevokedAccessToken, AppError> {
+        self.store.delete_one(query).await
+    }
+    async fn delete_many(&self, query: HashMap<String, String>) -> Result<u64, AppError> {
+        self.store.delete_many(query).await
+    }
+}
+
+#[async_trait]
+impl RevokedAccessTokenDatasource for RevokedAccessTokenInMemoryDatasourceImpl {}
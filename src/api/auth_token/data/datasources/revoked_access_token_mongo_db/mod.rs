@@ -0,0 +1,5 @@
+pub mod revoked_access_token_datasource_mongodb_impl;
+pub use revoked_access_token_datasource_mongodb_impl::*;
+
+pub mod revoked_access_token_mongo_model;
+pub use revoked_access_token_mongo_model::*;
@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+
+use bson::doc;
+use mongodb::{options::IndexOptions, Collection, Database, IndexModel};
+
+use crate::{
+    api::auth_token::{
+        data::datasources::{
+            revoked_access_token_datasource::RevokedAccessTokenDatasource,
+            revoked_access_token_mongo_db::RevokedAccessTokenMongoModel,
+        },
+        domain::entities::revoked_access_token::RevokedAccessToken,
+    },
+    core::{datasource::mongo_db::crud_datasource_mongodb_impl::CrudDatasourceMongoImpl, AppError},
+};
+
+pub struct RevokedAccessTokenMongoDatasourceImpl {
+    collection: Collection<RevokedAccessTokenMongoModel>,
+}
+
+impl RevokedAccessTokenMongoDatasourceImpl {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("revoked_access_tokens");
+        Self { collection }
+    }
+
+    /// Indexes the revoked access tokens, run once at startup
+    pub async fn prepare(&self) -> Result<(), AppError> {
+        // The tokens are looked up by `jti` on every request missing the cache, and deleted by
+        // the database once they expire, rejected by their signature from then on
+        let expire_now = IndexOptions::builder()
+            .expire_after(std::time::Duration::ZERO)
+            .build();
+        let indexes = [
+            IndexModel::builder().keys(doc! { "jti": 1 }).build(),
+            IndexModel::builder()
+                .keys(doc! { "expires_at": 1 })
+                .options(expire_now)
+                .build(),
+        ];
+        self.collection
+            .create_indexes(indexes)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Could not create the indexes: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CrudDatasourceMongoImpl<RevokedAccessToken, RevokedAccessTokenMongoModel>
+    for RevokedAccessTokenMongoDatasourceImpl
+{
+    fn get_collection(&self) -> &Collection<RevokedAccessTokenMongoModel> {
+        &self.collection
+    }
+}
+#[async_trait]
+impl RevokedAccessTokenDatasource for RevokedAccessTokenMongoDatasourceImpl {}
@@ -0,0 +1,68 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::auth_token::domain::entities::revoked_access_token::RevokedAccessToken,
+    core::{crud_model::CrudModel, AppError, Validators},
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RevokedAccessTokenMongoModel {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    #[serde(default)]
+    pub jti: String,
+
+    #[serde(default)]
+    pub user_id: ObjectId,
+
+    pub expires_at: BsonDateTime,
+    pub created_at: BsonDateTime,
+}
+
+impl TryFrom<RevokedAccessToken> for RevokedAccessTokenMongoModel {
+    type Error = AppError;
+
+    fn try_from(entity: RevokedAccessToken) -> Result<Self, Self::Error> {
+        let id = if entity.id.is_empty() {
+            None
+        } else {
+            let id_or_err = Validators::validate_object_id(&entity.id)?;
+            Some(id_or_err)
+        };
+
+        let user_id = Validators::validate_object_id(&entity.user_id)?;
+
+        Ok(Self {
+            id,
+            jti: entity.jti,
+            user_id,
+            expires_at: BsonDateTime::from_chrono(entity.expires_at),
+            created_at: BsonDateTime::from_chrono(entity.created_at),
+        })
+    }
+}
+
+impl From<RevokedAccessTokenMongoModel> for RevokedAccessToken {
+    fn from(model: RevokedAccessTokenMongoModel) -> Self {
+        Self {
+            id: model.id.unwrap().to_string(),
+            jti: model.jti,
+            user_id: model.user_id.to_string(),
+            expires_at: model.expires_at.to_chrono(),
+            created_at: model.created_at.to_chrono(),
+        }
+    }
+}
+
+impl CrudModel<RevokedAccessToken> for RevokedAccessTokenMongoModel {
+    fn try_from_entity(revoked_access_token: RevokedAccessToken) -> Result<Self, AppError> {
+        revoked_access_token.try_into()
+    }
+
+    fn to_entity(self) -> RevokedAccessToken {
+        self.into()
+    }
+}
@@ -1,3 +1,4 @@
 pub mod refresh_access_token_request_dtos;
 pub mod refresh_access_token_response_dto;
+pub mod session_response_dto;
 // pub mod update_refresh_token_dto;
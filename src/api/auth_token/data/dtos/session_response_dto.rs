@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+use crate::api::auth_token::domain::entities::session::Session;
+
+#[derive(Debug, Serialize)]
+pub struct SessionResponseDto {
+    #[serde(flatten)]
+    pub session: Session,
+    /// The session of the request
+    pub current: bool,
+}
+
+impl SessionResponseDto {
+    /// `current_session_id` is the `sid` of the request, if any
+    pub fn new(session: Session, current_session_id: Option<&str>) -> Self {
+        let current = current_session_id == Some(session.id.as_str());
+        Self { session, current }
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::{
     api::auth_token::{
@@ -13,7 +14,7 @@ use crate::{
             repositories::refresh_token_repository::RefreshTokenRepository,
         },
     },
-    core::CrudRepositoryImpl,
+    core::{AppError, CrudRepositoryImpl},
 };
 
 pub struct RefreshTokenRepositoryImpl {
@@ -37,4 +38,21 @@ impl CrudRepositoryImpl<RefreshToken, RefreshTokenMongoModel, dyn RefreshTokenDa
 }
 
 #[async_trait]
-impl RefreshTokenRepository for RefreshTokenRepositoryImpl {}
+impl RefreshTokenRepository for RefreshTokenRepositoryImpl {
+    async fn claim_unused(
+        &self,
+        token_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<RefreshToken>, AppError> {
+        self.datasource.claim_unused(token_hash, now).await
+    }
+
+    async fn find_current(
+        &self,
+        user_id: Option<&str>,
+        family_id: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<RefreshToken>, AppError> {
+        self.datasource.find_current(user_id, family_id, now).await
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::auth_token::{
+        data::datasources::{
+            revoked_access_token_datasource::RevokedAccessTokenDatasource,
+            revoked_access_token_mongo_db::RevokedAccessTokenMongoModel,
+        },
+        domain::{
+            entities::revoked_access_token::RevokedAccessToken,
+            repositories::revoked_access_token_repository::RevokedAccessTokenRepository,
+        },
+    },
+    core::CrudRepositoryImpl,
+};
+
+pub struct RevokedAccessTokenRepositoryImpl {
+    datasource: Arc<dyn RevokedAccessTokenDatasource>,
+}
+
+impl RevokedAccessTokenRepositoryImpl {
+    pub fn new(datasource: Arc<dyn RevokedAccessTokenDatasource>) -> Self {
+        Self { datasource }
+    }
+}
+
+#[async_trait]
+impl
+    CrudRepositoryImpl<
+        RevokedAccessToken,
+        RevokedAccessTokenMongoModel,
+        dyn RevokedAccessTokenDatasource,
+    > for RevokedAccessTokenRepositoryImpl
+{
+    fn get_datasource(&self) -> Arc<dyn RevokedAccessTokenDatasource> {
+        self.datasource.clone()
+    }
+}
+
+#[async_trait]
+impl RevokedAccessTokenRepository for RevokedAccessTokenRepositoryImpl {}
@@ -1 +1,3 @@
 pub mod refresh_token;
+pub mod revoked_access_token;
+pub mod session;
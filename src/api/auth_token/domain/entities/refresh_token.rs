@@ -1,16 +1,34 @@
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 
-use crate::api::auth::domain::entities::user_role::UserRole;
+use crate::{
+    api::auth::domain::entities::user_role::UserRole,
+    core::{middleware::ClientInfo, rand_token_service::TokenService},
+};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct RefreshToken {
     pub id: String,
     pub user_id: String,
-    pub token: String,
+    // Only the hash of the token is kept, the token itself is handed to the user once
+    pub token_hash: String,
+    // Every token refreshed from the same login belongs to the same family
+    pub family_id: String,
     pub user_role: UserRole,
     pub expires_at: DateTime<Utc>,
     pub revoked: bool,
+    // Set once the token has been exchanged, presenting it again means it leaked
+    pub used_at: Option<DateTime<Utc>>,
+
+    /* ······································································· [ Session fields ] */
+    // The family is the session of a login (see `Session`), these describe its device as of the
+    // last time the token was issued
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    // Set by the client at login, e.g. `Jane's iPhone`
+    pub device_name: Option<String>,
+    // When the user logged in, carried over by every token of the family
+    pub session_started_at: DateTime<Utc>,
 
     // Audit fields
     pub created_at: DateTime<Utc>,
@@ -20,9 +38,10 @@ pub struct RefreshToken {
 }
 
 impl RefreshToken {
+    /// `token` is an opaque secret, see `TokenService::generate_secret_token`
     pub fn new(
         user_id: String,
-        token: String,
+        token: &str,
         user_role: UserRole,
         expires_in_days: Option<i64>,
     ) -> Self {
@@ -32,10 +51,18 @@ impl RefreshToken {
         Self {
             id: "".to_string(),
             user_id: user_id.clone(),
-            token,
+            token_hash: TokenService::hash_token(token),
+            family_id: uuid::Uuid::new_v4().to_string(),
             user_role,
             expires_at: expires_at,
             revoked: false,
+            used_at: None,
+
+            // Session fields
+            user_agent: None,
+            ip_address: None,
+            device_name: None,
+            session_started_at: now,
 
             // Audit fields
             created_by: user_id.clone(),
@@ -45,13 +72,40 @@ impl RefreshToken {
         }
     }
 
-    pub fn set_token(&mut self, new_token: String) -> () {
-        self.token = new_token;
-        self.expires_at = Utc::now() + Duration::days(15);
+    /// The session of the token is on this device
+    pub fn with_client(mut self, client: ClientInfo, device_name: Option<String>) -> Self {
+        self.user_agent = client.user_agent;
+        self.ip_address = client.ip_address;
+        self.device_name = device_name;
+        self
+    }
+
+    /// The token replacing this one once it is used by `client`, in the same family. It expires
+    /// with the session, the user logs in again once the login token would have expired
+    pub fn rotate(&self, new_token: &str, client: ClientInfo) -> Self {
+        let mut next = Self::new(
+            self.user_id.clone(),
+            new_token,
+            self.user_role.clone(),
+            None,
+        )
+        .with_client(client, self.device_name.clone());
+        next.family_id = self.family_id.clone();
+        next.session_started_at = self.session_started_at;
+        next.expires_at = self.expires_at;
+        next
+    }
+
+    pub fn mark_used(&mut self) -> () {
+        self.used_at = Some(Utc::now());
+    }
+
+    pub fn is_used(&self) -> bool {
+        self.used_at.is_some()
     }
 
-    pub fn should_renew(&self) -> bool {
-        let expiration_threshold = Duration::hours(24);
-        (self.expires_at - Utc::now()) < expiration_threshold
+    /// Neither revoked nor expired. A used token is still valid, but presenting it is a reuse
+    pub fn is_valid(&self) -> bool {
+        !self.revoked && self.expires_at > Utc::now()
     }
 }
@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::api::auth::domain::entities::Claims;
+
+/// An access token rejected before it expires, e.g. the one of a logged out session. It is kept
+/// until then, the token is rejected on its own afterwards
+#[derive(Debug, Clone, Serialize)]
+pub struct RevokedAccessToken {
+    pub id: String,
+    pub jti: String,
+    pub user_id: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RevokedAccessToken {
+    pub fn new(claims: &Claims) -> Self {
+        let now = Utc::now();
+
+        Self {
+            id: "".to_string(),
+            jti: claims.jti.clone(),
+            user_id: claims.user_id.clone(),
+            expires_at: DateTime::from_timestamp(claims.exp as i64, 0).unwrap_or(now),
+            created_at: now,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::api::auth_token::domain::entities::refresh_token::RefreshToken;
+
+/// A login of a user on a device, i.e. a family of refresh tokens (see `RefreshToken::rotate`).
+/// Its id is the family id, which is also the `sid` of the access tokens it issues
+#[derive(Debug, Clone, Serialize)]
+pub struct Session {
+    pub id: String,
+    pub user_id: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub device_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// The last time an access token was issued to the session, at login or refresh
+    pub last_used_at: DateTime<Utc>,
+    /// Set at login, refreshing the session does not extend it
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<RefreshToken> for Session {
+    /// `refresh_token` is the current token of the session, the one not used yet
+    fn from(refresh_token: RefreshToken) -> Self {
+        Self {
+            id: refresh_token.family_id,
+            user_id: refresh_token.user_id,
+            user_agent: refresh_token.user_agent,
+            ip_address: refresh_token.ip_address,
+            device_name: refresh_token.device_name,
+            created_at: refresh_token.session_started_at,
+            last_used_at: refresh_token.created_at,
+            expires_at: refresh_token.expires_at,
+        }
+    }
+}
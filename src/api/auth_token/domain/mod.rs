@@ -1,3 +1,4 @@
 pub mod entities;
 pub mod repositories;
+pub mod services;
 pub mod usecases;
@@ -1 +1,2 @@
 pub mod refresh_token_repository;
+pub mod revoked_access_token_repository;
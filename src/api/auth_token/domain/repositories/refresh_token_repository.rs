@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::{
     api::auth_token::{
@@ -15,4 +16,18 @@ use crate::{
 pub trait RefreshTokenRepository:
     CrudRepository<RefreshToken, RefreshTokenMongoModel, AppError, dyn RefreshTokenDatasource>
 {
+    /// See `RefreshTokenDatasource::claim_unused`
+    async fn claim_unused(
+        &self,
+        token_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<RefreshToken>, AppError>;
+
+    /// See `RefreshTokenDatasource::find_current`
+    async fn find_current(
+        &self,
+        user_id: Option<&str>,
+        family_id: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<RefreshToken>, AppError>;
 }
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+
+use crate::{
+    api::auth_token::{
+        data::datasources::{
+            revoked_access_token_datasource::RevokedAccessTokenDatasource,
+            revoked_access_token_mongo_db::RevokedAccessTokenMongoModel,
+        },
+        domain::entities::revoked_access_token::RevokedAccessToken,
+    },
+    core::{AppError, CrudRepository},
+};
+
+#[async_trait]
+pub trait RevokedAccessTokenRepository:
+    CrudRepository<
+    RevokedAccessToken,
+    RevokedAccessTokenMongoModel,
+    AppError,
+    dyn RevokedAccessTokenDatasource,
+>
+{
+}
@@ -0,0 +1,280 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{self, Arc},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::RwLock;
+use warp::http::HeaderMap;
+
+use crate::{
+    api::{
+        api_key::domain::usecases::ResolveApiKey,
+        auth::domain::{entities::Claims, usecases::GetUserById},
+        auth_token::domain::{
+            entities::revoked_access_token::RevokedAccessToken,
+            usecases::{IsAccessTokenRevoked, IsSessionRevoked, RevokeAccessToken},
+        },
+    },
+    core::{jwt_service::JwtService, AppError, MsgBuilder, UseCase},
+};
+
+// Past this many entries, the expired ones are dropped from the caches
+const MAX_CACHED_ENTRIES: usize = 10_000;
+
+/// Resolves the credentials of a request, an access token or an API key (see `ApiKey`), into
+/// `Claims`, see `auth_middleware`. For an access token a valid signature is not enough, it must
+/// not be in the denylist (see `RevokedAccessToken`) nor issued before `User::tokens_valid_after`,
+/// its session (see `Session`) must not be revoked, and its user must still exist and not be
+/// banned.
+///
+/// These lookups are cached for `Config::access_token_revocation_cache_ttl` seconds: revocations
+/// made through this instance apply right away, those made by other instances sharing the
+/// database within that delay
+pub struct Authenticator {
+    jwt_service: Arc<JwtService>,
+    revoke_access_token: Arc<RevokeAccessToken>,
+    is_access_token_revoked: Arc<IsAccessTokenRevoked>,
+    is_session_revoked: Arc<IsSessionRevoked>,
+    resolve_api_key: Arc<ResolveApiKey>,
+    get_user_by_id: Arc<GetUserById>,
+    cache_ttl: Duration,
+    // Whether a `jti` is in the denylist
+    revoked_tokens: RwLock<HashMap<String, Cached<bool>>>,
+    // Whether a session (`sid`) is revoked
+    revoked_sessions: RwLock<HashMap<String, Cached<bool>>>,
+    // The access tokens of a user issued before this timestamp (in seconds) are rejected
+    users_tokens_valid_after: RwLock<HashMap<String, Cached<i64>>>,
+    // The `locale` claim of the access tokens authenticated, until they expire (see `locale`)
+    token_locales: RwLock<HashMap<String, Cached<Option<String>>>>,
+    // The scopes guarding the routes, see `register_scope`
+    scopes: sync::RwLock<BTreeSet<&'static str>>,
+}
+
+struct Cached<T> {
+    value: T,
+    until: Instant,
+}
+
+impl Authenticator {
+    pub fn new(
+        jwt_service: Arc<JwtService>,
+        revoke_access_token: Arc<RevokeAccessToken>,
+        is_access_token_revoked: Arc<IsAccessTokenRevoked>,
+        is_session_revoked: Arc<IsSessionRevoked>,
+        resolve_api_key: Arc<ResolveApiKey>,
+        get_user_by_id: Arc<GetUserById>,
+        cache_ttl: u64,
+    ) -> Self {
+        Self {
+            jwt_service,
+            revoke_access_token,
+            is_access_token_revoked,
+            is_session_revoked,
+            resolve_api_key,
+            get_user_by_id,
+            cache_ttl: Duration::from_secs(cache_ttl),
+            revoked_tokens: RwLock::new(HashMap::new()),
+            revoked_sessions: RwLock::new(HashMap::new()),
+            users_tokens_valid_after: RwLock::new(HashMap::new()),
+            token_locales: RwLock::new(HashMap::new()),
+            scopes: sync::RwLock::new(BTreeSet::new()),
+        }
+    }
+
+    /// The claims of the bearer token of the request, unless it has been revoked. Without
+    /// `Authorization` header, those of the API key in the `X-API-KEY` header
+    pub async fn authenticate(&self, headers: &HeaderMap) -> Result<Claims, AppError> {
+        if !headers.contains_key("Authorization") {
+            if let Some(key) = headers.get("X-API-KEY") {
+                let key = key.to_str().unwrap_or_default();
+                return self.authenticate_api_key(key).await;
+            }
+        }
+
+        let claims = self.jwt_service.decode_jwt(headers)?;
+        self.verify(&claims).await?;
+
+        if let Some(token) = self.jwt_service.get_jwt_from_auth_header(headers) {
+            let expires_in =
+                (claims.exp as u64).saturating_sub(chrono::Utc::now().timestamp() as u64);
+            let until = Instant::now() + Duration::from_secs(expires_in);
+            self.store(&self.token_locales, &token, claims.locale.clone(), until)
+                .await;
+        }
+
+        Ok(claims)
+    }
+
+    /// The `locale` claim of the bearer token of the request, if this token has already been
+    /// authenticated (see `authenticate`). The token is not decoded again, see `locale_middleware`
+    pub async fn locale(&self, headers: &HeaderMap) -> Option<String> {
+        let token = self.jwt_service.get_jwt_from_auth_header(headers)?;
+        let cache = self.token_locales.read().await;
+        cache
+            .get(&token)
+            .filter(|cached| cached.until > Instant::now())
+            .and_then(|cached| cached.value.clone())
+    }
+
+    /// Rejects the revoked access tokens. Note that a token issued within the same second as
+    /// `User::tokens_valid_after` still goes through, `iat` having no finer precision
+    pub async fn verify(&self, claims: &Claims) -> Result<(), AppError> {
+        let valid_after = self.tokens_valid_after(&claims.user_id).await?;
+        if (claims.iat as i64) < valid_after
+            || self.is_revoked(&claims.jti).await?
+            || self.is_session_revoked(claims.sid.as_deref()).await?
+        {
+            let msg = MsgBuilder::custom("Invalid access token! It has been revoked");
+            return Err(AppError::Unauthorized(msg));
+        }
+
+        Ok(())
+    }
+
+    /// Adds the access token to the denylist until it expires
+    pub async fn revoke(&self, claims: &Claims) -> Result<(), AppError> {
+        // The tokens issued before the `jti` claim was added can only be revoked with the rest of
+        // the user's tokens, see `User::revoke_access_tokens`
+        if claims.jti.is_empty() {
+            return Ok(());
+        }
+
+        self.revoke_access_token
+            .execute(RevokedAccessToken::new(claims))
+            .await?;
+        self.cache(&self.revoked_tokens, &claims.jti, true).await;
+
+        Ok(())
+    }
+
+    /// Records a scope guarding a route, see `scoped_auth_middleware`. The API keys may only be
+    /// given the recorded scopes
+    pub fn register_scope(&self, scope: &'static str) {
+        self.scopes
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(scope);
+    }
+
+    /// The scopes guarding the routes, sorted
+    pub fn known_scopes(&self) -> Vec<&'static str> {
+        let scopes = self.scopes.read().unwrap_or_else(|e| e.into_inner());
+        scopes.iter().copied().collect()
+    }
+
+    /// Drops what is cached about the user, to be called once its tokens are revoked, or once it
+    /// is banned or deleted
+    pub async fn forget_user(&self, user_id: &str) {
+        self.users_tokens_valid_after.write().await.remove(user_id);
+    }
+
+    /// Drops what is cached about the session, to be called once it is revoked
+    pub async fn forget_session(&self, session_id: &str) {
+        self.revoked_sessions.write().await.remove(session_id);
+    }
+
+    /* ····································································· [ Helper functions ] */
+    async fn authenticate_api_key(&self, key: &str) -> Result<Claims, AppError> {
+        let api_key = self.resolve_api_key.execute(key.to_string()).await?;
+
+        // The claims are those of the user as of now, e.g. with its current role
+        let user = match self.get_user_by_id.execute(api_key.user_id.clone()).await {
+            Ok(user) if !user.banned => user,
+            Ok(_) | Err(AppError::NotFound(_)) => {
+                let msg = MsgBuilder::custom("Invalid API key!");
+                return Err(AppError::Unauthorized(msg));
+            }
+            Err(err) => return Err(err),
+        };
+
+        Ok(Claims::for_api_key(&user, &api_key))
+    }
+
+    async fn tokens_valid_after(&self, user_id: &str) -> Result<i64, AppError> {
+        if let Some(valid_after) = Self::cached(&self.users_tokens_valid_after, user_id).await {
+            return Ok(valid_after);
+        }
+
+        let valid_after = match self.get_user_by_id.execute(user_id.to_string()).await {
+            // None of the tokens of a banned user is accepted
+            Ok(user) if user.banned => i64::MAX,
+            Ok(user) => user
+                .tokens_valid_after
+                .map(|valid_after| valid_after.timestamp())
+                .unwrap_or(0),
+            // Nor the ones of a deleted user
+            Err(AppError::NotFound(_)) => i64::MAX,
+            Err(err) => return Err(err),
+        };
+        self.cache(&self.users_tokens_valid_after, user_id, valid_after)
+            .await;
+
+        Ok(valid_after)
+    }
+
+    async fn is_revoked(&self, jti: &str) -> Result<bool, AppError> {
+        if jti.is_empty() {
+            return Ok(false);
+        }
+        if let Some(revoked) = Self::cached(&self.revoked_tokens, jti).await {
+            return Ok(revoked);
+        }
+
+        let revoked = self
+            .is_access_token_revoked
+            .execute(jti.to_string())
+            .await?;
+        self.cache(&self.revoked_tokens, jti, revoked).await;
+
+        Ok(revoked)
+    }
+
+    async fn is_session_revoked(&self, session_id: Option<&str>) -> Result<bool, AppError> {
+        // The tokens issued before the sessions were introduced have none
+        let Some(session_id) = session_id else {
+            return Ok(false);
+        };
+        if let Some(revoked) = Self::cached(&self.revoked_sessions, session_id).await {
+            return Ok(revoked);
+        }
+
+        let revoked = self
+            .is_session_revoked
+            .execute(session_id.to_string())
+            .await?;
+        self.cache(&self.revoked_sessions, session_id, revoked)
+            .await;
+
+        Ok(revoked)
+    }
+
+    async fn cached<T: Copy>(cache: &RwLock<HashMap<String, Cached<T>>>, key: &str) -> Option<T> {
+        let cache = cache.read().await;
+        cache
+            .get(key)
+            .filter(|cached| cached.until > Instant::now())
+            .map(|cached| cached.value)
+    }
+
+    async fn cache<T>(&self, cache: &RwLock<HashMap<String, Cached<T>>>, key: &str, value: T) {
+        let until = Instant::now() + self.cache_ttl;
+        self.store(cache, key, value, until).await;
+    }
+
+    async fn store<T>(
+        &self,
+        cache: &RwLock<HashMap<String, Cached<T>>>,
+        key: &str,
+        value: T,
+        until: Instant,
+    ) {
+        let mut cache = cache.write().await;
+        let now = Instant::now();
+        if cache.len() >= MAX_CACHED_ENTRIES {
+            cache.retain(|_, cached| cached.until > now);
+        }
+
+        cache.insert(key.to_string(), Cached { value, until });
+    }
+}
@@ -0,0 +1,2 @@
+pub mod authenticator;
+pub use authenticator::*;
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api::auth_token::domain::{
+        entities::refresh_token::RefreshToken,
+        repositories::refresh_token_repository::RefreshTokenRepository,
+    },
+    core::{AppError, UseCase},
+};
+
+/// Spends the refresh token with the given hash, see `RefreshTokenDatasource::claim_unused`. Of
+/// concurrent exchanges of the same token, only one gets it
+pub struct ClaimRefreshToken {
+    repository: Arc<dyn RefreshTokenRepository>,
+}
+
+impl ClaimRefreshToken {
+    pub fn new(repository: Arc<dyn RefreshTokenRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<String, Option<RefreshToken>> for ClaimRefreshToken {
+    async fn execute(&self, token_hash: String) -> Result<Option<RefreshToken>, AppError> {
+        self.repository.claim_unused(&token_hash, Utc::now()).await
+    }
+}
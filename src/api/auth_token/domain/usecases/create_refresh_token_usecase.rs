@@ -1,6 +1,7 @@
 use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
+use chrono::Utc;
 
 use crate::{
     api::auth_token::domain::{
@@ -23,15 +24,17 @@ impl CreateRefreshToken {
 #[async_trait]
 impl UseCase<RefreshToken, RefreshToken> for CreateRefreshToken {
     async fn execute(&self, refresh_token: RefreshToken) -> Result<RefreshToken, AppError> {
-        /* ································································· [ Delete old token ] */
+        /* ································································ [ Delete old tokens ] */
         // Before creating new token, to prevent useless database consumptions, we delete the
-        // previous token if any.
+        // expired ones if any. The used tokens are kept until then to catch their reuse, and the
+        // other sessions of the user are left alone
         let mut filter = HashMap::new();
         filter.insert("user_id".to_string(), refresh_token.user_id.to_string());
+        filter.insert("expires_at.lt".to_string(), Utc::now().to_rfc3339());
 
-        if let Err(err) = self.repository.delete_one(filter).await {
+        if let Err(err) = self.repository.delete_many(filter).await {
             match err {
-                // If we don't find a refresh token we don't do anything!
+                // If we don't find an expired token we don't do anything!
                 AppError::NotFound(_) => (),
                 _ => {
                     return Err(err);
@@ -22,7 +22,8 @@ impl CommandUseCase<String> for DeleteRefreshTokens {
     async fn execute(&self, user_id: String) -> Result<(), AppError> {
         let mut query = HashMap::new();
         query.insert("user_id".to_string(), user_id);
-        self.repository.delete_one(query).await?;
+        // Every login (and every refresh) leaves a token behind
+        self.repository.delete_many(query).await?;
         Ok(())
     }
 }
@@ -0,0 +1,38 @@
+use std::{cmp::Reverse, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api::auth_token::domain::{
+        entities::session::Session, repositories::refresh_token_repository::RefreshTokenRepository,
+    },
+    core::{AppError, UseCase},
+};
+
+/// The active sessions of a user, the most recently used first
+pub struct GetSessions {
+    repository: Arc<dyn RefreshTokenRepository>,
+}
+
+impl GetSessions {
+    pub fn new(repository: Arc<dyn RefreshTokenRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<String, Vec<Session>> for GetSessions {
+    async fn execute(&self, user_id: String) -> Result<Vec<Session>, AppError> {
+        // Each session has a single token not used yet, the others were exchanged already
+        let tokens = self
+            .repository
+            .find_current(Some(&user_id), None, Utc::now())
+            .await?;
+
+        let mut sessions: Vec<Session> = tokens.into_iter().map(Session::from).collect();
+        sessions.sort_by_key(|session| Reverse(session.last_used_at));
+
+        Ok(sessions)
+    }
+}
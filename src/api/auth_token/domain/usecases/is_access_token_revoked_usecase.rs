@@ -0,0 +1,33 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    api::auth_token::domain::repositories::revoked_access_token_repository::RevokedAccessTokenRepository,
+    core::{AppError, UseCase},
+};
+
+/// Whether the access token with this `jti` is in the denylist
+pub struct IsAccessTokenRevoked {
+    repository: Arc<dyn RevokedAccessTokenRepository>,
+}
+
+impl IsAccessTokenRevoked {
+    pub fn new(repository: Arc<dyn RevokedAccessTokenRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<String, bool> for IsAccessTokenRevoked {
+    async fn execute(&self, jti: String) -> Result<bool, AppError> {
+        let mut query = HashMap::new();
+        query.insert("jti".to_string(), jti);
+
+        match self.repository.find_one(query).await {
+            Ok(_) => Ok(true),
+            Err(AppError::NotFound(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
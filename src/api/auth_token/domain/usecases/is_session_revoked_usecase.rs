@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api::auth_token::domain::repositories::refresh_token_repository::RefreshTokenRepository,
+    core::{AppError, UseCase},
+};
+
+/// Whether the session (see `Session`) was revoked or deleted, e.g. by a logout. It is alive as long
+/// as its current refresh token is, i.e. the one not used yet
+pub struct IsSessionRevoked {
+    repository: Arc<dyn RefreshTokenRepository>,
+}
+
+impl IsSessionRevoked {
+    pub fn new(repository: Arc<dyn RefreshTokenRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<String, bool> for IsSessionRevoked {
+    async fn execute(&self, session_id: String) -> Result<bool, AppError> {
+        let current = self
+            .repository
+            .find_current(None, Some(&session_id), Utc::now())
+            .await?;
+
+        Ok(current.is_empty())
+    }
+}
@@ -5,6 +5,7 @@ pub mod revoke_refresh_token_usecase;
 
 pub use create_refresh_token_usecase::*;
 pub use delete_refresh_tokens_usecase::*;
+pub use revoke_refresh_token_usecase::*;
 
 pub mod get_one_refresh_token;
 pub use get_one_refresh_token::*;
@@ -14,3 +15,24 @@ pub use update_one_refresh_token::*;
 
 pub mod delete_many_refresh_tokens;
 pub use delete_many_refresh_tokens::*;
+
+pub mod revoke_refresh_token_family_usecase;
+pub use revoke_refresh_token_family_usecase::*;
+
+pub mod revoke_access_token_usecase;
+pub use revoke_access_token_usecase::*;
+
+pub mod is_access_token_revoked_usecase;
+pub use is_access_token_revoked_usecase::*;
+
+pub mod get_sessions_usecase;
+pub use get_sessions_usecase::*;
+
+pub mod revoke_sessions_usecase;
+pub use revoke_sessions_usecase::*;
+
+pub mod is_session_revoked_usecase;
+pub use is_session_revoked_usecase::*;
+
+pub mod claim_refresh_token_usecase;
+pub use claim_refresh_token_usecase::*;
@@ -0,0 +1,47 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api::auth_token::domain::{
+        entities::revoked_access_token::RevokedAccessToken,
+        repositories::revoked_access_token_repository::RevokedAccessTokenRepository,
+    },
+    core::{AppError, UseCase},
+};
+
+/// Adds an access token to the denylist
+pub struct RevokeAccessToken {
+    repository: Arc<dyn RevokedAccessTokenRepository>,
+}
+
+impl RevokeAccessToken {
+    pub fn new(repository: Arc<dyn RevokedAccessTokenRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<RevokedAccessToken, RevokedAccessToken> for RevokeAccessToken {
+    async fn execute(
+        &self,
+        revoked_access_token: RevokedAccessToken,
+    ) -> Result<RevokedAccessToken, AppError> {
+        /* ···························································· [ Delete expired tokens ] */
+        // The expired tokens are rejected anyway, no need to keep them around
+        let mut filter = HashMap::new();
+        filter.insert("expires_at.lt".to_string(), Utc::now().to_rfc3339());
+
+        if let Err(err) = self.repository.delete_many(filter).await {
+            match err {
+                AppError::NotFound(_) => (),
+                _ => {
+                    return Err(err);
+                }
+            }
+        }
+
+        self.repository.create_one(&revoked_access_token).await
+    }
+}
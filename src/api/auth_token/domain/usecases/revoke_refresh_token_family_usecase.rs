@@ -0,0 +1,42 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    api::auth_token::domain::repositories::refresh_token_repository::RefreshTokenRepository,
+    core::{pagination::PaginatedParams, AppError, UseCase},
+};
+
+/// Revokes every refresh token of a family, returns how many were still active
+pub struct RevokeRefreshTokenFamily {
+    repository: Arc<dyn RefreshTokenRepository>,
+}
+
+impl RevokeRefreshTokenFamily {
+    pub fn new(repository: Arc<dyn RefreshTokenRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<String, u64> for RevokeRefreshTokenFamily {
+    async fn execute(&self, family_id: String) -> Result<u64, AppError> {
+        let mut query = HashMap::new();
+        query.insert("family_id".to_string(), family_id);
+        query.insert("revoked".to_string(), "false".to_string());
+
+        let tokens = self
+            .repository
+            .find(PaginatedParams::all_with_filter(query))
+            .await?;
+
+        let mut revoked = 0;
+        for mut token in tokens.records {
+            token.revoked = true;
+            self.repository.update_one(&token).await?;
+            revoked += 1;
+        }
+
+        Ok(revoked)
+    }
+}
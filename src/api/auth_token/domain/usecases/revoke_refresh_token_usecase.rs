@@ -1,35 +1,57 @@
-// use std::{collections::HashMap, sync::Arc};
-
-// use async_trait::async_trait;
-
-// use crate::{
-//     api::auth::domain::repositories::refresh_token_repository::RefreshTokenRepository,
-//     core::{AppError, CommandUseCase},
-// };
-
-// pub struct RevokeRefreshTokensParams {
-//     pub token_id: String,
-// }
-
-// pub struct RevokeRefreshTokens {
-//     repository: Arc<dyn RefreshTokenRepository>,
-// }
-
-// impl RevokeRefreshTokens {
-//     pub fn new(repository: Arc<dyn RefreshTokenRepository>) -> Self {
-//         Self { repository }
-//     }
-// }
-
-// #[async_trait]
-// impl CommandUseCase<RevokeRefreshTokensParams> for RevokeRefreshTokens {
-//     async fn execute(&self, params: RevokeRefreshTokensParams) -> Result<(), AppError> {
-//         let mut query = HashMap::new();
-//         query.insert("token".to_string(), params.token_id.to_string());
-//         let mut updates = HashMap::new();
-//         updates.insert("set".to_string(), "revoked:true".to_string());
-
-//         self.repository.update_many(query, updates).await?;
-//         Ok(())
-//     }
-// }
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    api::auth_token::domain::{
+        entities::refresh_token::RefreshToken,
+        repositories::refresh_token_repository::RefreshTokenRepository,
+    },
+    core::{rand_token_service::TokenService, AppError, MsgBuilder, UseCase},
+};
+
+pub struct RevokeRefreshTokenParams {
+    pub user_id: String,
+    /// The session the token must belong to, if known (see `Claims::sid`)
+    pub session_id: Option<String>,
+    /// The token itself, as handed to the user
+    pub refresh_token: String,
+}
+
+/// Revokes a refresh token of the user, which ends its session (see `Session`)
+pub struct RevokeRefreshToken {
+    repository: Arc<dyn RefreshTokenRepository>,
+}
+
+impl RevokeRefreshToken {
+    pub fn new(repository: Arc<dyn RefreshTokenRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<RevokeRefreshTokenParams, RefreshToken> for RevokeRefreshToken {
+    async fn execute(&self, params: RevokeRefreshTokenParams) -> Result<RefreshToken, AppError> {
+        let mut query = HashMap::new();
+        query.insert(
+            "token_hash".to_string(),
+            TokenService::hash_token(&params.refresh_token),
+        );
+        query.insert("user_id".to_string(), params.user_id);
+        if let Some(session_id) = params.session_id {
+            query.insert("family_id".to_string(), session_id);
+        }
+
+        // A used token no longer stands for its session, nor does a revoked or expired one
+        let mut refresh_token = match self.repository.find_one(query).await {
+            Ok(token) if token.is_valid() && !token.is_used() => token,
+            Ok(_) | Err(AppError::NotFound(_)) => {
+                return Err(AppError::NotFound(MsgBuilder::not_found("refresh token")));
+            }
+            Err(err) => return Err(err),
+        };
+
+        refresh_token.revoked = true;
+        self.repository.update_one(&refresh_token).await
+    }
+}
@@ -0,0 +1,72 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    api::auth_token::domain::repositories::refresh_token_repository::RefreshTokenRepository,
+    core::{pagination::PaginatedParams, AppError, MsgBuilder, UseCase},
+};
+
+pub struct RevokeSessionsParams {
+    /// Any user's when `None`, which requires a `session_id`
+    pub user_id: Option<String>,
+    /// Only this session, all of the user's when `None`
+    pub session_id: Option<String>,
+    /// Kept, e.g. the session of the request when logging out everywhere else
+    pub except_session_id: Option<String>,
+}
+
+/// Revokes sessions of a user (see `Session`), returns their ids. Their access tokens are
+/// rejected from then on too, see `Authenticator`
+pub struct RevokeSessions {
+    repository: Arc<dyn RefreshTokenRepository>,
+}
+
+impl RevokeSessions {
+    pub fn new(repository: Arc<dyn RefreshTokenRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<RevokeSessionsParams, Vec<String>> for RevokeSessions {
+    async fn execute(&self, params: RevokeSessionsParams) -> Result<Vec<String>, AppError> {
+        if params.user_id.is_none() && params.session_id.is_none() {
+            let reason = "Either a user or a session is required".to_string();
+            return Err(AppError::InvalidInput(reason));
+        }
+
+        let mut query = HashMap::new();
+        query.insert("revoked".to_string(), "false".to_string());
+        if let Some(user_id) = params.user_id {
+            query.insert("user_id".to_string(), user_id);
+        }
+        if let Some(session_id) = &params.session_id {
+            query.insert("family_id".to_string(), session_id.clone());
+        }
+
+        let tokens = self
+            .repository
+            .find(PaginatedParams::all_with_filter(query))
+            .await?;
+
+        if params.session_id.is_some() && tokens.records.is_empty() {
+            return Err(AppError::NotFound(MsgBuilder::not_found("session")));
+        }
+
+        let mut session_ids: Vec<String> = vec![];
+        for mut token in tokens.records {
+            if params.except_session_id.as_ref() == Some(&token.family_id) {
+                continue;
+            }
+
+            token.revoked = true;
+            self.repository.update_one(&token).await?;
+            if !session_ids.contains(&token.family_id) {
+                session_ids.push(token.family_id);
+            }
+        }
+
+        Ok(session_ids)
+    }
+}
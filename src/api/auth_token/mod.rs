@@ -3,7 +3,12 @@ use std::sync::Arc;
 use warp::{reject::Rejection, reply::Reply, Filter};
 
 use crate::{
-    api::auth_token::presentation::handlers::RefreshAccessTokenHandler, di::ServiceLocator,
+    api::auth_token::presentation::handlers::{
+        GetJwksHandler, GetSessionsHandler, RefreshAccessTokenHandler, RevokeSessionHandler,
+        RevokeSessionsHandler,
+    },
+    core::CoreEventHandler,
+    di::ServiceLocator,
 };
 
 pub mod auth_token_di;
@@ -13,16 +18,44 @@ pub mod presentation;
 
 pub struct AuthTokenFeature {
     refresh_access_token_handler: Arc<RefreshAccessTokenHandler>,
+    get_jwks_handler: Arc<GetJwksHandler>,
+    /// [GET] /sessions
+    get_sessions_handler: Arc<GetSessionsHandler>,
+    /// [DELETE] /sessions/[String]
+    revoke_session_handler: Arc<RevokeSessionHandler>,
+    /// [DELETE] /sessions
+    revoke_sessions_handler: Arc<RevokeSessionsHandler>,
 }
 
 impl AuthTokenFeature {
     pub fn new(sl: Arc<ServiceLocator>) -> Self {
         Self {
             refresh_access_token_handler: Arc::new(RefreshAccessTokenHandler::new(sl.clone())),
+            get_jwks_handler: Arc::new(GetJwksHandler::new(sl.clone())),
+            get_sessions_handler: Arc::new(GetSessionsHandler::new(sl.clone())),
+            revoke_session_handler: Arc::new(RevokeSessionHandler::new(sl.clone())),
+            revoke_sessions_handler: Arc::new(RevokeSessionsHandler::new(sl.clone())),
         }
     }
 
-    pub fn routes(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
-        Arc::clone(&self.refresh_access_token_handler).route()
+    pub fn routes<E: CoreEventHandler + 'static>(
+        self: Arc<Self>,
+        event_handler: Arc<E>,
+    ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        Arc::clone(&self.refresh_access_token_handler)
+            .route(event_handler)
+            // [GET] api/sessions
+            .or(Arc::clone(&self.get_sessions_handler).route())
+            // [DELETE] api/sessions/<String>
+            .or(Arc::clone(&self.revoke_session_handler).route())
+            // [DELETE] api/sessions
+            .or(Arc::clone(&self.revoke_sessions_handler).route())
+    }
+
+    /// Routes served under `/.well-known` rather than under `/api`, e.g. `/.well-known/jwks.json`
+    pub fn well_known_routes(
+        self: Arc<Self>,
+    ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        Arc::clone(&self.get_jwks_handler).route()
     }
 }
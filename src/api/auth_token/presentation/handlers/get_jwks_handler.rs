@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use warp::{
+    reject::Rejection,
+    reply::{json, with_header, Reply},
+    Filter,
+};
+
+use crate::di::ServiceLocator;
+
+/// Serves the public keys of the access tokens so that other services can verify them without
+/// being able to sign them
+pub struct GetJwksHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl GetJwksHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    async fn handle(self: Arc<Self>) -> Result<impl Reply, Rejection> {
+        // The standard JWK Set document, not wrapped in an `ApiResponse`
+        let jwks = self.sl.jwt_service().jwks();
+
+        Ok(with_header(json(&jwks), "Cache-Control", "public, max-age=300").into_response())
+    }
+
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("jwks.json").and(warp::get()).and_then(move || {
+            let handler = self.clone();
+            async move { handler.handle().await }
+        })
+    }
+}
@@ -0,0 +1,81 @@
+use std::{collections::HashMap, sync::Arc};
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::{
+        auth::domain::entities::Claims,
+        auth_token::data::dtos::session_response_dto::SessionResponseDto,
+    },
+    core::{
+        middleware::{auth_middleware, owner_or_admin_middleware, session_middleware},
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// The active sessions of the logged in user. Admins may list those of another user with
+/// `?user_id=`
+pub struct GetSessionsHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl GetSessionsHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        user_id: String,
+        claims: Claims,
+    ) -> Result<impl Reply, Rejection> {
+        let sessions: Vec<SessionResponseDto> = self
+            .sl
+            .get_sessions()
+            .execute(user_id)
+            .await?
+            .into_iter()
+            .map(|session| SessionResponseDto::new(session, claims.sid.as_deref()))
+            .collect();
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::loaded_success("Sessions");
+        let response = ApiResponse::success(msg, Some(sessions));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("sessions")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and(auth_middleware(self.sl.authenticator()).and_then(session_middleware))
+            .and_then(
+                |query: HashMap<String, String>, claims: Claims| async move {
+                    let user_id = match query.get("user_id") {
+                        Some(user_id) => user_id.clone(),
+                        None => claims.user_id.clone(),
+                    };
+                    owner_or_admin_middleware(user_id.clone(), claims.clone()).await?;
+                    Ok::<(String, Claims), Rejection>((user_id, claims))
+                },
+            )
+            .untuple_one()
+            .and_then(move |user_id: String, claims: Claims| {
+                let handler = self.clone();
+                async move { handler.handle(user_id, claims).await }
+            })
+    }
+}
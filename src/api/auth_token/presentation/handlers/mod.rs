@@ -1,3 +1,11 @@
+pub mod get_jwks_handler;
+pub mod get_sessions_handler;
 pub mod refresh_access_token_handler;
+pub mod revoke_session_handler;
+pub mod revoke_sessions_handler;
 
+pub use get_jwks_handler::*;
+pub use get_sessions_handler::*;
 pub use refresh_access_token_handler::*;
+pub use revoke_session_handler::*;
+pub use revoke_sessions_handler::*;
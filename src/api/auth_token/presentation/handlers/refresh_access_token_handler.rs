@@ -1,6 +1,5 @@
 use std::{collections::HashMap, sync::Arc};
 
-use chrono::Utc;
 use warp::{
     http::StatusCode,
     reject::Rejection,
@@ -16,7 +15,13 @@ use crate::{
         },
         domain::entities::refresh_token::RefreshToken,
     },
-    core::{errors::early_err_response, response::ApiResponse, MsgBuilder, UseCase},
+    core::{
+        errors::early_err_response,
+        middleware::{client_middleware, ClientInfo},
+        rand_token_service::TokenService,
+        response::ApiResponse,
+        AppError, CoreEventHandler, MsgBuilder, RefreshTokenReusedEvent, UseCase,
+    },
     di::ServiceLocator,
 };
 
@@ -29,52 +34,69 @@ impl RefreshAccessTokenHandler {
         Self { sl }
     }
 
-    async fn handle(
+    async fn handle<E: CoreEventHandler + 'static>(
         self: Arc<Self>,
         dto: RefreshAccessTokenRequestDto,
+        client: ClientInfo,
+        event_handler: Arc<E>,
     ) -> Result<impl Reply, Rejection> {
-        // Search Filter
-        let mut filter = HashMap::new();
-        filter.insert("token".to_string(), dto.refresh_token.to_string());
-        filter.insert("revoked".to_string(), "false".to_string());
-        filter.insert("expires_at.gt".to_string(), Utc::now().to_rfc3339());
-
-        let mut refresh_token: RefreshToken =
-            match self.sl.get_one_refresh_token().execute(filter).await {
-                Ok(result) => result,
-                Err(err) => {
-                    return Ok(early_err_response(err).await);
-                }
-            };
+        // The refresh tokens are stored hashed
+        let token_hash = TokenService::hash_token(&dto.refresh_token);
 
-        let user = self
+        /* ····································································· [ Rotate Token ] */
+        // The presented token is spent, a new one of the same family (i.e. session) replaces it.
+        // Spending it is atomic, of concurrent exchanges of the same token only one goes through,
+        // and the new one is saved in the same transaction
+        let refresh_token_value = TokenService::generate_secret_token();
+        let rotated = self
             .sl
-            .get_user_by_id_usecase()
-            .execute(refresh_token.user_id.to_string())
-            .await?;
+            .transactions()
+            .run(|| async {
+                let claimed = self
+                    .sl
+                    .claim_refresh_token()
+                    .execute(token_hash.clone())
+                    .await?;
+                let Some(refresh_token) = claimed else {
+                    return Ok(None);
+                };
 
-        /* ······································································ [ Renew Token ] */
-        // Renew refresh token if it is expiring today
-        if refresh_token.should_renew() {
-            let new_token = self.sl.jwt_service().generate_jwt(&user)?;
+                let user = self
+                    .sl
+                    .get_user_by_id_usecase()
+                    .execute(refresh_token.user_id.to_string())
+                    .await?;
+                // Not an error, the token is spent and ending the session beats leaving it to look
+                // like a reuse the next time it is presented
+                if let Err(err) = user.is_allowed() {
+                    return Ok(Some(Err((refresh_token.family_id, err))));
+                }
+                let next_refresh_token = self
+                    .sl
+                    .create_refresh_token()
+                    .execute(refresh_token.rotate(&refresh_token_value, client.clone()))
+                    .await?;
 
-            refresh_token.set_token(new_token);
+                Ok(Some(Ok((user, next_refresh_token))))
+            })
+            .await?;
 
-            self.sl
-                .update_one_refresh_token()
-                .execute(refresh_token.clone())
-                .await?;
-        }
+        let (user, next_refresh_token) = match rotated {
+            Some(Ok(rotated)) => rotated,
+            Some(Err((family_id, err))) => return self.reject_disallowed(family_id, err).await,
+            None => return self.reject_unclaimed(token_hash, event_handler).await,
+        };
 
-        // The token is still valid for over a day, so non need to update it we continue to access
-        // token generation
         // create a new access token
-        let access_token = self.sl.jwt_service().generate_jwt(&user)?;
+        let access_token = self
+            .sl
+            .jwt_service()
+            .generate_session_jwt(&user, &next_refresh_token.family_id)?;
 
         let response_body = RefreshAccessTokenResponseDto {
-            id: refresh_token.id,
+            id: next_refresh_token.id,
             access_token,
-            refresh_token: refresh_token.token,
+            refresh_token: refresh_token_value,
         };
 
         let msg = MsgBuilder::created_success("refresh token");
@@ -83,13 +105,84 @@ impl RefreshAccessTokenHandler {
         Ok(with_status(json(&response), StatusCode::OK).into_response())
     }
 
-    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    // The user was banned since the login, or is otherwise not allowed in anymore: the session ends
+    async fn reject_disallowed(
+        &self,
+        family_id: String,
+        err: AppError,
+    ) -> Result<warp::reply::Response, Rejection> {
+        self.sl
+            .revoke_refresh_token_family()
+            .execute(family_id.clone())
+            .await?;
+        self.sl.authenticator().forget_session(&family_id).await;
+
+        Ok(early_err_response(err).await)
+    }
+
+    // The token could not be claimed: it does not exist, is revoked or expired, or was used
+    async fn reject_unclaimed<E: CoreEventHandler + 'static>(
+        &self,
+        token_hash: String,
+        event_handler: Arc<E>,
+    ) -> Result<warp::reply::Response, Rejection> {
+        let mut filter = HashMap::new();
+        filter.insert("token_hash".to_string(), token_hash);
+
+        let refresh_token: RefreshToken =
+            match self.sl.get_one_refresh_token().execute(filter).await {
+                Ok(result) if result.is_valid() && result.is_used() => result,
+                Ok(_) | Err(AppError::NotFound(_)) => {
+                    let err = AppError::NotFound(MsgBuilder::not_found("refresh token"));
+                    return Ok(early_err_response(err).await);
+                }
+                Err(err) => {
+                    return Ok(early_err_response(err).await);
+                }
+            };
+
+        /* ·································································· [ Reuse Detection ] */
+        // Each token is exchanged once. Seeing it again means that it was stolen (or that the
+        // thief already used it), either way nobody in the family can be trusted anymore
+        let revoked_tokens = self
+            .sl
+            .revoke_refresh_token_family()
+            .execute(refresh_token.family_id.clone())
+            .await?;
+        self.sl
+            .authenticator()
+            .forget_session(&refresh_token.family_id)
+            .await;
+
+        let event = RefreshTokenReusedEvent {
+            user_id: refresh_token.user_id.clone(),
+            token_id: refresh_token.id.clone(),
+            family_id: refresh_token.family_id.clone(),
+            revoked_tokens,
+        };
+        eprintln!("Refresh token reused, revoked its family: {:?}", event);
+        tokio::spawn(async move {
+            let _ = event_handler.on_refresh_token_reused(&event).await;
+        });
+
+        let msg = MsgBuilder::custom("This session has been revoked. Please login to continue");
+        Ok(early_err_response(AppError::Unauthorized(msg)).await)
+    }
+
+    pub fn route<E: CoreEventHandler + 'static>(
+        self: Arc<Self>,
+        event_handler: Arc<E>,
+    ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
         warp::path("refresh-jwt")
             .and(warp::post())
             .and(warp::body::json())
-            .and_then(move |dto: RefreshAccessTokenRequestDto| {
-                let handler = self.clone();
-                async move { handler.handle(dto).await }
-            })
+            .and(client_middleware())
+            .and_then(
+                move |dto: RefreshAccessTokenRequestDto, client: ClientInfo| {
+                    let handler = self.clone();
+                    let event_handler = Arc::clone(&event_handler);
+                    async move { handler.handle(dto, client, event_handler).await }
+                },
+            )
     }
 }
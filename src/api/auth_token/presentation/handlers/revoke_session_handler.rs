@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::{auth::domain::entities::Claims, auth_token::domain::usecases::RevokeSessionsParams},
+    core::{
+        middleware::{auth_middleware, session_middleware},
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// Revokes a session of the logged in user, e.g. of a lost device. Admins may revoke anybody's
+pub struct RevokeSessionHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl RevokeSessionHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(self: Arc<Self>, id: String, claims: Claims) -> Result<impl Reply, Rejection> {
+        // The sessions of another user are not found, rather than forbidden
+        let params = RevokeSessionsParams {
+            user_id: (!claims.is_admin()).then(|| claims.user_id.clone()),
+            session_id: Some(id),
+            except_session_id: None,
+        };
+
+        let session_ids = self.sl.revoke_sessions().execute(params).await?;
+
+        // Its access tokens are rejected from now on
+        for session_id in &session_ids {
+            self.sl.authenticator().forget_session(session_id).await;
+        }
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::custom("Session revoked successfully!");
+        let response = ApiResponse::<()>::success(msg, None);
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("sessions" / String)
+            .and(warp::delete())
+            .and(auth_middleware(self.sl.authenticator()).and_then(session_middleware))
+            .and_then(move |id: String, claims: Claims| {
+                let handler = self.clone();
+                async move { handler.handle(id, claims).await }
+            })
+    }
+}
@@ -0,0 +1,86 @@
+use std::{collections::HashMap, sync::Arc};
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::{auth::domain::entities::Claims, auth_token::domain::usecases::RevokeSessionsParams},
+    core::{
+        middleware::{auth_middleware, owner_or_admin_middleware, session_middleware},
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// Logs the user out everywhere else, i.e. revokes all of its sessions but the one of the request.
+/// Admins may revoke all the sessions of another user with `?user_id=`
+pub struct RevokeSessionsHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl RevokeSessionsHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        user_id: String,
+        claims: Claims,
+    ) -> Result<impl Reply, Rejection> {
+        let except_session_id = match user_id == claims.user_id {
+            true => claims.sid,
+            false => None,
+        };
+        let params = RevokeSessionsParams {
+            user_id: Some(user_id),
+            session_id: None,
+            except_session_id,
+        };
+
+        let session_ids = self.sl.revoke_sessions().execute(params).await?;
+
+        // Their access tokens are rejected from now on
+        for session_id in &session_ids {
+            self.sl.authenticator().forget_session(session_id).await;
+        }
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::custom("Sessions revoked successfully!");
+        let response = ApiResponse::success(msg, Some(session_ids));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("sessions")
+            .and(warp::delete())
+            .and(warp::query::<HashMap<String, String>>())
+            .and(auth_middleware(self.sl.authenticator()).and_then(session_middleware))
+            .and_then(
+                |query: HashMap<String, String>, claims: Claims| async move {
+                    let user_id = match query.get("user_id") {
+                        Some(user_id) => user_id.clone(),
+                        None => claims.user_id.clone(),
+                    };
+                    owner_or_admin_middleware(user_id.clone(), claims.clone()).await?;
+                    Ok::<(String, Claims), Rejection>((user_id, claims))
+                },
+            )
+            .untuple_one()
+            .and_then(move |user_id: String, claims: Claims| {
+                let handler = self.clone();
+                async move { handler.handle(user_id, claims).await }
+            })
+    }
+}
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+/// Query string of `dev/emails`
+#[derive(Debug, Default, Deserialize)]
+pub struct CapturedEmailsQueryDto {
+    /// Only the emails sent to this address (case insensitive)
+    pub to: Option<String>,
+}
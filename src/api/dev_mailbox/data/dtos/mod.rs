@@ -0,0 +1,2 @@
+mod captured_emails_query_dto;
+pub use captured_emails_query_dto::*;
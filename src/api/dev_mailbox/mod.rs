@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use presentation::handlers::*;
+use warp::{reject::Rejection, reply::Reply, Filter};
+
+use crate::di::ServiceLocator;
+
+pub mod data;
+pub mod presentation;
+
+/// Read the emails captured by `EmailServiceCaptureImpl`, e.g. to follow the activation link of a
+/// freshly registered user without a mail server.
+///
+/// Development only: the routes are unauthenticated and only mounted when
+/// `EmailBackendConfig::Capture` is selected, which `Config::is_dev` requires.
+pub struct DevMailboxFeature {
+    mounted: bool,
+    /// [GET] /dev/emails?to=[String]
+    get_captured_emails_handler: Arc<GetCapturedEmailsHandler>,
+    /// [GET] /dev/emails/[String]
+    get_captured_email_handler: Arc<GetCapturedEmailHandler>,
+}
+
+impl DevMailboxFeature {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self {
+            mounted: sl.email_capture().is_some(),
+            get_captured_emails_handler: Arc::new(GetCapturedEmailsHandler::new(sl.clone())),
+            get_captured_email_handler: Arc::new(GetCapturedEmailHandler::new(sl.clone())),
+        }
+    }
+
+    pub fn routes(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        let mounted = self.mounted;
+
+        warp::any()
+            .and_then(move || async move {
+                match mounted {
+                    true => Ok(()),
+                    false => Err(warp::reject::not_found()),
+                }
+            })
+            .untuple_one()
+            .and(
+                // [GET] api/dev/emails
+                Arc::clone(&self.get_captured_emails_handler)
+                    .route()
+                    // [GET] api/dev/emails/<String>
+                    .or(Arc::clone(&self.get_captured_email_handler).route()),
+            )
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    core::{response::ApiResponse, MsgBuilder},
+    di::ServiceLocator,
+};
+
+/// One captured email with its headers and bodies
+pub struct GetCapturedEmailHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl GetCapturedEmailHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(self: Arc<Self>, id: String) -> Result<impl Reply, Rejection> {
+        // The route doesn't exist unless the emails are captured
+        let Some(capture) = self.sl.email_capture() else {
+            return Err(warp::reject::not_found());
+        };
+
+        let email = capture.get(&id).await?;
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::loaded_success("Email");
+        let response = ApiResponse::success(msg, Some(email));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("dev" / "emails" / String)
+            .and(warp::get())
+            .and_then(move |id: String| {
+                let handler = self.clone();
+                async move { handler.handle(id).await }
+            })
+    }
+}
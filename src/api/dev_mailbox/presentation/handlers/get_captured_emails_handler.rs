@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::dev_mailbox::data::dtos::CapturedEmailsQueryDto,
+    core::{response::ApiResponse, MsgBuilder},
+    di::ServiceLocator,
+};
+
+/// The captured emails, the most recent first
+pub struct GetCapturedEmailsHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl GetCapturedEmailsHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        query: CapturedEmailsQueryDto,
+    ) -> Result<impl Reply, Rejection> {
+        // The route doesn't exist unless the emails are captured
+        let Some(capture) = self.sl.email_capture() else {
+            return Err(warp::reject::not_found());
+        };
+
+        let mut emails = capture.list().await?;
+        if let Some(to) = query.to {
+            let to = to.to_lowercase();
+            emails.retain(|email| email.to.to_lowercase().contains(&to));
+        }
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::loaded_success("Emails");
+        let response = ApiResponse::success(msg, Some(emails));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("dev" / "emails")
+            .and(warp::get())
+            .and(warp::query::<CapturedEmailsQueryDto>())
+            .and_then(move |query: CapturedEmailsQueryDto| {
+                let handler = self.clone();
+                async move { handler.handle(query).await }
+            })
+    }
+}
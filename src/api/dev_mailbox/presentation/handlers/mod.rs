@@ -0,0 +1,5 @@
+mod get_captured_email_handler;
+mod get_captured_emails_handler;
+
+pub use get_captured_email_handler::*;
+pub use get_captured_emails_handler::*;
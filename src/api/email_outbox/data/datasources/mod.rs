@@ -0,0 +1,3 @@
+pub mod outbox_email_datasource;
+pub mod outbox_email_in_memory;
+pub mod outbox_email_mongo_db;
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    api::email_outbox::{
+        data::datasources::outbox_email_mongo_db::OutboxEmailMongoModel,
+        domain::entities::outbox_email::OutboxEmail,
+    },
+    core::{datasource::crud_datasource::CrudDataSource, AppError},
+};
+
+#[async_trait]
+pub trait OutboxEmailDatasource:
+    CrudDataSource<OutboxEmail, OutboxEmailMongoModel, AppError> + Send + Sync
+{
+    /// Atomically takes the pending email due the longest: counts the attempt and pushes its
+    /// `next_attempt_at` to `lease_until` so no other worker picks it up meanwhile
+    async fn claim_next_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<OutboxEmail>, AppError>;
+}
@@ -0,0 +1,2 @@
+pub mod outbox_email_datasource_in_memory_impl;
+pub use outbox_email_datasource_in_memory_impl::*;
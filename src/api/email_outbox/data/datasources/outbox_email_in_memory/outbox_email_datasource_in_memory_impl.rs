@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use tokio::sync::Mutex;
+
+use crate::{
+    api::email_outbox::{
+        data::datasources::{
+            outbox_email_datasource::OutboxEmailDatasource,
+            outbox_email_mongo_db::OutboxEmailMongoModel,
+        },
+        domain::entities::outbox_email::OutboxEmail,
+    },
+    core::{
+        datasource::{crud_datasource::CrudDataSource, in_memory::InMemoryStore},
+        pagination::{PaginatedParams, PaginatedResponse},
+        AppError,
+    },
+};
+
+/// Outbox datasource backed by an `InMemoryStore`. Used for tests and local development.
+#[derive(Default)]
+pub struct OutboxEmailInMemoryDatasourceImpl {
+    store: InMemoryStore<OutboxEmail, OutboxEmailMongoModel>,
+    // Makes the find then update of `claim_next_due` atomic
+    claim_lock: Mutex<()>,
+}
+
+impl OutboxEmailInMemoryDatasourceImpl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CrudDataSource<OutboxEmail, OutboxEmailMongoModel, AppError>
+    for OutboxEmailInMemoryDatasourceImpl
+{
+    async fn create(&self, item: &OutboxEmail) -> Result<OutboxEmail, AppError> {
+        self.store.create(item).await
+    }
+    async fn find_one_by_id(&self, id: &str) -> Result<OutboxEmail, AppError> {
+        self.store.find_one_by_id(id).await
+    }
+    async fn find_one(&self, query: HashMap<String, String>) -> Result<OutboxEmail, AppError> {
+        self.store.find_one(query).await
+    }
+    async fn find(
+        &self,
+        params: PaginatedParams,
+    ) -> Result<PaginatedResponse<OutboxEmail>, AppError> {
+        self.store.find(params).await
+    }
+    async fn update_one(&self, item: &OutboxEmail) -> Result<OutboxEmail, AppError> {
+        self.store.update_one(item).await
+    }
+    async fn delete_by_id(&self, id: &str) -> Result<OutboxEmail, AppError> {
+        self.store.delete_by_id(id).await
+    }
+    async fn delete_one(&self, query: HashMap<String, String>) -> Result<OutboxEmail, AppError> {
+        self.store.delete_one(query).await
+    }
+    async fn delete_many(&self, query: HashMap<String, String>) -> Result<u64, AppError> {
+        self.store.delete_many(query).await
+    }
+}
+
+#[async_trait]
+impl OutboxEmailDatasource for OutboxEmailInMemoryDatasourceImpl {
+    async fn claim_next_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<OutboxEmail>, AppError> {
+        let _claim = self.claim_lock.lock().await;
+
+        let mut query = HashMap::new();
+        query.insert("status".to_string(), "pending".to_string());
+        query.insert(
+            "next_attempt_at.lte".to_string(),
+            now.to_rfc3339_opts(SecondsFormat::Millis, true),
+        );
+        query.insert("sort".to_string(), "next_attempt_at:1".to_string());
+        let mut params = PaginatedParams::with_filter(&query);
+        params.limit = 1;
+
+        let Some(mut email) = self.store.find(params).await?.records.pop() else {
+            return Ok(None);
+        };
+        email.attempts += 1;
+        email.next_attempt_at = lease_until;
+
+        self.store.update_one(&email).await.map(Some)
+    }
+}
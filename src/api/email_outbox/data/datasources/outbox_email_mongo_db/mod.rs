@@ -0,0 +1,5 @@
+pub mod outbox_email_datasource_mongodb_impl;
+pub use outbox_email_datasource_mongodb_impl::*;
+
+pub mod outbox_email_mongo_model;
+pub use outbox_email_mongo_model::*;
@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use bson::{doc, DateTime as BsonDateTime};
+use chrono::{DateTime, Utc};
+use mongodb::{options::ReturnDocument, Collection, Database, IndexModel};
+
+use crate::{
+    api::email_outbox::{
+        data::datasources::{
+            outbox_email_datasource::OutboxEmailDatasource,
+            outbox_email_mongo_db::OutboxEmailMongoModel,
+        },
+        domain::entities::outbox_email::OutboxEmail,
+    },
+    core::{
+        crud_model::CrudModel,
+        datasource::mongo_db::crud_datasource_mongodb_impl::CrudDatasourceMongoImpl, AppError,
+    },
+};
+
+pub struct OutboxEmailMongoDatasourceImpl {
+    collection: Collection<OutboxEmailMongoModel>,
+}
+
+impl OutboxEmailMongoDatasourceImpl {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("email_outbox");
+        Self { collection }
+    }
+
+    /// Indexes the outbox, run once at startup
+    pub async fn prepare(&self) -> Result<(), AppError> {
+        // The worker polls for the next pending email due, in order
+        let index = IndexModel::builder()
+            .keys(doc! { "status": 1, "next_attempt_at": 1 })
+            .build();
+        self.collection
+            .create_index(index)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Could not create the indexes: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CrudDatasourceMongoImpl<OutboxEmail, OutboxEmailMongoModel>
+    for OutboxEmailMongoDatasourceImpl
+{
+    fn get_collection(&self) -> &Collection<OutboxEmailMongoModel> {
+        &self.collection
+    }
+}
+
+#[async_trait]
+impl OutboxEmailDatasource for OutboxEmailMongoDatasourceImpl {
+    async fn claim_next_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<OutboxEmail>, AppError> {
+        let filter = doc! {
+            "status": "pending",
+            "next_attempt_at": { "$lte": BsonDateTime::from_chrono(now) },
+        };
+        let update = doc! {
+            "$set": { "next_attempt_at": BsonDateTime::from_chrono(lease_until) },
+            "$inc": { "attempts": 1 },
+            "$currentDate": { "updated_at": true },
+        };
+
+        let claimed = self
+            .collection
+            .find_one_and_update(filter, update)
+            .sort(doc! { "next_attempt_at": 1 })
+            .return_document(ReturnDocument::After)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Claim failed: {}", e)))?;
+
+        Ok(claimed.map(|model| model.to_entity()))
+    }
+}
@@ -0,0 +1,96 @@
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::DateTime as BsonDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::email_outbox::domain::entities::outbox_email::{OutboxEmail, OutboxEmailStatus},
+    core::{crud_model::CrudModel, AppError, Email, Validators},
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OutboxEmailMongoModel {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub email: Email,
+
+    #[serde(default)]
+    pub status: OutboxEmailStatus,
+
+    #[serde(default)]
+    pub attempts: u32,
+
+    pub next_attempt_at: BsonDateTime,
+
+    #[serde(default)]
+    pub last_error: Option<String>,
+
+    #[serde(default)]
+    pub sent_at: Option<BsonDateTime>,
+
+    // Audit Fields
+    pub created_at: BsonDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<BsonDateTime>,
+}
+
+impl TryFrom<OutboxEmail> for OutboxEmailMongoModel {
+    type Error = AppError;
+
+    fn try_from(entity: OutboxEmail) -> Result<Self, Self::Error> {
+        let id = if entity.id.is_empty() {
+            None
+        } else {
+            let id_or_err = Validators::validate_object_id(&entity.id)?;
+            Some(id_or_err)
+        };
+
+        let mut updated_at: Option<BsonDateTime> = None;
+        if id.is_none() {
+            // we are creating a new document so we need to set updated at
+            updated_at = Some(BsonDateTime::from_chrono(entity.updated_at));
+        }
+
+        Ok(Self {
+            id,
+            email: entity.email,
+            status: entity.status,
+            attempts: entity.attempts,
+            next_attempt_at: BsonDateTime::from_chrono(entity.next_attempt_at),
+            last_error: entity.last_error,
+            sent_at: entity.sent_at.map(BsonDateTime::from_chrono),
+
+            // Audit fields
+            created_at: BsonDateTime::from_chrono(entity.created_at),
+            updated_at,
+        })
+    }
+}
+
+impl From<OutboxEmailMongoModel> for OutboxEmail {
+    fn from(model: OutboxEmailMongoModel) -> Self {
+        Self {
+            id: model.id.unwrap().to_string(),
+            email: model.email,
+            status: model.status,
+            attempts: model.attempts,
+            next_attempt_at: model.next_attempt_at.to_chrono(),
+            last_error: model.last_error,
+            sent_at: model.sent_at.map(|sent_at| sent_at.to_chrono()),
+
+            // Audit Fields
+            created_at: model.created_at.to_chrono(),
+            updated_at: model.updated_at.unwrap().to_chrono(),
+        }
+    }
+}
+
+impl CrudModel<OutboxEmail> for OutboxEmailMongoModel {
+    fn try_from_entity(outbox_email: OutboxEmail) -> Result<Self, AppError> {
+        outbox_email.try_into()
+    }
+
+    fn to_entity(self) -> OutboxEmail {
+        self.into()
+    }
+}
@@ -0,0 +1,2 @@
+mod outbox_email_response_dto;
+pub use outbox_email_response_dto::*;
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::api::email_outbox::domain::entities::outbox_email::{OutboxEmail, OutboxEmailStatus};
+
+/// An outbox email without its body, which may hold activation or reset tokens
+#[derive(Debug, Serialize)]
+pub struct OutboxEmailResponseDto {
+    pub id: String,
+    pub to: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    pub subject: String,
+    pub status: OutboxEmailStatus,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<OutboxEmail> for OutboxEmailResponseDto {
+    fn from(outbox_email: OutboxEmail) -> Self {
+        Self {
+            id: outbox_email.id,
+            to: outbox_email
+                .email
+                .to
+                .iter()
+                .map(|to| to.address.clone())
+                .collect::<Vec<_>>()
+                .join(", "),
+            from: outbox_email.email.from.map(|from| from.address),
+            subject: outbox_email.email.subject,
+            status: outbox_email.status,
+            attempts: outbox_email.attempts,
+            next_attempt_at: outbox_email.next_attempt_at,
+            last_error: outbox_email.last_error,
+            sent_at: outbox_email.sent_at,
+            created_at: outbox_email.created_at,
+            updated_at: outbox_email.updated_at,
+        }
+    }
+}
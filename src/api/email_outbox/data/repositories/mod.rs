@@ -0,0 +1 @@
+pub mod outbox_email_repository_impl;
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    api::email_outbox::{
+        data::datasources::{
+            outbox_email_datasource::OutboxEmailDatasource,
+            outbox_email_mongo_db::OutboxEmailMongoModel,
+        },
+        domain::{
+            entities::outbox_email::OutboxEmail,
+            repositories::outbox_email_repository::OutboxEmailRepository,
+        },
+    },
+    core::{AppError, CrudRepositoryImpl},
+};
+
+pub struct OutboxEmailRepositoryImpl {
+    datasource: Arc<dyn OutboxEmailDatasource>,
+}
+
+impl OutboxEmailRepositoryImpl {
+    // constructor
+    pub fn new(datasource: Arc<dyn OutboxEmailDatasource>) -> Self {
+        Self { datasource }
+    }
+}
+
+#[async_trait]
+impl CrudRepositoryImpl<OutboxEmail, OutboxEmailMongoModel, dyn OutboxEmailDatasource>
+    for OutboxEmailRepositoryImpl
+{
+    fn get_datasource(&self) -> Arc<dyn OutboxEmailDatasource> {
+        self.datasource.clone()
+    }
+}
+
+#[async_trait]
+impl OutboxEmailRepository for OutboxEmailRepositoryImpl {
+    async fn claim_next_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<OutboxEmail>, AppError> {
+        self.datasource.claim_next_due(now, lease_until).await
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::{Email, EmailOutboxConfig};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutboxEmailStatus {
+    /// Waiting for its next attempt (`next_attempt_at`)
+    #[default]
+    Pending,
+    Sent,
+    /// Gave up after `EmailOutboxConfig::max_attempts`, only an admin retry sends it again
+    Dead,
+}
+
+/// An `Email` queued in the outbox until the `EmailService` provider accepts it
+#[derive(Debug, Clone)]
+pub struct OutboxEmail {
+    pub id: String,
+    pub email: Email,
+    pub status: OutboxEmailStatus,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+
+    // Audit fields
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OutboxEmail {
+    pub fn new(email: Email) -> Self {
+        let now = Utc::now();
+        Self {
+            id: "".to_string(),
+            email,
+            status: OutboxEmailStatus::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            sent_at: None,
+
+            // Audit fields
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// The content of a sent email is dropped, it may carry live tokens (e.g. activation or reset
+    /// PINs). The recipients and the subject are kept for the record
+    pub fn mark_sent(&mut self) {
+        self.status = OutboxEmailStatus::Sent;
+        self.sent_at = Some(Utc::now());
+        self.last_error = None;
+        self.email.content.html_content = String::new();
+        self.email.content.text_content = None;
+        self.email.attachments.clear();
+    }
+
+    /// Schedules the next attempt with an exponential backoff, or dead-letters the email once
+    /// `max_attempts` is reached. `attempts` already counts the failed one (see `claim_next_due`)
+    pub fn mark_failed(&mut self, error: String, config: &EmailOutboxConfig) {
+        self.last_error = Some(error);
+        if self.attempts >= config.max_attempts {
+            self.status = OutboxEmailStatus::Dead;
+        } else {
+            self.status = OutboxEmailStatus::Pending;
+            self.next_attempt_at = Utc::now() + config.backoff(self.attempts);
+        }
+    }
+
+    /// Queues the email again with a fresh set of attempts
+    pub fn retry(&mut self) {
+        self.status = OutboxEmailStatus::Pending;
+        self.attempts = 0;
+        self.next_attempt_at = Utc::now();
+    }
+}
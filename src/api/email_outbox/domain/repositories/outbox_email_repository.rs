@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    api::email_outbox::{
+        data::datasources::{
+            outbox_email_datasource::OutboxEmailDatasource,
+            outbox_email_mongo_db::OutboxEmailMongoModel,
+        },
+        domain::entities::outbox_email::OutboxEmail,
+    },
+    core::{AppError, CrudRepository},
+};
+
+#[async_trait]
+pub trait OutboxEmailRepository:
+    CrudRepository<OutboxEmail, OutboxEmailMongoModel, AppError, dyn OutboxEmailDatasource>
+{
+    /// See `OutboxEmailDatasource::claim_next_due`
+    async fn claim_next_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<OutboxEmail>, AppError>;
+}
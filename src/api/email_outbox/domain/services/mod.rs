@@ -0,0 +1,2 @@
+pub mod outbox_email_service;
+pub use outbox_email_service::*;
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::email_outbox::domain::usecases::EnqueueEmail,
+    core::{Email, EmailService, EmailServiceResult, TemplateRegistry, UseCase},
+};
+
+/// The `EmailService` handed to the handlers: emails are queued in the outbox, which lives in the
+/// same database as the rest of the data, and delivered in the background by the outbox worker
+/// (see `spawn_email_outbox_worker`). A provider outage no longer fails the request that sent
+/// the email.
+pub struct OutboxEmailService {
+    enqueue_email: Arc<EnqueueEmail>,
+    templates: Arc<TemplateRegistry>,
+}
+
+impl OutboxEmailService {
+    pub fn new(enqueue_email: Arc<EnqueueEmail>, templates: Arc<TemplateRegistry>) -> Self {
+        Self {
+            enqueue_email,
+            templates,
+        }
+    }
+}
+
+#[async_trait]
+impl EmailService for OutboxEmailService {
+    async fn send(&self, email: &Email) -> EmailServiceResult<()> {
+        self.enqueue_email.execute(email.clone()).await?;
+
+        Ok(())
+    }
+
+    fn templates(&self) -> &TemplateRegistry {
+        &self.templates
+    }
+}
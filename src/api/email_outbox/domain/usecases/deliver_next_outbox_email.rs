@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api::email_outbox::domain::{
+        entities::outbox_email::OutboxEmail,
+        repositories::outbox_email_repository::OutboxEmailRepository,
+    },
+    core::{AppError, EmailOutboxConfig, EmailService, UseCase},
+};
+
+/// Sends the pending email due the longest through the provider (`transport`), then records
+/// the outcome. Returns `None` when no email is due.
+pub struct DeliverNextOutboxEmail {
+    pub repository: Arc<dyn OutboxEmailRepository>,
+    pub transport: Arc<dyn EmailService>,
+    pub config: EmailOutboxConfig,
+}
+
+impl DeliverNextOutboxEmail {
+    pub fn new(
+        repository: Arc<dyn OutboxEmailRepository>,
+        transport: Arc<dyn EmailService>,
+        config: EmailOutboxConfig,
+    ) -> Self {
+        Self {
+            repository,
+            transport,
+            config,
+        }
+    }
+}
+
+#[async_trait]
+impl UseCase<(), Option<OutboxEmail>> for DeliverNextOutboxEmail {
+    async fn execute(&self, _: ()) -> Result<Option<OutboxEmail>, AppError> {
+        let now = Utc::now();
+        let lease_until = now + self.config.lease_duration();
+
+        let Some(mut outbox_email) = self.repository.claim_next_due(now, lease_until).await? else {
+            return Ok(None);
+        };
+
+        match self.transport.send(&outbox_email.email).await {
+            Ok(()) => outbox_email.mark_sent(),
+            Err(e) => outbox_email.mark_failed(e.to_string(), &self.config),
+        }
+
+        self.repository.update_one(&outbox_email).await.map(Some)
+    }
+}
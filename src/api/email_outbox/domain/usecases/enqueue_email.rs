@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::Notify;
+
+use crate::{
+    api::email_outbox::domain::{
+        entities::outbox_email::OutboxEmail,
+        repositories::outbox_email_repository::OutboxEmailRepository,
+    },
+    core::{datasource::transaction::TransactionRunner, AppError, Email, UseCase},
+};
+
+/// Queues an email and wakes the outbox worker up to deliver it right away. Within a transaction
+/// (see `TransactionRunner`) the email is only queued if the transaction commits
+pub struct EnqueueEmail {
+    pub repository: Arc<dyn OutboxEmailRepository>,
+    pub wake_worker: Arc<Notify>,
+}
+
+impl EnqueueEmail {
+    pub fn new(repository: Arc<dyn OutboxEmailRepository>, wake_worker: Arc<Notify>) -> Self {
+        Self {
+            repository,
+            wake_worker,
+        }
+    }
+}
+
+#[async_trait]
+impl UseCase<Email, OutboxEmail> for EnqueueEmail {
+    async fn execute(&self, email: Email) -> Result<OutboxEmail, AppError> {
+        let queued = self.repository.create_one(&OutboxEmail::new(email)).await?;
+        // The worker cannot see the email before the transaction commits
+        let wake_worker = Arc::clone(&self.wake_worker);
+        TransactionRunner::after_commit(move || wake_worker.notify_one());
+        Ok(queued)
+    }
+}
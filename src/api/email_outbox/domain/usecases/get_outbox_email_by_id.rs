@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::email_outbox::domain::{
+        entities::outbox_email::OutboxEmail,
+        repositories::outbox_email_repository::OutboxEmailRepository,
+    },
+    core::{AppError, UseCase},
+};
+
+pub struct GetOutboxEmailById {
+    pub repository: Arc<dyn OutboxEmailRepository>,
+}
+
+impl GetOutboxEmailById {
+    pub fn new(repository: Arc<dyn OutboxEmailRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<String, OutboxEmail> for GetOutboxEmailById {
+    async fn execute(&self, id: String) -> Result<OutboxEmail, AppError> {
+        self.repository.find_one_by_id(&id).await
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::email_outbox::domain::{
+        entities::outbox_email::OutboxEmail,
+        repositories::outbox_email_repository::OutboxEmailRepository,
+    },
+    core::{
+        pagination::{PaginatedParams, PaginatedResponse},
+        AppError, UseCase,
+    },
+};
+
+pub struct GetOutboxEmails {
+    pub repository: Arc<dyn OutboxEmailRepository>,
+}
+
+impl GetOutboxEmails {
+    pub fn new(repository: Arc<dyn OutboxEmailRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl UseCase<PaginatedParams, PaginatedResponse<OutboxEmail>> for GetOutboxEmails {
+    async fn execute(
+        &self,
+        params: PaginatedParams,
+    ) -> Result<PaginatedResponse<OutboxEmail>, AppError> {
+        self.repository.find(params).await
+    }
+}
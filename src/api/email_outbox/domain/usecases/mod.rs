@@ -0,0 +1,14 @@
+pub mod enqueue_email;
+pub use enqueue_email::*;
+
+pub mod deliver_next_outbox_email;
+pub use deliver_next_outbox_email::*;
+
+pub mod get_outbox_emails;
+pub use get_outbox_emails::*;
+
+pub mod get_outbox_email_by_id;
+pub use get_outbox_email_by_id::*;
+
+pub mod retry_outbox_email;
+pub use retry_outbox_email::*;
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::Notify;
+
+use crate::{
+    api::email_outbox::domain::{
+        entities::outbox_email::{OutboxEmail, OutboxEmailStatus},
+        repositories::outbox_email_repository::OutboxEmailRepository,
+    },
+    core::{AppError, UseCase},
+};
+
+/// Queues a dead-lettered (or still pending) email again with a fresh set of attempts
+pub struct RetryOutboxEmail {
+    pub repository: Arc<dyn OutboxEmailRepository>,
+    pub wake_worker: Arc<Notify>,
+}
+
+impl RetryOutboxEmail {
+    pub fn new(repository: Arc<dyn OutboxEmailRepository>, wake_worker: Arc<Notify>) -> Self {
+        Self {
+            repository,
+            wake_worker,
+        }
+    }
+}
+
+#[async_trait]
+impl UseCase<String, OutboxEmail> for RetryOutboxEmail {
+    async fn execute(&self, id: String) -> Result<OutboxEmail, AppError> {
+        let mut outbox_email = self.repository.find_one_by_id(&id).await?;
+
+        if outbox_email.status == OutboxEmailStatus::Sent {
+            return Err(AppError::InvalidInput(
+                "This email has already been sent".to_string(),
+            ));
+        }
+
+        outbox_email.retry();
+        let outbox_email = self.repository.update_one(&outbox_email).await?;
+        self.wake_worker.notify_one();
+
+        Ok(outbox_email)
+    }
+}
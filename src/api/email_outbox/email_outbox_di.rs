@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use mongodb::Database;
+use tokio::sync::Notify;
+
+use crate::{
+    api::email_outbox::{
+        data::{
+            datasources::{
+                outbox_email_datasource::OutboxEmailDatasource,
+                outbox_email_in_memory::OutboxEmailInMemoryDatasourceImpl,
+                outbox_email_mongo_db::OutboxEmailMongoDatasourceImpl,
+            },
+            repositories::outbox_email_repository_impl::OutboxEmailRepositoryImpl,
+        },
+        domain::usecases::*,
+    },
+    core::{AppError, EmailOutboxConfig, EmailService},
+};
+
+pub struct EmailOutboxDi {
+    pub enqueue_email: Arc<EnqueueEmail>,
+    pub deliver_next_outbox_email: Arc<DeliverNextOutboxEmail>,
+    pub get_outbox_emails: Arc<GetOutboxEmails>,
+    pub get_outbox_email_by_id: Arc<GetOutboxEmailById>,
+    pub retry_outbox_email: Arc<RetryOutboxEmail>,
+    /// Notified whenever an email is ready to be delivered
+    pub wake_worker: Arc<Notify>,
+}
+
+impl EmailOutboxDi {
+    /// `transport` is the provider the outbox worker delivers the emails with
+    pub fn new(db: &Database, transport: Arc<dyn EmailService>, config: EmailOutboxConfig) -> Self {
+        /* ························································ [ Datasource Implementation ] */
+        Self::with_datasource(
+            Arc::new(OutboxEmailMongoDatasourceImpl::new(db)),
+            transport,
+            config,
+        )
+    }
+
+    /// Prepares the collections of the feature, to be run once at startup before serving
+    pub async fn prepare_database(db: &Database) -> Result<(), AppError> {
+        OutboxEmailMongoDatasourceImpl::new(db).prepare().await
+    }
+
+    /// Wires the feature against an in-memory datasource (tests and local development)
+    pub fn in_memory(transport: Arc<dyn EmailService>, config: EmailOutboxConfig) -> Self {
+        Self::with_datasource(
+            Arc::new(OutboxEmailInMemoryDatasourceImpl::new()),
+            transport,
+            config,
+        )
+    }
+
+    pub fn with_datasource(
+        datasource: Arc<dyn OutboxEmailDatasource>,
+        transport: Arc<dyn EmailService>,
+        config: EmailOutboxConfig,
+    ) -> Self {
+        /* ························································ [ Repository Implementation ] */
+        let repository = Arc::new(OutboxEmailRepositoryImpl::new(datasource));
+        let wake_worker = Arc::new(Notify::new());
+
+        /* ········································································· [ Usecases ] */
+        let enqueue_email = Arc::new(EnqueueEmail::new(repository.clone(), wake_worker.clone()));
+        let deliver_next_outbox_email = Arc::new(DeliverNextOutboxEmail::new(
+            repository.clone(),
+            transport,
+            config,
+        ));
+        let get_outbox_emails = Arc::new(GetOutboxEmails::new(repository.clone()));
+        let get_outbox_email_by_id = Arc::new(GetOutboxEmailById::new(repository.clone()));
+        let retry_outbox_email = Arc::new(RetryOutboxEmail::new(
+            repository.clone(),
+            wake_worker.clone(),
+        ));
+
+        Self {
+            enqueue_email,
+            deliver_next_outbox_email,
+            get_outbox_emails,
+            get_outbox_email_by_id,
+            retry_outbox_email,
+            wake_worker,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::{sync::Arc, time::Duration};
+
+use presentation::handlers::*;
+use warp::{reject::Rejection, reply::Reply, Filter};
+
+use crate::{
+    api::email_outbox::domain::entities::outbox_email::OutboxEmailStatus, core::UseCase,
+    di::ServiceLocator,
+};
+
+pub mod data;
+pub mod domain;
+pub mod email_outbox_di;
+pub mod presentation;
+
+/// Inspect the emails queued by `OutboxEmailService` and retry the dead-lettered ones. Admin only.
+pub struct EmailOutboxFeature {
+    /// [GET] /email-outbox
+    get_outbox_emails_handler: Arc<GetOutboxEmailsHandler>,
+    /// [GET] /email-outbox/[String]
+    get_outbox_email_handler: Arc<GetOutboxEmailHandler>,
+    /// [POST] /email-outbox/[String]/retry
+    retry_outbox_email_handler: Arc<RetryOutboxEmailHandler>,
+}
+
+impl EmailOutboxFeature {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self {
+            get_outbox_emails_handler: Arc::new(GetOutboxEmailsHandler::new(sl.clone())),
+            get_outbox_email_handler: Arc::new(GetOutboxEmailHandler::new(sl.clone())),
+            retry_outbox_email_handler: Arc::new(RetryOutboxEmailHandler::new(sl.clone())),
+        }
+    }
+
+    pub fn routes(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        // [GET] api/email-outbox
+        Arc::clone(&self.get_outbox_emails_handler)
+            .route()
+            // [GET] api/email-outbox/<String>
+            .or(Arc::clone(&self.get_outbox_email_handler).route())
+            // [POST] api/email-outbox/<String>/retry
+            .or(Arc::clone(&self.retry_outbox_email_handler).route())
+    }
+}
+
+/// Delivers the queued emails in the background.
+///
+/// Wakes up as soon as an email is queued, and every `EmailOutboxConfig::poll_interval` seconds
+/// for the retries that became due.
+pub fn spawn_email_outbox_worker(sl: Arc<ServiceLocator>) {
+    let deliver = sl.deliver_next_outbox_email();
+    let wake_worker = sl.email_outbox_wake_worker();
+    let poll_interval = Duration::from_secs(deliver.config.poll_interval.max(1));
+
+    tokio::spawn(async move {
+        loop {
+            // Everything due is delivered before waiting again
+            loop {
+                match deliver.execute(()).await {
+                    Ok(Some(outbox_email)) => match outbox_email.status {
+                        OutboxEmailStatus::Sent => {}
+                        OutboxEmailStatus::Pending => eprintln!(
+                            "Could not send email {} (attempt {}), retrying at {}: {}",
+                            outbox_email.id,
+                            outbox_email.attempts,
+                            outbox_email.next_attempt_at,
+                            outbox_email.last_error.unwrap_or_default()
+                        ),
+                        OutboxEmailStatus::Dead => eprintln!(
+                            "Gave up sending email {} after {} attempts: {}",
+                            outbox_email.id,
+                            outbox_email.attempts,
+                            outbox_email.last_error.unwrap_or_default()
+                        ),
+                    },
+                    Ok(None) => break,
+                    Err(e) => {
+                        eprintln!("Email outbox delivery failed: {}", e);
+                        break;
+                    }
+                }
+            }
+
+            tokio::select! {
+                _ = wake_worker.notified() => {}
+                _ = tokio::time::sleep(poll_interval) => {}
+            }
+        }
+    });
+}
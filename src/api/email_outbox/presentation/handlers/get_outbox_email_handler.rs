@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::{auth::domain::entities::Claims, email_outbox::data::dtos::OutboxEmailResponseDto},
+    core::{
+        middleware::{admin_middleware, scoped_auth_middleware},
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// One outbox email with its delivery attempts. Admin only.
+pub struct GetOutboxEmailHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl GetOutboxEmailHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(self: Arc<Self>, id: String) -> Result<impl Reply, Rejection> {
+        let outbox_email = self.sl.get_outbox_email_by_id().execute(id).await?;
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::loaded_success("Outbox email");
+        let response = ApiResponse::success(msg, Some(OutboxEmailResponseDto::from(outbox_email)));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("email-outbox" / String)
+            .and(warp::get())
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "email-outbox:read",
+            ))
+            .and_then(|id: String, claims: Claims| async move {
+                admin_middleware(claims).await?;
+                Ok::<String, Rejection>(id)
+            })
+            .and_then(move |id: String| {
+                let handler = self.clone();
+                async move { handler.handle(id).await }
+            })
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::{auth::domain::entities::Claims, email_outbox::data::dtos::OutboxEmailResponseDto},
+    core::{
+        middleware::{admin_middleware, scoped_auth_middleware},
+        pagination::PaginatedParams,
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// The outbox emails, e.g. `?status=dead` for the dead-lettered ones. Admin only.
+pub struct GetOutboxEmailsHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl GetOutboxEmailsHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(self: Arc<Self>, params: PaginatedParams) -> Result<impl Reply, Rejection> {
+        let paginated_response = self.sl.get_outbox_emails().execute(params).await?;
+
+        let records = paginated_response
+            .records
+            .iter()
+            .cloned()
+            .map(OutboxEmailResponseDto::from)
+            .collect();
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::loaded_success("Outbox emails");
+        let response = ApiResponse::success(msg, Some(paginated_response.with_records(records)));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("email-outbox")
+            .and(warp::get())
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "email-outbox:read",
+            ))
+            .and_then(admin_middleware)
+            .and(warp::query::<PaginatedParams>())
+            .and_then(move |_claims: Claims, params: PaginatedParams| {
+                let handler = self.clone();
+                async move { handler.handle(params).await }
+            })
+    }
+}
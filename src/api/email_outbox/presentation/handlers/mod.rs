@@ -0,0 +1,7 @@
+mod get_outbox_email_handler;
+mod get_outbox_emails_handler;
+mod retry_outbox_email_handler;
+
+pub use get_outbox_email_handler::*;
+pub use get_outbox_emails_handler::*;
+pub use retry_outbox_email_handler::*;
@@ -1,2 +1,6 @@
+pub mod api_key;
 pub mod auth;
 pub mod auth_token;
+pub mod dev_mailbox;
+pub mod email_outbox;
+pub mod storage;
@@ -0,0 +1,4 @@
+mod signed_url_query_dto;
+mod storage_file_dto;
+pub use signed_url_query_dto::*;
+pub use storage_file_dto::*;
@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+/// Query string of a link built by `StorageService::get_signed_path`
+#[derive(Debug, Deserialize)]
+pub struct SignedUrlQueryDto {
+    /// Unix timestamp after which the link stops working
+    pub expires: i64,
+    /// Hex encoded HMAC of the file key and `expires`
+    pub signature: String,
+}
@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct StorageFileDto {
+    /// The stored (safe) file name, use it to download or delete the file
+    pub filename: String,
+    /// `/uploads/{entity_dir}/{entity_id}/{filename}`
+    pub path: String,
+    /// A link usable without an `Authorization` header until it expires
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Names of the image variants generated on upload
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<String>,
+}
@@ -0,0 +1,2 @@
+pub mod orphan_storage_dir;
+pub use orphan_storage_dir::OrphanStorageDir;
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+use crate::core::StorageUsage;
+
+/// A `{entity_dir}/{entity_id}` directory whose user no longer exists
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanStorageDir {
+    pub entity_dir: String,
+    pub entity_id: String,
+    pub usage: StorageUsage,
+}
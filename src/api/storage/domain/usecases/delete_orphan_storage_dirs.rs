@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    api::storage::domain::{entities::OrphanStorageDir, usecases::FindOrphanStorageDirs},
+    core::{AppError, StorageService, UseCase},
+};
+
+/// Deletes the directories found by `FindOrphanStorageDirs`, returns what was deleted
+pub struct DeleteOrphanStorageDirs {
+    storage: Arc<StorageService>,
+    find_orphan_storage_dirs: Arc<FindOrphanStorageDirs>,
+}
+
+impl DeleteOrphanStorageDirs {
+    pub fn new(
+        storage: Arc<StorageService>,
+        find_orphan_storage_dirs: Arc<FindOrphanStorageDirs>,
+    ) -> Self {
+        Self {
+            storage,
+            find_orphan_storage_dirs,
+        }
+    }
+}
+
+#[async_trait]
+impl UseCase<Vec<String>, Vec<OrphanStorageDir>> for DeleteOrphanStorageDirs {
+    async fn execute(&self, entity_dirs: Vec<String>) -> Result<Vec<OrphanStorageDir>, AppError> {
+        let orphans = self.find_orphan_storage_dirs.execute(entity_dirs).await?;
+
+        let mut deleted = Vec::with_capacity(orphans.len());
+        for orphan in orphans {
+            match self
+                .storage
+                .delete_entity_storage_dir(&orphan.entity_id, &orphan.entity_dir)
+                .await
+            {
+                Ok(()) => deleted.push(orphan),
+                // Deleted in the meantime
+                Err(AppError::UserDirectoryNotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,75 @@
+use std::{collections::HashSet, sync::Arc};
+
+use async_trait::async_trait;
+use bson::oid::ObjectId;
+
+use crate::{
+    api::{auth::domain::usecases::GetManyUsers, storage::domain::entities::OrphanStorageDir},
+    core::{pagination::PaginatedParams, AppError, StorageService, UseCase},
+};
+
+/// Users looked up per query
+const BATCH_SIZE: usize = 100;
+
+/// Lists the directories of the given `entity_dir`s whose entity id is not a user anymore.
+///
+/// Only ids shaped as user ids (i.e. ObjectIds) are considered, anything else was not created
+/// for a user and is left alone.
+pub struct FindOrphanStorageDirs {
+    storage: Arc<StorageService>,
+    get_many_users: Arc<GetManyUsers>,
+}
+
+impl FindOrphanStorageDirs {
+    pub fn new(storage: Arc<StorageService>, get_many_users: Arc<GetManyUsers>) -> Self {
+        Self {
+            storage,
+            get_many_users,
+        }
+    }
+
+    async fn existing_user_ids(&self, ids: &[String]) -> Result<HashSet<String>, AppError> {
+        let mut params = PaginatedParams::new();
+        params.limit = ids.len() as i32;
+        params.query.insert("_id.in".to_string(), ids.join(","));
+
+        let users = self.get_many_users.execute(params).await?;
+        Ok(users.records.into_iter().map(|user| user.id).collect())
+    }
+}
+
+#[async_trait]
+impl UseCase<Vec<String>, Vec<OrphanStorageDir>> for FindOrphanStorageDirs {
+    async fn execute(&self, entity_dirs: Vec<String>) -> Result<Vec<OrphanStorageDir>, AppError> {
+        let mut orphans = Vec::new();
+
+        for entity_dir in entity_dirs {
+            let ids: Vec<String> = self
+                .storage
+                .backend()
+                .list_dirs(&entity_dir)
+                .await?
+                .into_iter()
+                .filter(|id| ObjectId::parse_str(id).is_ok())
+                .collect();
+
+            for batch in ids.chunks(BATCH_SIZE) {
+                let existing = self.existing_user_ids(batch).await?;
+
+                for entity_id in batch.iter().filter(|id| !existing.contains(*id)) {
+                    let usage = self
+                        .storage
+                        .entity_dir_usage(entity_id, &entity_dir)
+                        .await?;
+                    orphans.push(OrphanStorageDir {
+                        entity_dir: entity_dir.clone(),
+                        entity_id: entity_id.clone(),
+                        usage,
+                    });
+                }
+            }
+        }
+
+        Ok(orphans)
+    }
+}
@@ -0,0 +1,4 @@
+pub mod find_orphan_storage_dirs;
+pub use find_orphan_storage_dirs::*;
+pub mod delete_orphan_storage_dirs;
+pub use delete_orphan_storage_dirs::*;
@@ -0,0 +1,126 @@
+use std::{sync::Arc, time::Duration};
+
+use presentation::handlers::*;
+use tokio::time::Instant;
+use warp::{reject::Rejection, reply::Reply, Filter};
+
+use crate::{core::UseCase, di::ServiceLocator};
+
+pub mod data;
+pub mod domain;
+pub mod presentation;
+pub mod storage_di;
+
+/// Upload, download, list and delete the files the `StorageService` keeps for an entity.
+///
+/// Files live under `uploads/{entity_dir}/{entity_id}` which matches
+/// `StorageService::get_public_path`. Only the entity owner (i.e. `entity_id` is the user id) or
+/// an admin can access them, unless they follow a signed link (see
+/// `StorageService::get_signed_path`).
+///
+/// What each entity stores is reported under `storage/usage`, and the `user_dirs` left by
+/// deleted users under `storage/orphans`.
+pub struct StorageFeature {
+    /// [POST] /uploads/[String]/[String]
+    upload_entity_files_handler: Arc<UploadEntityFilesHandler>,
+    /// [GET] /uploads/[String]/[String]
+    get_entity_files_handler: Arc<GetEntityFilesHandler>,
+    /// [GET] /uploads/[String]/[String]/[String]
+    download_entity_file_handler: Arc<DownloadEntityFileHandler>,
+    /// [GET] /uploads/signed/[String]/[String]/[String]?expires=[i64]&signature=[String]
+    download_signed_entity_file_handler: Arc<DownloadSignedEntityFileHandler>,
+    /// [DELETE] /uploads/[String]/[String]/[String]
+    delete_entity_file_handler: Arc<DeleteEntityFileHandler>,
+    /// [GET] /storage/usage/[String]
+    get_entity_storage_usage_handler: Arc<GetEntityStorageUsageHandler>,
+    /// [GET] /storage/usage
+    get_storage_usage_report_handler: Arc<GetStorageUsageReportHandler>,
+    /// [GET] /storage/orphans
+    get_orphan_storage_dirs_handler: Arc<GetOrphanStorageDirsHandler>,
+    /// [DELETE] /storage/orphans
+    delete_orphan_storage_dirs_handler: Arc<DeleteOrphanStorageDirsHandler>,
+}
+
+impl StorageFeature {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self {
+            upload_entity_files_handler: Arc::new(UploadEntityFilesHandler::new(sl.clone())),
+            get_entity_files_handler: Arc::new(GetEntityFilesHandler::new(sl.clone())),
+            download_entity_file_handler: Arc::new(DownloadEntityFileHandler::new(sl.clone())),
+            download_signed_entity_file_handler: Arc::new(DownloadSignedEntityFileHandler::new(
+                sl.clone(),
+            )),
+            delete_entity_file_handler: Arc::new(DeleteEntityFileHandler::new(sl.clone())),
+            get_entity_storage_usage_handler: Arc::new(GetEntityStorageUsageHandler::new(
+                sl.clone(),
+            )),
+            get_storage_usage_report_handler: Arc::new(GetStorageUsageReportHandler::new(
+                sl.clone(),
+            )),
+            get_orphan_storage_dirs_handler: Arc::new(GetOrphanStorageDirsHandler::new(sl.clone())),
+            delete_orphan_storage_dirs_handler: Arc::new(DeleteOrphanStorageDirsHandler::new(
+                sl.clone(),
+            )),
+        }
+    }
+
+    pub fn routes(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        // [POST] api/uploads/<String>/<String>
+        Arc::clone(&self.upload_entity_files_handler)
+            .route()
+            // [GET] api/uploads/<String>/<String>
+            .or(Arc::clone(&self.get_entity_files_handler).route())
+            // [GET] api/uploads/<String>/<String>/<String>
+            .or(Arc::clone(&self.download_entity_file_handler).route())
+            // [GET] api/uploads/signed/<String>/<String>/<String>
+            // After the download route which also matches it (and asks for a bearer token): warp
+            // reports the rejection of the last route tried
+            .or(Arc::clone(&self.download_signed_entity_file_handler).route())
+            // [DELETE] api/uploads/<String>/<String>/<String>
+            .or(Arc::clone(&self.delete_entity_file_handler).route())
+            // [GET] api/storage/usage/<String>
+            .or(Arc::clone(&self.get_entity_storage_usage_handler).route())
+            // [GET] api/storage/usage
+            .or(Arc::clone(&self.get_storage_usage_report_handler).route())
+            // [GET] api/storage/orphans
+            .or(Arc::clone(&self.get_orphan_storage_dirs_handler).route())
+            // [DELETE] api/storage/orphans
+            .or(Arc::clone(&self.delete_orphan_storage_dirs_handler).route())
+    }
+}
+
+/// Deletes the orphaned `user_dirs` every `orphan_sweep_interval` seconds.
+///
+/// Does nothing unless both are configured. The first sweep runs one interval after start up.
+pub fn spawn_orphan_storage_sweeper(sl: Arc<ServiceLocator>) {
+    let storage = sl.storage_service();
+    let config = storage.config();
+    let (Some(interval), false) = (config.orphan_sweep_interval, config.user_dirs.is_empty())
+    else {
+        return;
+    };
+    let period = Duration::from_secs(interval.max(1));
+    let user_dirs = config.user_dirs.clone();
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval_at(Instant::now() + period, period);
+        loop {
+            ticker.tick().await;
+            match sl
+                .delete_orphan_storage_dirs()
+                .execute(user_dirs.clone())
+                .await
+            {
+                Ok(deleted) => {
+                    for orphan in deleted {
+                        eprintln!(
+                            "Deleted orphan storage dir {}/{} ({} bytes)",
+                            orphan.entity_dir, orphan.entity_id, orphan.usage.bytes
+                        );
+                    }
+                }
+                Err(e) => eprintln!("Orphan storage sweep failed: {}", e),
+            }
+        }
+    });
+}
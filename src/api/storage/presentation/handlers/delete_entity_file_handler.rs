@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use super::decode_segment;
+use crate::{
+    api::auth::domain::entities::Claims,
+    core::{
+        middleware::{owner_or_admin_middleware, scoped_auth_middleware},
+        response::ApiResponse,
+        MsgBuilder,
+    },
+    di::ServiceLocator,
+};
+
+pub struct DeleteEntityFileHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl DeleteEntityFileHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        entity_dir: String,
+        entity_id: String,
+        filename: String,
+        claims: Claims,
+    ) -> Result<impl Reply, Rejection> {
+        owner_or_admin_middleware(entity_id.clone(), claims).await?;
+
+        let filename = decode_segment(&filename)?;
+        self.sl
+            .storage_service()
+            .delete_entity_file(&entity_id, &entity_dir, &filename)
+            .await?;
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::deleted_success("File");
+        let response = ApiResponse::<()>::success(msg, None);
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("uploads" / String / String / String)
+            .and(warp::delete())
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "storage:write",
+            ))
+            .and_then(
+                move |entity_dir: String, entity_id: String, filename: String, claims: Claims| {
+                    let handler = self.clone();
+                    async move {
+                        handler
+                            .handle(entity_dir, entity_id, filename, claims)
+                            .await
+                    }
+                },
+            )
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::auth::domain::entities::Claims,
+    core::{
+        middleware::{admin_middleware, scoped_auth_middleware},
+        response::ApiResponse,
+        MsgBuilder, UseCase,
+    },
+    di::ServiceLocator,
+};
+
+/// Deletes the `user_dirs` directories left by users that no longer exist and returns them.
+/// Admin only, the same sweep runs every `orphan_sweep_interval` seconds when configured.
+pub struct DeleteOrphanStorageDirsHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl DeleteOrphanStorageDirsHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(self: Arc<Self>) -> Result<impl Reply, Rejection> {
+        let user_dirs = self.sl.storage_service().config().user_dirs.clone();
+        let orphans = self
+            .sl
+            .delete_orphan_storage_dirs()
+            .execute(user_dirs)
+            .await?;
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::deleted_success("Orphan storage directories");
+        let response = ApiResponse::success(msg, Some(orphans));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("storage" / "orphans")
+            .and(warp::delete())
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "storage:write",
+            ))
+            .and_then(admin_middleware)
+            .and_then(move |_claims: Claims| {
+                let handler = self.clone();
+                async move { handler.handle().await }
+            })
+    }
+}
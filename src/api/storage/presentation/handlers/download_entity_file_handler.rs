@@ -0,0 +1,298 @@
+use std::{ops::RangeInclusive, sync::Arc};
+
+use futures::TryStreamExt;
+use warp::{
+    filters::{path::Peek, BoxedFilter},
+    http::{
+        header::{ACCEPT_RANGES, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE},
+        Response, StatusCode,
+    },
+    reject::Rejection,
+    reply::Reply,
+    Filter,
+};
+
+use super::decode_segment;
+use crate::{
+    api::auth::domain::entities::Claims,
+    core::{
+        middleware::{owner_or_admin_middleware, scoped_auth_middleware},
+        AppError, StorageBackendConfig, StorageService,
+    },
+    di::ServiceLocator,
+};
+
+/// Serves an entity's file with the `Content-Type` guessed from its extension.
+///
+/// With the `FileSystem` backend the file is handed to `warp::fs` once access is granted, so it is
+/// streamed from disk (with `Range` and conditional requests support).
+///
+/// Other backends are read through the `StorageService`. A single `Range` is honoured and only
+/// the requested bytes are fetched from the backend, but they are buffered before being sent
+/// since warp does not expose a streaming body outside of `warp::fs`.
+pub struct DownloadEntityFileHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl DownloadEntityFileHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        entity_dir: String,
+        entity_id: String,
+        filename: String,
+        range: Option<String>,
+        claims: Claims,
+    ) -> Result<impl Reply, Rejection> {
+        owner_or_admin_middleware(entity_id.clone(), claims).await?;
+
+        let filename = decode_segment(&filename)?;
+        let storage = self.sl.storage_service();
+
+        let response =
+            serve_entity_file(&storage, &entity_id, &entity_dir, &filename, range).await?;
+        Ok(response)
+    }
+
+    /// Grants access to `{entity_dir}/{entity_id}/{filename}` before `warp::fs` serves it
+    async fn authorize(self: Arc<Self>, tail: Peek, claims: Claims) -> Result<(), Rejection> {
+        let segments = tail
+            .segments()
+            .map(decode_segment)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let [entity_dir, entity_id, filename] = segments.as_slice() else {
+            return Err(warp::reject::not_found());
+        };
+
+        owner_or_admin_middleware(entity_id.clone(), claims).await?;
+
+        // Answer with our own error rather than warp's generic not found
+        let stat = self
+            .sl
+            .storage_service()
+            .stat_entity_file(entity_id, entity_dir, filename)
+            .await?;
+        if stat.is_none() {
+            return Err(AppError::FileNotFound(filename.to_string()).into());
+        }
+
+        Ok(())
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        let storage = self.sl.storage_service();
+
+        match storage.config().backend {
+            StorageBackendConfig::FileSystem => self.file_system_route(&storage.config().base_path),
+            _ => self.backend_route(),
+        }
+    }
+
+    fn file_system_route(self: Arc<Self>, base_path: &str) -> BoxedFilter<(Box<dyn Reply>,)> {
+        warp::path("uploads")
+            .and(warp::get())
+            .and(warp::path::peek())
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "storage:read",
+            ))
+            .and_then(move |tail: Peek, claims: Claims| {
+                let handler = self.clone();
+                async move { handler.authorize(tail, claims).await }
+            })
+            .untuple_one()
+            .and(warp::fs::dir(base_path.to_string()))
+            .map(|file| Box::new(file) as Box<dyn Reply>)
+            .boxed()
+    }
+
+    fn backend_route(self: Arc<Self>) -> BoxedFilter<(Box<dyn Reply>,)> {
+        warp::path!("uploads" / String / String / String)
+            .and(warp::get())
+            .and(warp::header::optional::<String>("range"))
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "storage:read",
+            ))
+            .and_then(
+                move |entity_dir: String,
+                      entity_id: String,
+                      filename: String,
+                      range: Option<String>,
+                      claims: Claims| {
+                    let handler = self.clone();
+                    async move {
+                        handler
+                            .handle(entity_dir, entity_id, filename, range, claims)
+                            .await
+                    }
+                },
+            )
+            .map(|reply| Box::new(reply) as Box<dyn Reply>)
+            .boxed()
+    }
+}
+
+/// Streams (or sends the requested `Range` of) an entity's file through the `StorageService`,
+/// access must have been granted beforehand
+pub(super) async fn serve_entity_file(
+    storage: &StorageService,
+    entity_id: &str,
+    entity_dir: &str,
+    filename: &str,
+    range: Option<String>,
+) -> Result<Response<Vec<u8>>, AppError> {
+    let Some(stat) = storage
+        .stat_entity_file(entity_id, entity_dir, filename)
+        .await?
+    else {
+        return Err(AppError::FileNotFound(filename.to_string()));
+    };
+
+    let mime = mime_guess::from_path(filename).first_or_octet_stream();
+    let response = Response::builder()
+        .header(CONTENT_TYPE, mime.as_ref())
+        .header(ACCEPT_RANGES, "bytes");
+
+    /* Whole file or requested range ························································ */
+    let (response, stream) = match RequestedRange::parse(range.as_deref(), stat.size) {
+        RequestedRange::Full => {
+            let stream = storage
+                .stream_entity_file(entity_id, entity_dir, filename)
+                .await?;
+            (response.status(StatusCode::OK), stream)
+        }
+        RequestedRange::Partial(range) => {
+            let content_range = format!("bytes {}-{}/{}", range.start(), range.end(), stat.size);
+            let stream = storage
+                .stream_entity_file_range(entity_id, entity_dir, filename, range)
+                .await?;
+            let response = response
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(CONTENT_RANGE, content_range);
+            (response, stream)
+        }
+        RequestedRange::Unsatisfiable => {
+            let response = response
+                .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                .header(CONTENT_RANGE, format!("bytes */{}", stat.size))
+                .body(Vec::new())
+                .map_err(|e| AppError::InternalServer(e.to_string()))?;
+            return Ok(response);
+        }
+    };
+
+    let content = stream
+        .try_fold(Vec::new(), |mut content, chunk| async move {
+            content.extend_from_slice(&chunk);
+            Ok(content)
+        })
+        .await?;
+
+    let response = response
+        .header(CONTENT_LENGTH, content.len())
+        .body(content)
+        .map_err(|e| AppError::InternalServer(e.to_string()))?;
+
+    Ok(response)
+}
+
+/// The part of a file asked by the `Range` header
+#[derive(Debug, PartialEq)]
+enum RequestedRange {
+    Full,
+    Partial(RangeInclusive<u64>),
+    Unsatisfiable,
+}
+
+impl RequestedRange {
+    /// Only single byte ranges are supported (`bytes=0-99`, `bytes=100-` or `bytes=-100`). Any
+    /// other value is ignored and the whole file is sent, as allowed by RFC 9110
+    fn parse(header: Option<&str>, size: u64) -> Self {
+        let Some(spec) = header.and_then(|h| h.trim().strip_prefix("bytes=")) else {
+            return Self::Full;
+        };
+        let Some((start, end)) = spec.split_once('-') else {
+            return Self::Full;
+        };
+        if spec.contains(',') {
+            return Self::Full;
+        }
+
+        let (start, end) = (start.trim(), end.trim());
+        let range = match (start.parse::<u64>().ok(), end.parse::<u64>().ok()) {
+            // bytes=-100 => the last 100 bytes
+            (None, Some(suffix)) if start.is_empty() => {
+                if suffix == 0 {
+                    return Self::Unsatisfiable;
+                }
+                size.saturating_sub(suffix)..=size.saturating_sub(1)
+            }
+            // bytes=100-
+            (Some(start), None) if end.is_empty() => start..=size.saturating_sub(1),
+            // bytes=0-99
+            (Some(start), Some(end)) if start <= end => start..=end.min(size.saturating_sub(1)),
+            _ => return Self::Full,
+        };
+
+        match size > 0 && range.start() < &size {
+            true => Self::Partial(range),
+            false => Self::Unsatisfiable,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(header: &str) -> RequestedRange {
+        RequestedRange::parse(Some(header), 1000)
+    }
+
+    #[test]
+    fn parses_closed_ranges() {
+        assert_eq!(parse("bytes=0-99"), RequestedRange::Partial(0..=99));
+        assert_eq!(parse("bytes=999-999"), RequestedRange::Partial(999..=999));
+        // The end is clamped to the last byte
+        assert_eq!(parse("bytes=500-5000"), RequestedRange::Partial(500..=999));
+    }
+
+    #[test]
+    fn parses_open_and_suffix_ranges() {
+        assert_eq!(parse("bytes=900-"), RequestedRange::Partial(900..=999));
+        assert_eq!(parse("bytes=-100"), RequestedRange::Partial(900..=999));
+        // A suffix longer than the file is the whole file
+        assert_eq!(parse("bytes=-5000"), RequestedRange::Partial(0..=999));
+    }
+
+    #[test]
+    fn refuses_ranges_past_the_end() {
+        assert_eq!(parse("bytes=1000-"), RequestedRange::Unsatisfiable);
+        assert_eq!(parse("bytes=1000-1999"), RequestedRange::Unsatisfiable);
+        assert_eq!(parse("bytes=-0"), RequestedRange::Unsatisfiable);
+        assert_eq!(
+            RequestedRange::parse(Some("bytes=0-"), 0),
+            RequestedRange::Unsatisfiable
+        );
+    }
+
+    #[test]
+    fn sends_the_whole_file_otherwise() {
+        assert_eq!(RequestedRange::parse(None, 1000), RequestedRange::Full);
+        assert_eq!(parse("bytes=0-99,200-299"), RequestedRange::Full);
+        assert_eq!(parse("bytes=99-0"), RequestedRange::Full);
+        assert_eq!(parse("bytes=a-b"), RequestedRange::Full);
+        assert_eq!(parse("items=0-99"), RequestedRange::Full);
+    }
+}
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use warp::{
+    filters::{path::Peek, BoxedFilter},
+    reject::Rejection,
+    reply::Reply,
+    Filter,
+};
+
+use super::{decode_segment, download_entity_file_handler::serve_entity_file};
+use crate::{
+    api::storage::data::dtos::SignedUrlQueryDto,
+    core::{AppError, StorageBackendConfig},
+    di::ServiceLocator,
+};
+
+/// Serves an entity's file from a link built by `StorageService::get_signed_path`, no
+/// `Authorization` header needed: the signature grants access until the link expires.
+///
+/// Files are served the same way as `DownloadEntityFileHandler` does.
+pub struct DownloadSignedEntityFileHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl DownloadSignedEntityFileHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        entity_dir: String,
+        entity_id: String,
+        filename: String,
+        query: SignedUrlQueryDto,
+        range: Option<String>,
+    ) -> Result<impl Reply, Rejection> {
+        let filename = decode_segment(&filename)?;
+        let storage = self.sl.storage_service();
+
+        storage.verify_signed_path(
+            &entity_id,
+            &entity_dir,
+            &filename,
+            query.expires,
+            &query.signature,
+        )?;
+
+        let response =
+            serve_entity_file(&storage, &entity_id, &entity_dir, &filename, range).await?;
+        Ok(response)
+    }
+
+    /// Checks the signature of `{entity_dir}/{entity_id}/{filename}` before `warp::fs` serves it
+    async fn authorize(
+        self: Arc<Self>,
+        tail: Peek,
+        query: SignedUrlQueryDto,
+    ) -> Result<(), Rejection> {
+        let segments = tail
+            .segments()
+            .map(decode_segment)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let [entity_dir, entity_id, filename] = segments.as_slice() else {
+            return Err(warp::reject::not_found());
+        };
+
+        let storage = self.sl.storage_service();
+        storage.verify_signed_path(
+            entity_id,
+            entity_dir,
+            filename,
+            query.expires,
+            &query.signature,
+        )?;
+
+        // Answer with our own error rather than warp's generic not found
+        if storage
+            .stat_entity_file(entity_id, entity_dir, filename)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::FileNotFound(filename.to_string()).into());
+        }
+
+        Ok(())
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        let storage = self.sl.storage_service();
+
+        match storage.config().backend {
+            StorageBackendConfig::FileSystem => self.file_system_route(&storage.config().base_path),
+            _ => self.backend_route(),
+        }
+    }
+
+    fn file_system_route(self: Arc<Self>, base_path: &str) -> BoxedFilter<(Box<dyn Reply>,)> {
+        warp::path("uploads")
+            .and(warp::path("signed"))
+            .and(warp::get())
+            .and(warp::path::peek())
+            .and(warp::query::<SignedUrlQueryDto>())
+            .and_then(move |tail: Peek, query: SignedUrlQueryDto| {
+                let handler = self.clone();
+                async move { handler.authorize(tail, query).await }
+            })
+            .untuple_one()
+            .and(warp::fs::dir(base_path.to_string()))
+            .map(|file| Box::new(file) as Box<dyn Reply>)
+            .boxed()
+    }
+
+    fn backend_route(self: Arc<Self>) -> BoxedFilter<(Box<dyn Reply>,)> {
+        warp::path!("uploads" / "signed" / String / String / String)
+            .and(warp::get())
+            .and(warp::query::<SignedUrlQueryDto>())
+            .and(warp::header::optional::<String>("range"))
+            .and_then(
+                move |entity_dir: String,
+                      entity_id: String,
+                      filename: String,
+                      query: SignedUrlQueryDto,
+                      range: Option<String>| {
+                    let handler = self.clone();
+                    async move {
+                        handler
+                            .handle(entity_dir, entity_id, filename, query, range)
+                            .await
+                    }
+                },
+            )
+            .map(|reply| Box::new(reply) as Box<dyn Reply>)
+            .boxed()
+    }
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::{auth::domain::entities::Claims, storage::data::dtos::StorageFileDto},
+    core::{
+        middleware::{owner_or_admin_middleware, scoped_auth_middleware},
+        response::ApiResponse,
+        MsgBuilder,
+    },
+    di::ServiceLocator,
+};
+
+pub struct GetEntityFilesHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl GetEntityFilesHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        entity_dir: String,
+        entity_id: String,
+        claims: Claims,
+    ) -> Result<impl Reply, Rejection> {
+        owner_or_admin_middleware(entity_id.clone(), claims).await?;
+
+        let storage = self.sl.storage_service();
+        let mut filenames = storage.list_entity_files(&entity_id, &entity_dir).await?;
+        filenames.sort();
+
+        let files: Vec<StorageFileDto> = filenames
+            .into_iter()
+            .map(|filename| StorageFileDto {
+                path: storage.get_public_path(&entity_id, &entity_dir, &filename),
+                // `None` when no signing key is configured
+                signed_path: storage
+                    .get_signed_path(&entity_id, &entity_dir, &filename)
+                    .ok(),
+                filename,
+                size: None,
+                variants: vec![],
+            })
+            .collect();
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::loaded_success("Files");
+        let response = ApiResponse::success(msg, Some(files));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("uploads" / String / String)
+            .and(warp::get())
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "storage:read",
+            ))
+            .and_then(
+                move |entity_dir: String, entity_id: String, claims: Claims| {
+                    let handler = self.clone();
+                    async move { handler.handle(entity_dir, entity_id, claims).await }
+                },
+            )
+    }
+}
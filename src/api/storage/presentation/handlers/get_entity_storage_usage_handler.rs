@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::auth::domain::entities::Claims,
+    core::{
+        middleware::{owner_or_admin_middleware, scoped_auth_middleware},
+        response::ApiResponse,
+        MsgBuilder,
+    },
+    di::ServiceLocator,
+};
+
+/// How much an entity stores, overall and per `entity_dir`, with its quota
+pub struct GetEntityStorageUsageHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl GetEntityStorageUsageHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        entity_id: String,
+        claims: Claims,
+    ) -> Result<impl Reply, Rejection> {
+        owner_or_admin_middleware(entity_id.clone(), claims).await?;
+
+        let usage = self.sl.storage_service().entity_usage(&entity_id).await?;
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::loaded_success("Storage usage");
+        let response = ApiResponse::success(msg, Some(usage));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("storage" / "usage" / String)
+            .and(warp::get())
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "storage:read",
+            ))
+            .and_then(move |entity_id: String, claims: Claims| {
+                let handler = self.clone();
+                async move { handler.handle(entity_id, claims).await }
+            })
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::auth::domain::entities::Claims,
+    core::{
+        middleware::{admin_middleware, scoped_auth_middleware},
+        response::ApiResponse,
+        MsgBuilder,
+    },
+    di::ServiceLocator,
+};
+
+/// The usage of every entity having files, largest first. Admin only.
+///
+/// The report walks the whole backend, it is meant for occasional checks rather than dashboards
+/// polling it.
+pub struct GetStorageUsageReportHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl GetStorageUsageReportHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(self: Arc<Self>) -> Result<impl Reply, Rejection> {
+        let report = self.sl.storage_service().usage_report().await?;
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::loaded_success("Storage usage report");
+        let response = ApiResponse::success(msg, Some(report));
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        warp::path!("storage" / "usage")
+            .and(warp::get())
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "storage:read",
+            ))
+            .and_then(admin_middleware)
+            .and_then(move |_claims: Claims| {
+                let handler = self.clone();
+                async move { handler.handle().await }
+            })
+    }
+}
@@ -0,0 +1,31 @@
+mod delete_entity_file_handler;
+mod delete_orphan_storage_dirs_handler;
+mod download_entity_file_handler;
+mod download_signed_entity_file_handler;
+mod get_entity_files_handler;
+mod get_entity_storage_usage_handler;
+mod get_orphan_storage_dirs_handler;
+mod get_storage_usage_report_handler;
+mod upload_entity_files_handler;
+
+pub use delete_entity_file_handler::*;
+pub use delete_orphan_storage_dirs_handler::*;
+pub use download_entity_file_handler::*;
+pub use download_signed_entity_file_handler::*;
+pub use get_entity_files_handler::*;
+pub use get_entity_storage_usage_handler::*;
+pub use get_orphan_storage_dirs_handler::*;
+pub use get_storage_usage_report_handler::*;
+pub use upload_entity_files_handler::*;
+
+use percent_encoding::percent_decode_str;
+
+use crate::core::AppError;
+
+/// Path params are extracted percent-encoded (e.g. a file name with spaces)
+fn decode_segment(segment: &str) -> Result<String, AppError> {
+    percent_decode_str(segment)
+        .decode_utf8()
+        .map(|s| s.into_owned())
+        .map_err(|_| AppError::BadRequest(format!("Invalid path segment: {}", segment)))
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use bytes::Buf;
+use futures::{StreamExt, TryStreamExt};
+use warp::{
+    http::StatusCode,
+    multipart::FormData,
+    reject::Rejection,
+    reply::{json, with_status, Reply},
+    Filter,
+};
+
+use crate::{
+    api::{auth::domain::entities::Claims, storage::data::dtos::StorageFileDto},
+    core::{
+        middleware::{owner_or_admin_middleware, scoped_auth_middleware},
+        response::ApiResponse,
+        AppError, MsgBuilder, StorageService,
+    },
+    di::ServiceLocator,
+};
+
+/// The whole multipart body may carry up to this many files of the largest allowed size
+const MAX_FILES_PER_UPLOAD: u64 = 10;
+
+pub struct UploadEntityFilesHandler {
+    sl: Arc<ServiceLocator>,
+}
+
+impl UploadEntityFilesHandler {
+    pub fn new(sl: Arc<ServiceLocator>) -> Self {
+        Self { sl }
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                 Handle (i.e. Service0)                                   │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    async fn handle(
+        self: Arc<Self>,
+        entity_dir: String,
+        entity_id: String,
+        claims: Claims,
+        form: FormData,
+    ) -> Result<impl Reply, Rejection> {
+        owner_or_admin_middleware(entity_id.clone(), claims).await?;
+
+        let storage = self.sl.storage_service();
+        storage.get_entity_directory_key(&entity_id, &entity_dir)?;
+
+        /* Save every file part ································································· */
+        let mut files = Vec::new();
+        if let Err(err) =
+            Self::save_parts(&storage, &entity_dir, &entity_id, form, &mut files).await
+        {
+            // All or nothing: drop what was saved before the failing part
+            for file in &files {
+                let _ = storage
+                    .delete_entity_file(&entity_id, &entity_dir, &file.filename)
+                    .await;
+            }
+            return Err(err.into());
+        }
+
+        if files.is_empty() {
+            let msg = MsgBuilder::custom("No file found in the request");
+            return Err(AppError::BadRequest(msg).into());
+        }
+
+        //* Success ············································································· */
+        let msg = MsgBuilder::created_success("Files");
+        let response = ApiResponse::success(msg, Some(files));
+        Ok(with_status(json(&response), StatusCode::CREATED))
+    }
+
+    /// Parts without a file name are regular form fields and are ignored
+    async fn save_parts(
+        storage: &StorageService,
+        entity_dir: &str,
+        entity_id: &str,
+        mut form: FormData,
+        files: &mut Vec<StorageFileDto>,
+    ) -> Result<(), AppError> {
+        while let Some(part) = form.try_next().await.map_err(Self::multipart_error)? {
+            let Some(filename) = part.filename().map(str::to_string) else {
+                continue;
+            };
+
+            // Chunks are written as they arrive, the size limit is enforced on the way
+            let stream = part.stream().map(|chunk| {
+                chunk
+                    .map(|mut buf| buf.copy_to_bytes(buf.remaining()))
+                    .map_err(Self::multipart_error)
+            });
+            let saved = storage
+                .save_entity_file_stream(entity_id, entity_dir, &filename, stream)
+                .await?;
+
+            files.push(StorageFileDto {
+                path: storage.get_public_path(entity_id, entity_dir, &saved.filename),
+                // `None` when no signing key is configured
+                signed_path: storage
+                    .get_signed_path(entity_id, entity_dir, &saved.filename)
+                    .ok(),
+                filename: saved.filename,
+                size: Some(saved.size),
+                variants: saved.variants,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn multipart_error(err: warp::Error) -> AppError {
+        AppError::BadRequest(format!("Invalid multipart body: {}", err))
+    }
+
+    //* ┌──────────────────────────────────────────────────────────────────────────────────────────┐
+    //* │                                        The Route                                         │
+    //* └──────────────────────────────────────────────────────────────────────────────────────────┘
+    pub fn route(self: Arc<Self>) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+        let max_length =
+            self.sl.storage_service().config().largest_max_file_size() * MAX_FILES_PER_UPLOAD;
+
+        warp::path!("uploads" / String / String)
+            .and(warp::post())
+            .and(scoped_auth_middleware(
+                self.sl.authenticator(),
+                "storage:write",
+            ))
+            .and(warp::multipart::form().max_length(max_length))
+            .and_then(
+                move |entity_dir: String, entity_id: String, claims: Claims, form: FormData| {
+                    let handler = self.clone();
+                    async move { handler.handle(entity_dir, entity_id, claims, form).await }
+                },
+            )
+    }
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::{
+    api::{auth::domain::usecases::GetManyUsers, storage::domain::usecases::*},
+    core::StorageService,
+};
+
+pub struct StorageDi {
+    pub find_orphan_storage_dirs: Arc<FindOrphanStorageDirs>,
+    pub delete_orphan_storage_dirs: Arc<DeleteOrphanStorageDirs>,
+}
+
+impl StorageDi {
+    /// The files live in the `StorageService`, users are looked up through the auth feature
+    pub fn new(storage: Arc<StorageService>, get_many_users: Arc<GetManyUsers>) -> Self {
+        /* ········································································· [ Usecases ] */
+        let find_orphan_storage_dirs =
+            Arc::new(FindOrphanStorageDirs::new(storage.clone(), get_many_users));
+        let delete_orphan_storage_dirs = Arc::new(DeleteOrphanStorageDirs::new(
+            storage,
+            find_orphan_storage_dirs.clone(),
+        ));
+
+        Self {
+            find_orphan_storage_dirs,
+            delete_orphan_storage_dirs,
+        }
+    }
+}
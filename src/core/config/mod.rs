@@ -1,48 +1,310 @@
-use std::env;
+use std::{collections::HashMap, env};
+
+use thiserror::Error;
+
+use crate::core::{
+    jwt_service::{JwtAlgorithm, JwtSigningConfig},
+    CaptureEmailConfig, EmailBackendConfig, EmailOutboxConfig, S3StorageConfig, SmtpConfig,
+    SmtpTls, StorageBackendConfig, StoragePolicy,
+};
+
+/// Where the core features persist their data
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum DatabaseBackend {
+    #[default]
+    MongoDb,
+    /// Nothing is persisted and no database is required. Meant for tests and local development.
+    InMemory,
+}
+
+impl DatabaseBackend {
+    pub fn from_env_value(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "memory" | "in_memory" | "in-memory" => Self::InMemory,
+            _ => Self::MongoDb,
+        }
+    }
+}
+
+/// Why `Config::new` could not read the environment
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    /// A required variable is not set
+    #[error(transparent)]
+    Missing(#[from] env::VarError),
+    /// A variable is set to a value that cannot be used
+    #[error("{name} {reason}")]
+    Invalid { name: &'static str, reason: String },
+}
 
 #[derive(Clone, Debug)]
 pub struct Config {
+    /// Set by `Config::new(true)`. The development only features, e.g.
+    /// `EmailBackendConfig::Capture` and its unauthenticated `dev/emails` routes, are refused
+    /// otherwise
+    pub is_dev: bool,
+    pub database_backend: DatabaseBackend,
     pub database_uri_string: String,
     pub database_name: String,
     pub jwt_secret: String,
     pub jwt_expiration: i64,
+    /// Algorithm and key of the access tokens, HS256 with `jwt_secret` by default
+    pub jwt_signing: JwtSigningConfig,
+    /// `iss` claim of the access tokens. When set, the tokens without it or issued by someone else
+    /// are rejected
+    pub jwt_issuer: Option<String>,
+    /// `aud` claim of the access tokens. When set, the tokens without it or meant for someone else
+    /// are rejected
+    pub jwt_audience: Option<String>,
+    /// Seconds the revocation status of the access tokens is cached, see `Authenticator`. The
+    /// revocations made by other instances sharing the database may take this long to apply
+    pub access_token_revocation_cache_ttl: u64,
     pub email_from: String,
     pub app_name: String,
     pub uploads_base: String,
+    pub storage_backend: StorageBackendConfig,
+    /// Per `entity_dir` upload rules, e.g. `avatars` only takes images up to 2 MB
+    pub storage_policies: HashMap<String, StoragePolicy>,
+    /// Bytes a user (or any entity) may store across all `entity_dir`s, unlimited if `None`
+    pub storage_entity_quota: Option<u64>,
+    /// Key signing the file download links, derived from `jwt_secret` when `None`
+    pub storage_signing_key: Option<String>,
+    /// `entity_dir`s holding user files (keyed by user id), deleted along with the user
+    pub storage_user_dirs: Vec<String>,
+    /// Seconds between two sweeps of the `storage_user_dirs` left by deleted users
+    pub storage_orphan_sweep_interval: Option<u64>,
+    /// Selects the `EmailService`, Resend (using `resend_token`) or SMTP
+    pub email_backend: EmailBackendConfig,
+    /// Retries of the emails queued in the outbox
+    pub email_outbox: EmailOutboxConfig,
+    /// Directory of email templates overriding or extending the built-in ones (see
+    /// `TemplateRegistry`)
+    pub email_templates_dir: Option<String>,
+    /// Locale of the users without one and of the requests without `Accept-Language`
+    pub default_locale: String,
+    /// Directory of `<locale>.json` message catalogs overriding or extending the built-in ones (see
+    /// `I18n`)
+    pub locales_dir: Option<String>,
     pub resend_token: String,
 }
 
 impl Config {
-    pub fn new(is_dev: bool) -> Result<Self, env::VarError> {
+    pub fn new(is_dev: bool) -> Result<Self, ConfigError> {
         /* ······································································ [ Development ] */
         if is_dev {
             Ok(Config {
+                is_dev: true,
+                database_backend: DatabaseBackend::MongoDb,
                 database_uri_string: "mongodb://127.0.0.1:27017/dev_db_aidoi".to_string(),
                 database_name: "dev_db_aidoi".to_string(),
                 jwt_secret: "1little_2_unsafe_jwt_123456_secret".to_string(),
                 jwt_expiration: 3600,
+                jwt_signing: JwtSigningConfig::default(),
+                jwt_issuer: None,
+                jwt_audience: None,
+                access_token_revocation_cache_ttl: 30,
                 email_from: "no-reply@mail.com".to_string(),
                 app_name: "younss_core_server".to_string(), // Change to fit your needs ;P
                 uploads_base: "./uploads".to_string(),      // if needed
-                resend_token: "".to_string(),               // You should provide a resend_token
+                storage_backend: StorageBackendConfig::FileSystem,
+                storage_policies: HashMap::new(),
+                storage_entity_quota: None,
+                storage_signing_key: None,
+                storage_user_dirs: vec![],
+                storage_orphan_sweep_interval: None,
+                // Emails are kept in memory and listed under `dev/emails`. Switch to
+                // `EmailBackendConfig::Resend` and provide a resend_token to send them
+                email_backend: EmailBackendConfig::Capture(CaptureEmailConfig::default()),
+                email_outbox: EmailOutboxConfig::default(),
+                email_templates_dir: None,
+                default_locale: "en".to_string(),
+                locales_dir: None,
+                resend_token: "".to_string(),
             })
         } else {
             /* ··································································· [ Production ] */
+            let email_backend = Self::email_backend_from_env()?;
             Ok(Config {
+                is_dev: false,
+                // Optional, defaults to mongodb. Set it to `memory` to run without a database
+                database_backend: env::var("DATABASE_BACKEND")
+                    .map(|v| DatabaseBackend::from_env_value(&v))
+                    .unwrap_or_default(),
                 database_uri_string: env::var("MONGODB_URI")?,
                 database_name: env::var("DATABASE_NAME")?,
                 jwt_secret: env::var("JWT_SECRET")?,
                 jwt_expiration: env::var("JWT_EXPIRATION")?.parse().unwrap_or(3600), // 60 * 60 = 3600 seconds => 1 Hour
+                // Optional, HS256 (default) signs with `JWT_SECRET`. RS256, ES256 and EdDSA sign
+                // with `JWT_PRIVATE_KEY_PATH` and publish the public key at /.well-known/jwks.json
+                jwt_signing: JwtSigningConfig {
+                    algorithm: Self::optional_env("JWT_ALGORITHM", |v| {
+                        JwtAlgorithm::from_env_value(v)
+                            .ok_or_else(|| "must be HS256, RS256, ES256 or EdDSA".to_string())
+                    })?
+                    .unwrap_or_default(),
+                    private_key_path: env::var("JWT_PRIVATE_KEY_PATH").ok(),
+                    key_id: env::var("JWT_KEY_ID").ok(),
+                    // Optional, JSON array e.g.
+                    // [{"algorithm": "ES256", "key_path": "./keys/2026-01.pub.pem",
+                    // "verify_until": "2026-07-01T01:00:00Z"}]
+                    retired_keys: Self::optional_env("JWT_RETIRED_KEYS", |v| {
+                        serde_json::from_str(v).map_err(|e| format!("must be valid JSON: {e}"))
+                    })?
+                    .unwrap_or_default(),
+                },
+                // Optional, e.g. `https://auth.my-app.com` and `my-app-api`. Set them to different
+                // values on each deployment sharing a key
+                jwt_issuer: env::var("JWT_ISSUER").ok(),
+                jwt_audience: env::var("JWT_AUDIENCE").ok(),
+                // Optional, in seconds. Defaults to 30, 0 looks the revocations up on each request
+                access_token_revocation_cache_ttl: Self::optional_env(
+                    "ACCESS_TOKEN_REVOCATION_CACHE_TTL",
+                    |v| {
+                        v.parse()
+                            .map_err(|_| "must be a number of seconds".to_string())
+                    },
+                )?
+                .unwrap_or(30),
                 email_from: env::var("EMAIL_FROM")?,
                 app_name: env::var("APP_NAME")?,
                 // upload_base is the root where your server is storing user's related images or
                 // documents. Make to set the proper permission.
                 uploads_base: env::var("UPLOADS_BAS")?,
-                // Here we are using resend for email handling.
-                // If you are using a different email provider or simple smtp, provide an
-                // implementation for the EmailService found under services/email_service
-                resend_token: env::var("RESEND_TOKEN")?,
+                // Optional, files are stored under `uploads_base` unless `STORAGE_BACKEND=s3`
+                storage_backend: Self::storage_backend_from_env()?,
+                // Optional, JSON object e.g.
+                // {"avatars": {"max_file_size": 2097152, "allowed_extensions": ["jpg", "png"],
+                // "quota": 10485760}}
+                storage_policies: Self::optional_env("STORAGE_POLICIES", |v| {
+                    serde_json::from_str(v).map_err(|e| format!("must be valid JSON: {e}"))
+                })?
+                .unwrap_or_default(),
+                // Optional, in bytes. Per `entity_dir` quotas are set with `STORAGE_POLICIES`
+                storage_entity_quota: Self::optional_env("STORAGE_ENTITY_QUOTA", |v| {
+                    v.parse()
+                        .map_err(|_| "must be a number of bytes".to_string())
+                })?,
+                // Optional, signs the file download links. Defaults to a key derived from
+                // `JWT_SECRET`
+                storage_signing_key: env::var("STORAGE_SIGNING_KEY").ok(),
+                // Optional, comma separated e.g. `avatars,documents`
+                storage_user_dirs: env::var("STORAGE_USER_DIRS")
+                    .map(|v| {
+                        v.split(',')
+                            .map(|dir| dir.trim().to_string())
+                            .filter(|dir| !dir.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                // Optional, in seconds. No sweep when not set
+                storage_orphan_sweep_interval: Self::optional_env(
+                    "STORAGE_ORPHAN_SWEEP_INTERVAL",
+                    |v| {
+                        v.parse()
+                            .map_err(|_| "must be a number of seconds".to_string())
+                    },
+                )?,
+                // Here we are using resend for email handling unless `EMAIL_BACKEND=smtp`.
+                // If you are using a different email provider, provide an implementation for the
+                // EmailService found under services/email_service
+                resend_token: match email_backend {
+                    EmailBackendConfig::Resend => env::var("RESEND_TOKEN")?,
+                    _ => env::var("RESEND_TOKEN").unwrap_or_default(),
+                },
+                email_backend,
+                email_outbox: Self::email_outbox_from_env()?,
+                // Optional, e.g. `./email_templates` holding `activation.html`, `layouts/base.html`
+                email_templates_dir: env::var("EMAIL_TEMPLATES_DIR").ok(),
+                // Optional, defaults to `en`, the language of the built-in messages
+                default_locale: env::var("DEFAULT_LOCALE").unwrap_or_else(|_| "en".to_string()),
+                // Optional, e.g. `./locales` holding `fr.json`, `pt-BR.json`
+                locales_dir: env::var("LOCALES_DIR").ok(),
             })
         }
     }
+
+    fn storage_backend_from_env() -> Result<StorageBackendConfig, ConfigError> {
+        match env::var("STORAGE_BACKEND").as_deref() {
+            Err(_) | Ok("fs") => Ok(StorageBackendConfig::FileSystem),
+            Ok("s3") => Ok(StorageBackendConfig::S3(S3StorageConfig {
+                endpoint: env::var("S3_ENDPOINT")?,
+                region: env::var("S3_REGION")?,
+                bucket: env::var("S3_BUCKET")?,
+                access_key: env::var("S3_ACCESS_KEY")?,
+                secret_key: env::var("S3_SECRET_KEY")?,
+                path_style: env::var("S3_PATH_STYLE").is_ok_and(|v| v == "true"),
+            })),
+            Ok(_) => Err(ConfigError::Invalid {
+                name: "STORAGE_BACKEND",
+                reason: "must be fs or s3".to_string(),
+            }),
+        }
+    }
+
+    fn email_backend_from_env() -> Result<EmailBackendConfig, ConfigError> {
+        match env::var("EMAIL_BACKEND").as_deref() {
+            Ok("smtp") => Ok(EmailBackendConfig::Smtp(SmtpConfig {
+                port: Self::optional_env("SMTP_PORT", |v| {
+                    v.parse().map_err(|_| "must be a port number".to_string())
+                })?,
+                username: env::var("SMTP_USERNAME").ok(),
+                password: env::var("SMTP_PASSWORD").ok(),
+                // `starttls` (default), `tls` or `none`
+                tls: Self::optional_env("SMTP_TLS", |v| {
+                    SmtpTls::from_env_value(v)
+                        .ok_or_else(|| "must be starttls, tls or none".to_string())
+                })?
+                .unwrap_or_default(),
+                pool_size: Self::optional_env("SMTP_POOL_SIZE", |v| {
+                    v.parse()
+                        .ok()
+                        .filter(|&size| size > 0)
+                        .ok_or_else(|| "must be a positive number of connections".to_string())
+                })?
+                .unwrap_or(10),
+                ..SmtpConfig::new(&env::var("SMTP_HOST")?)
+            })),
+            // The captured emails hold the activation and reset tokens, and are readable by anyone
+            Ok("capture") => Err(ConfigError::Invalid {
+                name: "EMAIL_BACKEND",
+                reason: "capture is only available in development".to_string(),
+            }),
+            _ => Ok(EmailBackendConfig::Resend),
+        }
+    }
+
+    /// Parses the variable `name` when it is set
+    fn optional_env<T>(
+        name: &'static str,
+        parse: impl FnOnce(&str) -> Result<T, String>,
+    ) -> Result<Option<T>, ConfigError> {
+        env::var(name)
+            .ok()
+            .map(|v| parse(&v).map_err(|reason| ConfigError::Invalid { name, reason }))
+            .transpose()
+    }
+
+    fn email_outbox_from_env() -> Result<EmailOutboxConfig, ConfigError> {
+        let default = EmailOutboxConfig::default();
+        let seconds = |name| {
+            Self::optional_env(name, |v| {
+                v.parse()
+                    .map_err(|_| "must be a number of seconds".to_string())
+            })
+        };
+
+        Ok(EmailOutboxConfig {
+            poll_interval: seconds("EMAIL_OUTBOX_POLL_INTERVAL")?.unwrap_or(default.poll_interval),
+            max_attempts: Self::optional_env("EMAIL_OUTBOX_MAX_ATTEMPTS", |v| {
+                v.parse()
+                    .ok()
+                    .filter(|&attempts| attempts > 0)
+                    .ok_or_else(|| "must be a positive number of attempts".to_string())
+            })?
+            .unwrap_or(default.max_attempts),
+            base_delay: seconds("EMAIL_OUTBOX_BASE_DELAY")?.unwrap_or(default.base_delay),
+            max_delay: seconds("EMAIL_OUTBOX_MAX_DELAY")?.unwrap_or(default.max_delay),
+            lease: default.lease,
+        })
+    }
 }
@@ -0,0 +1,373 @@
+// core/datasource/in_memory/document_matcher.rs
+//
+// Evaluates the documents produced by `query_to_document` against plain BSON documents so the
+// in-memory datasource answers queries the same way MongoDB would. Only the subset of the query
+// language emitted by the query params parser is supported.
+use std::cmp::Ordering;
+
+use bson::{Bson, Document};
+use regex::RegexBuilder;
+
+/// Returns true when `document` satisfies every condition of `filter`
+pub fn matches_filter(document: &Document, filter: &Document) -> bool {
+    filter.iter().all(|(key, condition)| match key.as_str() {
+        "$and" => match condition {
+            Bson::Array(conditions) => conditions.iter().all(|c| match c {
+                Bson::Document(sub_filter) => matches_filter(document, sub_filter),
+                _ => false,
+            }),
+            _ => false,
+        },
+        "$or" => match condition {
+            Bson::Array(conditions) => conditions.iter().any(|c| match c {
+                Bson::Document(sub_filter) => matches_filter(document, sub_filter),
+                _ => false,
+            }),
+            _ => false,
+        },
+        "$expr" => match condition {
+            Bson::Document(expr) => matches_expr(document, expr),
+            _ => false,
+        },
+        // `$set` is an update operator the parser may leave in the filter; it never filters
+        "$set" => true,
+        field => matches_field(get_path(document, field), condition),
+    })
+}
+
+/// Resolves a dot notation path (e.g. `address.city`) inside a document
+pub fn get_path<'a>(document: &'a Document, path: &str) -> Option<&'a Bson> {
+    let mut parts = path.split('.');
+    let mut current = document.get(parts.next()?)?;
+
+    for part in parts {
+        current = match current {
+            Bson::Document(doc) => doc.get(part)?,
+            _ => return None,
+        };
+    }
+
+    Some(current)
+}
+
+/// Total ordering used for sorting and range operators.
+///
+/// Numbers are compared numerically whatever their BSON width. Values of different kinds are
+/// ordered by their kind following MongoDB's comparison order.
+pub fn compare_bson(a: &Bson, b: &Bson) -> Ordering {
+    if let (Some(x), Some(y)) = (as_f64(a), as_f64(b)) {
+        return x.partial_cmp(&y).unwrap_or(Ordering::Equal);
+    }
+
+    match (a, b) {
+        (Bson::String(x), Bson::String(y)) => x.cmp(y),
+        (Bson::Boolean(x), Bson::Boolean(y)) => x.cmp(y),
+        (Bson::DateTime(x), Bson::DateTime(y)) => x.cmp(y),
+        (Bson::ObjectId(x), Bson::ObjectId(y)) => x.bytes().cmp(&y.bytes()),
+        _ => type_rank(a).cmp(&type_rank(b)),
+    }
+}
+
+fn matches_field(value: Option<&Bson>, condition: &Bson) -> bool {
+    match condition {
+        Bson::Document(op_doc) if is_operator_document(op_doc) => {
+            op_doc
+                .iter()
+                .all(|(operator, operand)| match operator.as_str() {
+                    "$ne" => !value_equals(value, operand),
+                    "$gt" => compare_value(value, operand, |o| o == Ordering::Greater),
+                    "$gte" => compare_value(value, operand, |o| o != Ordering::Less),
+                    "$lt" => compare_value(value, operand, |o| o == Ordering::Less),
+                    "$lte" => compare_value(value, operand, |o| o != Ordering::Greater),
+                    "$in" => match operand {
+                        Bson::Array(candidates) => {
+                            candidates.iter().any(|c| value_equals(value, c))
+                        }
+                        _ => false,
+                    },
+                    "$regex" => {
+                        let options = op_doc.get_str("$options").unwrap_or("");
+                        match (value, operand) {
+                            (Some(Bson::String(s)), Bson::String(pattern)) => {
+                                regex_matches(pattern, options, s)
+                            }
+                            _ => false,
+                        }
+                    }
+                    // consumed together with `$regex`
+                    "$options" => true,
+                    _ => false,
+                })
+        }
+        _ => value_equals(value, condition),
+    }
+}
+
+/// Only the `$regexMatch` expression generated for `number-regex` typed queries is supported
+fn matches_expr(document: &Document, expr: &Document) -> bool {
+    let Ok(regex_match) = expr.get_document("$regexMatch") else {
+        return false;
+    };
+
+    let field = regex_match
+        .get_document("input")
+        .ok()
+        .and_then(|input| input.get_str("$toString").ok())
+        .map(|field| field.trim_start_matches('$'));
+    let pattern = regex_match.get_str("regex").unwrap_or("");
+    let options = regex_match.get_str("options").unwrap_or("");
+
+    match field.and_then(|f| get_path(document, f)) {
+        Some(value) => regex_matches(pattern, options, &bson_to_string(value)),
+        None => false,
+    }
+}
+
+/// MongoDB equality: a missing field equals null, and an array field matches when any of its
+/// elements is equal to the expected value
+fn value_equals(value: Option<&Bson>, expected: &Bson) -> bool {
+    match value {
+        None | Some(Bson::Null) => matches!(expected, Bson::Null),
+        Some(Bson::Array(items)) if !matches!(expected, Bson::Array(_)) => {
+            items.iter().any(|item| compare_equal(item, expected))
+        }
+        Some(v) => compare_equal(v, expected),
+    }
+}
+
+fn compare_value(value: Option<&Bson>, operand: &Bson, predicate: fn(Ordering) -> bool) -> bool {
+    match value {
+        None | Some(Bson::Null) => false,
+        Some(v) if type_rank(v) != type_rank(operand) => false,
+        Some(v) => predicate(compare_bson(v, operand)),
+    }
+}
+
+fn compare_equal(a: &Bson, b: &Bson) -> bool {
+    type_rank(a) == type_rank(b) && compare_bson(a, b) == Ordering::Equal && same_shape(a, b)
+}
+
+/// Documents and arrays are compared structurally
+fn same_shape(a: &Bson, b: &Bson) -> bool {
+    match (a, b) {
+        (Bson::Document(_), _) | (Bson::Array(_), _) => a == b,
+        _ => true,
+    }
+}
+
+fn is_operator_document(doc: &Document) -> bool {
+    !doc.is_empty() && doc.keys().all(|k| k.starts_with('$'))
+}
+
+fn regex_matches(pattern: &str, options: &str, haystack: &str) -> bool {
+    RegexBuilder::new(pattern)
+        .case_insensitive(options.contains('i'))
+        .multi_line(options.contains('m'))
+        .build()
+        .map(|re| re.is_match(haystack))
+        .unwrap_or(false)
+}
+
+fn as_f64(value: &Bson) -> Option<f64> {
+    match value {
+        Bson::Int32(v) => Some(*v as f64),
+        Bson::Int64(v) => Some(*v as f64),
+        Bson::Double(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn bson_to_string(value: &Bson) -> String {
+    match value {
+        Bson::String(s) => s.clone(),
+        Bson::Int32(v) => v.to_string(),
+        Bson::Int64(v) => v.to_string(),
+        Bson::Double(v) => v.to_string(),
+        Bson::ObjectId(id) => id.to_hex(),
+        other => other.to_string(),
+    }
+}
+
+/// Relative order of BSON kinds as defined by MongoDB
+fn type_rank(value: &Bson) -> u8 {
+    match value {
+        Bson::MinKey => 0,
+        Bson::Null | Bson::Undefined => 1,
+        Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_) | Bson::Decimal128(_) => 2,
+        Bson::String(_) | Bson::Symbol(_) => 3,
+        Bson::Document(_) => 4,
+        Bson::Array(_) => 5,
+        Bson::Binary(_) => 6,
+        Bson::ObjectId(_) => 7,
+        Bson::Boolean(_) => 8,
+        Bson::DateTime(_) => 9,
+        Bson::Timestamp(_) => 10,
+        Bson::RegularExpression(_) => 11,
+        Bson::MaxKey => 13,
+        _ => 12,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bson::{doc, oid::ObjectId, DateTime as BsonDateTime};
+
+    use super::*;
+    use crate::core::query_params_parser::query_to_document;
+
+    fn user() -> Document {
+        doc! {
+            "name": "Jane",
+            "age": 32,
+            "score": 7.5,
+            "role": "admin",
+            "verified": true,
+            "tags": ["a", "b"],
+            "address": { "city": "Rabat", "zip": 10000 },
+            "created_at": BsonDateTime::parse_rfc3339_str("2026-01-01T00:00:00Z").unwrap(),
+        }
+    }
+
+    // Runs the query params through the same parser the repositories use
+    fn matches_query(document: &Document, pairs: &[(&str, &str)]) -> bool {
+        let query: HashMap<String, String> = pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        let (filter, _, _) = query_to_document(query);
+        matches_filter(document, &filter)
+    }
+
+    #[test]
+    fn equality() {
+        let doc = user();
+        assert!(matches_query(&doc, &[("name", "Jane")]));
+        assert!(matches_query(&doc, &[("verified", "true")]));
+        assert!(matches_query(&doc, &[("name.eq", "Jane")]));
+        assert!(!matches_query(&doc, &[("name", "John")]));
+        assert!(!matches_query(&doc, &[("name", "Jane"), ("role", "user")]));
+    }
+
+    #[test]
+    fn comparison_operators() {
+        let doc = user();
+        assert!(matches_query(&doc, &[("age.gt", "30")]));
+        assert!(!matches_query(&doc, &[("age.gt", "32")]));
+        assert!(matches_query(&doc, &[("age.gte", "32")]));
+        assert!(matches_query(&doc, &[("age.lt", "33")]));
+        assert!(!matches_query(&doc, &[("age.lt", "32")]));
+        assert!(matches_query(&doc, &[("age.lte", "32")]));
+        assert!(matches_query(&doc, &[("age.ne", "40")]));
+        assert!(!matches_query(&doc, &[("age.ne", "32")]));
+        // Int32 and Double are compared numerically
+        assert!(matches_query(&doc, &[("score.gt", "7")]));
+        assert!(matches_query(
+            &doc,
+            &[("created_at.lt", "2026-06-01T00:00:00Z")]
+        ));
+        assert!(!matches_query(
+            &doc,
+            &[("created_at.gt", "2026-06-01T00:00:00Z")]
+        ));
+    }
+
+    #[test]
+    fn comparison_across_types_never_matches() {
+        let doc = user();
+        assert!(!matches_query(&doc, &[("name.gt", "1")]));
+        assert!(!matches_query(&doc, &[("missing.lt", "10")]));
+    }
+
+    #[test]
+    fn in_operator() {
+        let doc = user();
+        assert!(matches_query(&doc, &[("role.in", "user,admin")]));
+        assert!(!matches_query(&doc, &[("role.in", "user,guest")]));
+        assert!(matches_query(&doc, &[("age.in", "31,32")]));
+    }
+
+    #[test]
+    fn regex_operator_is_case_insensitive() {
+        let doc = user();
+        assert!(matches_query(&doc, &[("name.regex", "^ja")]));
+        assert!(!matches_query(&doc, &[("name.regex", "^jo")]));
+        assert!(matches_query(&doc, &[("age.regex", "3~number-regex")]));
+        assert!(!matches_query(&doc, &[("age.regex", "^4~number-regex")]));
+    }
+
+    #[test]
+    fn or_conditions() {
+        let doc = user();
+        assert!(matches_query(
+            &doc,
+            &[("or.name", "John"), ("or.role", "admin")]
+        ));
+        assert!(!matches_query(
+            &doc,
+            &[("or.name", "John"), ("or.role", "user")]
+        ));
+        assert!(matches_query(
+            &doc,
+            &[("verified", "true"), ("or.age.gt", "30")]
+        ));
+        assert!(!matches_query(
+            &doc,
+            &[("verified", "false"), ("or.age.gt", "30")]
+        ));
+    }
+
+    #[test]
+    fn nested_fields_and_arrays() {
+        let doc = user();
+        assert!(matches_query(&doc, &[("address.city", "Rabat")]));
+        assert!(matches_query(&doc, &[("address.zip.gte", "10000")]));
+        // An array field matches when any of its elements does
+        assert!(matches_query(&doc, &[("tags", "b")]));
+        assert!(!matches_query(&doc, &[("tags", "c")]));
+    }
+
+    #[test]
+    fn missing_fields_equal_null() {
+        let doc = user();
+        assert!(matches_filter(&doc, &doc! { "deleted_at": Bson::Null }));
+        assert!(!matches_filter(&doc, &doc! { "name": Bson::Null }));
+        assert!(matches_filter(&doc, &doc! { "deleted_at": { "$ne": "x" } }));
+    }
+
+    #[test]
+    fn object_ids() {
+        let id = ObjectId::new();
+        let doc = doc! { "_id": id };
+        assert!(matches_query(&doc, &[("_id", &id.to_hex())]));
+        assert!(!matches_query(&doc, &[("_id", &ObjectId::new().to_hex())]));
+    }
+
+    #[test]
+    fn unknown_operators_never_match() {
+        let doc = user();
+        assert!(!matches_filter(&doc, &doc! { "age": { "$exists": true } }));
+    }
+
+    #[test]
+    fn compare_bson_orders_kinds_like_mongodb() {
+        assert_eq!(
+            compare_bson(&Bson::Int32(2), &Bson::Double(2.0)),
+            Ordering::Equal
+        );
+        assert_eq!(
+            compare_bson(&Bson::Int64(1), &Bson::Int32(2)),
+            Ordering::Less
+        );
+        assert_eq!(compare_bson(&Bson::Null, &Bson::Int32(0)), Ordering::Less);
+        assert_eq!(
+            compare_bson(&Bson::String("a".into()), &Bson::Int32(9)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_bson(&Bson::String("a".into()), &Bson::String("b".into())),
+            Ordering::Less
+        );
+    }
+}
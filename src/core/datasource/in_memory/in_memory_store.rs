@@ -0,0 +1,292 @@
+// core/datasource/in_memory/in_memory_store.rs
+use std::{cmp::Ordering, collections::HashMap, marker::PhantomData};
+
+use bson::{doc, oid::ObjectId, Bson, DateTime as BsonDateTime, Document};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::RwLock;
+
+use crate::core::{
+    crud_model::CrudModel,
+    datasource::in_memory::document_matcher::{compare_bson, get_path, matches_filter},
+    pagination::{PaginatedParams, PaginatedResponse},
+    query_params_parser::query_to_document,
+    AppError,
+};
+
+/// An in-memory collection of models.
+///
+/// Models are kept as BSON documents (exactly as they would be persisted by the mongo datasource)
+/// so that the filters built by `query_to_document` are evaluated the same way. It is meant for
+/// tests and local development, nothing is persisted once the process stops.
+///
+/// Features expose it through their own datasource type, see `UserDataSourceInMemoryImpl` for an
+/// example.
+pub struct InMemoryStore<T, M> {
+    documents: RwLock<Vec<Document>>,
+    _marker: PhantomData<fn() -> (T, M)>,
+}
+
+impl<T, M> Default for InMemoryStore<T, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, M> InMemoryStore<T, M> {
+    pub fn new() -> Self {
+        Self {
+            documents: RwLock::new(Vec::new()),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, M> InMemoryStore<T, M>
+where
+    T: Clone + Send + Sync,
+    M: CrudModel<T> + Serialize + DeserializeOwned,
+{
+    /* ··········································································· [ CREATE ONE ]*/
+    pub async fn create(&self, item: &T) -> Result<T, AppError> {
+        let model: M = M::try_from_entity(item.clone())?;
+        let mut document = to_document(&model)?;
+
+        if !matches!(document.get("_id"), Some(Bson::ObjectId(_))) {
+            document.insert("_id", ObjectId::new());
+        }
+
+        self.documents.write().await.push(document.clone());
+
+        Ok(from_document::<M>(document)?.to_entity())
+    }
+
+    /* ·············································································· [ FIND ONE ]*/
+    pub async fn find_one(&self, query: HashMap<String, String>) -> Result<T, AppError> {
+        let (filter, _, _) = query_to_document(query);
+
+        let document = self
+            .documents
+            .read()
+            .await
+            .iter()
+            .find(|doc| matches_filter(doc, &filter))
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("Document not found".to_string()))?;
+
+        Ok(from_document::<M>(document)?.to_entity())
+    }
+
+    /* ········································································ [ FIND ONE BY ID ]*/
+    pub async fn find_one_by_id(&self, id: &str) -> Result<T, AppError> {
+        ObjectId::parse_str(id).map_err(|_| {
+            let msg = format!("Invalid Object ID format. Found id: {id}");
+            AppError::InvalidInput(msg)
+        })?;
+
+        let mut query = HashMap::new();
+        query.insert("_id".to_string(), id.to_string());
+
+        self.find_one(query).await
+    }
+
+    /* ············································································· [ FIND MANY ]*/
+    pub async fn find(&self, params: PaginatedParams) -> Result<PaginatedResponse<T>, AppError> {
+        let page = params.page;
+        let limit = params.limit;
+        let skip = page * limit;
+
+        let (filter, sort, project) = query_to_document(params.query);
+
+        let mut documents: Vec<Document> = self
+            .documents
+            .read()
+            .await
+            .iter()
+            .filter(|doc| matches_filter(doc, &filter))
+            .cloned()
+            .collect();
+
+        let total = documents.len() as u64;
+
+        let sort = sort
+            .and_then(|s| s.get_document("$sort").ok().cloned())
+            .unwrap_or_else(|| doc! { "created_at": -1 });
+        sort_documents(&mut documents, &sort);
+
+        if let Some(project) = project.and_then(|p| p.get_document("$project").ok().cloned()) {
+            documents = documents
+                .into_iter()
+                .map(|doc| project_document(doc, &project))
+                .collect();
+        }
+
+        if limit > 0 {
+            documents = documents
+                .into_iter()
+                .skip(skip.max(0) as usize)
+                .take(limit as usize)
+                .collect();
+        }
+
+        let has_next = total > (skip as u64 + documents.len() as u64);
+
+        let records = documents
+            .into_iter()
+            .map(|doc| from_document::<M>(doc).map(|model| model.to_entity()))
+            .collect::<Result<Vec<T>, AppError>>()?;
+
+        Ok(PaginatedResponse {
+            records,
+            has_next,
+            current_page: page,
+            total,
+        })
+    }
+
+    /* ············································································ [ UPDATE ONE ]*/
+    pub async fn update_one(&self, item: &T) -> Result<T, AppError> {
+        let model: M = M::try_from_entity(item.clone())?;
+        let mut document = to_document(&model)?;
+
+        let id = document
+            .get_object_id("_id")
+            .map_err(|_| AppError::InvalidInput("Item has no _id field".to_string()))?;
+
+        // Mirrors the `$currentDate` set by the mongo datasource
+        document.insert("updated_at", BsonDateTime::now());
+
+        let mut documents = self.documents.write().await;
+        let stored = documents
+            .iter_mut()
+            .find(|doc| doc.get_object_id("_id").ok() == Some(id))
+            .ok_or_else(|| {
+                AppError::DatabaseError("Could not update the document for the moment".to_string())
+            })?;
+
+        // `$set` semantics: fields absent from the model are kept as they are
+        stored.extend(document);
+
+        Ok(from_document::<M>(stored.clone())?.to_entity())
+    }
+
+    /* ·········································································· [ UPDATE FIRST ]*/
+    /// Updates the first document matching `filter` in a single step, like the conditional
+    /// `find_one_and_update` of the mongo datasources. `update` edits the document in place and
+    /// returns false to leave it untouched, then `None` is returned
+    pub async fn update_first(
+        &self,
+        filter: &Document,
+        update: impl FnOnce(&mut Document) -> bool + Send,
+    ) -> Result<Option<T>, AppError> {
+        let mut documents = self.documents.write().await;
+        let Some(stored) = documents.iter_mut().find(|doc| matches_filter(doc, filter)) else {
+            return Ok(None);
+        };
+
+        let mut document = stored.clone();
+        if !update(&mut document) {
+            return Ok(None);
+        }
+        document.insert("updated_at", BsonDateTime::now());
+        *stored = document;
+
+        Ok(Some(from_document::<M>(stored.clone())?.to_entity()))
+    }
+
+    /* ······································································ [ DELETE ONE BY ID ]*/
+    pub async fn delete_by_id(&self, id: &str) -> Result<T, AppError> {
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| AppError::InvalidInput("Invalid ObjectId format".to_string()))?;
+
+        self.delete_first(&doc! { "_id": object_id }).await
+    }
+
+    /* ············································································ [ DELETE ONE ]*/
+    pub async fn delete_one(&self, query: HashMap<String, String>) -> Result<T, AppError> {
+        let (filter, _, _) = query_to_document(query);
+        self.delete_first(&filter).await
+    }
+
+    /* ··········································································· [ DELETE MANY ]*/
+    pub async fn delete_many(&self, query: HashMap<String, String>) -> Result<u64, AppError> {
+        let (filter, _, _) = query_to_document(query);
+
+        let mut documents = self.documents.write().await;
+        let before = documents.len();
+        documents.retain(|doc| !matches_filter(doc, &filter));
+        let deleted_count = (before - documents.len()) as u64;
+
+        if deleted_count == 0 {
+            return Err(AppError::NotFound(
+                "No documents found to delete".to_string(),
+            ));
+        }
+
+        Ok(deleted_count)
+    }
+
+    async fn delete_first(&self, filter: &Document) -> Result<T, AppError> {
+        let mut documents = self.documents.write().await;
+        let position = documents
+            .iter()
+            .position(|doc| matches_filter(doc, filter))
+            .ok_or_else(|| AppError::NotFound("Document not found".to_string()))?;
+
+        let deleted = documents.remove(position);
+        Ok(from_document::<M>(deleted)?.to_entity())
+    }
+}
+
+fn to_document<M: Serialize>(model: &M) -> Result<Document, AppError> {
+    bson::to_document(model)
+        .map_err(|e| AppError::DatabaseError(format!("DB Serialization error: {}", e)))
+}
+
+fn from_document<M: DeserializeOwned>(document: Document) -> Result<M, AppError> {
+    bson::from_document(document)
+        .map_err(|e| AppError::DatabaseError(format!("Deserialization error: {}", e)))
+}
+
+fn sort_documents(documents: &mut [Document], sort: &Document) {
+    documents.sort_by(|a, b| {
+        for (field, direction) in sort {
+            let ordering = match (get_path(a, field), get_path(b, field)) {
+                (Some(x), Some(y)) => compare_bson(x, y),
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            };
+            let ordering = if matches!(direction, Bson::Int32(-1) | Bson::Int64(-1)) {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    });
+}
+
+/// Applies a `$project` stage. As with MongoDB, `_id` is kept unless explicitly excluded and a
+/// projection is either inclusive or exclusive.
+fn project_document(document: Document, project: &Document) -> Document {
+    let is_included = |v: &Bson| !matches!(v, Bson::Int32(0) | Bson::Int64(0));
+    let inclusive = project
+        .iter()
+        .any(|(field, v)| field != "_id" && is_included(v));
+
+    let mut projected = Document::new();
+    for (field, value) in document {
+        let keep = match project.get(&field) {
+            Some(v) => is_included(v),
+            None if field == "_id" => true,
+            None => !inclusive,
+        };
+        if keep {
+            projected.insert(field, value);
+        }
+    }
+    projected
+}
@@ -0,0 +1,3 @@
+pub mod document_matcher;
+pub mod in_memory_store;
+pub use in_memory_store::*;
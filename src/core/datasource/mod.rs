@@ -1,2 +1,4 @@
 pub mod crud_datasource;
+pub mod in_memory;
 pub mod mongo_db;
+pub mod transaction;
@@ -3,12 +3,12 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 use bson::{doc, oid::ObjectId};
-use mongodb::{options::ReturnDocument, Collection};
+use mongodb::{action::Action, options::ReturnDocument, Collection};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::core::{
     crud_model::CrudModel,
-    datasource::crud_datasource::CrudDataSource,
+    datasource::{crud_datasource::CrudDataSource, transaction::Transaction},
     pagination::{PaginatedParams, PaginatedResponse},
     query_params_parser::query_to_document,
     AppError,
@@ -29,10 +29,13 @@ where
     /* ··········································································· [ CREATE ONE ]*/
     async fn create(&self, item: &T) -> Result<T, AppError> {
         let model: M = M::try_from_entity(item.clone())?;
+        let transaction = Transaction::current();
+        let mut session = Transaction::session_of(&transaction).await;
 
         let result = self
             .get_collection()
             .insert_one(&model)
+            .optional(session.as_deref_mut(), |a, s| a.session(s))
             .await
             .map_err(|e| AppError::DatabaseError(format!("Could not create document: {}", e)))?;
 
@@ -41,6 +44,7 @@ where
             let created_model = self
                 .get_collection()
                 .find_one(filter)
+                .optional(session.as_deref_mut(), |a, s| a.session(s))
                 .await
                 .map_err(|e| {
                     AppError::DatabaseError(format!("Failed to fetch created document: {}", e))
@@ -56,10 +60,13 @@ where
     /* ·············································································· [ FIND ONE ]*/
     async fn find_one(&self, query: HashMap<String, String>) -> Result<T, AppError> {
         let (filter, _, _) = query_to_document(query);
+        let transaction = Transaction::current();
+        let mut session = Transaction::session_of(&transaction).await;
 
         let model = self
             .get_collection()
             .find_one(filter)
+            .optional(session.as_deref_mut(), |a, s| a.session(s))
             .await
             .map_err(|e| AppError::DatabaseError(format!("Database error: {}", e)))?
             .ok_or_else(|| AppError::NotFound("Document not found".to_string()))?;
@@ -81,6 +88,7 @@ where
     }
 
     /* ············································································· [ FIND MANY ]*/
+    // Reads outside of the current transaction, if any
     async fn find(&self, params: PaginatedParams) -> Result<PaginatedResponse<T>, AppError> {
         let page = params.page;
         let limit = params.limit;
@@ -170,10 +178,13 @@ where
             "$currentDate": { "updated_at": true }
         };
 
+        let transaction = Transaction::current();
+        let mut session = Transaction::session_of(&transaction).await;
         let updated_document: Option<M> = self
             .get_collection()
             .find_one_and_update(doc! { "_id": id }, update_doc)
             .return_document(ReturnDocument::After)
+            .optional(session.as_deref_mut(), |a, s| a.session(s))
             .await
             .map_err(|e| AppError::DatabaseError(format!("Update failed: {}", e)))?;
 
@@ -189,10 +200,13 @@ where
     async fn delete_by_id(&self, id: &str) -> Result<T, AppError> {
         let object_id = ObjectId::parse_str(id)
             .map_err(|_| AppError::InvalidInput("Invalid ObjectId format".to_string()))?;
+        let transaction = Transaction::current();
+        let mut session = Transaction::session_of(&transaction).await;
 
         let deleted_model: M = self
             .get_collection()
             .find_one_and_delete(doc! { "_id": object_id })
+            .optional(session.as_deref_mut(), |a, s| a.session(s))
             .await
             .map_err(|e| AppError::DatabaseError(format!("Delete failed: {}", e)))?
             .ok_or_else(|| AppError::NotFound("Document not found".to_string()))?;
@@ -203,10 +217,13 @@ where
     /* ············································································ [ DELETE ONE ]*/
     async fn delete_one(&self, query: HashMap<String, String>) -> Result<T, AppError> {
         let (filter, _, _) = query_to_document(query);
+        let transaction = Transaction::current();
+        let mut session = Transaction::session_of(&transaction).await;
 
         let deleted_model: M = self
             .get_collection()
             .find_one_and_delete(filter)
+            .optional(session.as_deref_mut(), |a, s| a.session(s))
             .await
             .map_err(|e| AppError::DatabaseError(format!("Delete failed: {}", e)))?
             .ok_or_else(|| AppError::NotFound("Document not found".to_string()))?;
@@ -217,10 +234,13 @@ where
     /* ··········································································· [ DELETE MANY ]*/
     async fn delete_many(&self, query: HashMap<String, String>) -> Result<u64, AppError> {
         let (filter, _, _) = query_to_document(query);
+        let transaction = Transaction::current();
+        let mut session = Transaction::session_of(&transaction).await;
 
         let result = self
             .get_collection()
             .delete_many(filter)
+            .optional(session.as_deref_mut(), |a, s| a.session(s))
             .await
             .map_err(|e| AppError::DatabaseError(format!("Delete many failed: {}", e)))?;
 
@@ -1,5 +1,5 @@
 use bson::doc;
-use mongodb::{Client, Database};
+use mongodb::{options::ClientOptions, Client, Database};
 
 use crate::core::{AppError, Config};
 
@@ -7,6 +7,8 @@ use crate::core::{AppError, Config};
 // crud_datasource implementation
 pub struct MongoConnection {
     pub database: Database,
+    /// Replica sets and sharded clusters run transactions, standalone servers don't
+    pub supports_transactions: bool,
 }
 impl MongoConnection {
     pub async fn new(config: Config) -> Result<Self, AppError> {
@@ -25,6 +27,26 @@ impl MongoConnection {
             return Err(AppError::DatabaseError(msg));
         }
 
-        Ok(Self { database })
+        let supports_transactions = match database.run_command(doc! {"hello": 1}).await {
+            Ok(hello) => hello.contains_key("setName") || hello.get_str("msg") == Ok("isdbgrid"),
+            Err(_) => false,
+        };
+
+        Ok(Self {
+            database,
+            supports_transactions,
+        })
+    }
+
+    /// A handle on the configured database that is never connected to, for the in-memory backend
+    pub fn detached(config: &Config) -> Result<Self, AppError> {
+        let client = Client::with_options(ClientOptions::default())
+            .map_err(|e| AppError::DatabaseError(format!("{:?}", e)))?;
+        let database = client.database(&config.database_name);
+
+        Ok(Self {
+            database,
+            supports_transactions: false,
+        })
     }
 }
@@ -0,0 +1,119 @@
+// core/datasource/transaction.rs
+use std::{future::Future, sync::Arc};
+
+use mongodb::{Client, ClientSession};
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::core::AppError;
+
+type CommitHook = Box<dyn FnOnce() + Send>;
+
+/// The transaction the current task runs in, see `TransactionRunner::run`
+pub struct Transaction {
+    // `None` when the database cannot run transactions (in-memory backend, standalone MongoDB)
+    session: Option<Mutex<ClientSession>>,
+    on_commit: std::sync::Mutex<Vec<CommitHook>>,
+}
+
+tokio::task_local! {
+    static CURRENT_TRANSACTION: Arc<Transaction>;
+}
+
+impl Transaction {
+    /// The transaction of the current task, if any
+    pub fn current() -> Option<Arc<Transaction>> {
+        CURRENT_TRANSACTION.try_with(Arc::clone).ok()
+    }
+
+    /// The session the mongo datasources run their operations in
+    pub async fn session(&self) -> Option<MutexGuard<'_, ClientSession>> {
+        match &self.session {
+            Some(session) => Some(session.lock().await),
+            None => None,
+        }
+    }
+
+    /// The session of `transaction` (see `current`), the operations run outside of any when `None`
+    pub async fn session_of(
+        transaction: &Option<Arc<Transaction>>,
+    ) -> Option<MutexGuard<'_, ClientSession>> {
+        match transaction {
+            Some(transaction) => transaction.session().await,
+            None => None,
+        }
+    }
+}
+
+/// Runs several writes, possibly of different features, as a whole
+pub struct TransactionRunner {
+    // `None` unless the database supports transactions (replica set or sharded cluster)
+    client: Option<Client>,
+}
+
+impl TransactionRunner {
+    pub fn new(client: Option<Client>) -> Self {
+        Self { client }
+    }
+
+    /// Runs `operations` in a MongoDB transaction, committed when they succeed and aborted
+    /// otherwise. Operations of the mongo datasources join it, except `find` whose reads are made
+    /// outside of it. Without transaction support the operations simply run one after the other.
+    ///
+    /// Nested calls join the outer transaction.
+    pub async fn run<R, F, Fut>(&self, operations: F) -> Result<R, AppError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<R, AppError>>,
+    {
+        if Transaction::current().is_some() {
+            return operations().await;
+        }
+
+        let session = match &self.client {
+            Some(client) => {
+                let mut session = client.start_session().await.map_err(Self::db_error)?;
+                session.start_transaction().await.map_err(Self::db_error)?;
+                Some(Mutex::new(session))
+            }
+            None => None,
+        };
+        let transaction = Arc::new(Transaction {
+            session,
+            on_commit: std::sync::Mutex::new(vec![]),
+        });
+
+        let result = CURRENT_TRANSACTION
+            .scope(Arc::clone(&transaction), operations())
+            .await;
+
+        if let Some(mut session) = transaction.session().await {
+            match &result {
+                Ok(_) => session.commit_transaction().await.map_err(Self::db_error)?,
+                // Nothing was written, the original error is the one worth reporting
+                Err(_) => {
+                    let _ = session.abort_transaction().await;
+                }
+            }
+        }
+
+        if result.is_ok() {
+            let hooks = std::mem::take(&mut *transaction.on_commit.lock().unwrap());
+            hooks.into_iter().for_each(|hook| hook());
+        }
+
+        result
+    }
+
+    /// Runs `hook` once the transaction of the current task commits, or right away outside of a
+    /// transaction. E.g. to notify a worker of a document it could not see before
+    pub fn after_commit(hook: impl FnOnce() + Send + 'static) {
+        match Transaction::current() {
+            Some(transaction) => transaction.on_commit.lock().unwrap().push(Box::new(hook)),
+            None => hook(),
+        }
+    }
+
+    fn db_error(e: mongodb::error::Error) -> AppError {
+        AppError::DatabaseError(format!("Transaction failed: {}", e))
+    }
+}
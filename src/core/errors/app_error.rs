@@ -33,6 +33,9 @@ pub enum AppError {
     #[error("jwt_error::{0}")]
     JWTError(String),
 
+    #[error("jwt_configuration_error::{0}")]
+    JwtConfigurationError(String),
+
     #[error("empty_query::The search query is required")]
     EmptyQuery,
 
@@ -46,6 +49,9 @@ pub enum AppError {
     #[error("file_too_large::{0} bytes exceeds limit of {1} bytes")]
     FileTooLarge(u64, u64),
 
+    #[error("storage_quota_exceeded::{0} bytes exceeds the storage quota of {1} bytes")]
+    StorageQuotaExceeded(u64, u64),
+
     #[error("invalid_file_type::Not a valid file type. Allowed types: {0:?}")]
     InvalidFileType(Vec<String>),
 
@@ -76,6 +82,9 @@ pub enum AppError {
 
     #[error("email_configuration_error::{0}")]
     EmailConfigurationError(String),
+
+    #[error("email_template_error::{0}")]
+    EmailTemplateError(String),
 }
 
 impl Reject for AppError {}
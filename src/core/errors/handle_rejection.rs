@@ -17,30 +17,35 @@ pub async fn handle_app_rejection(err: Rejection) -> Result<impl Reply, Rejectio
             | AppError::AccountNotActive(_)
             | AppError::AuthenticationFailed(_) => (StatusCode::FORBIDDEN, e.to_string()),
 
-            AppError::NotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
+            AppError::NotFound(_) | AppError::FileNotFound(_) => {
+                (StatusCode::NOT_FOUND, e.to_string())
+            }
 
             /* ·································································· [ Bad Request ] */
             AppError::BadRequest(_) | AppError::EmptyQuery | AppError::InvalidInput(_) => {
                 (StatusCode::BAD_REQUEST, e.to_string())
             }
+            /* ······································································ [ Storage ] */
+            AppError::InvalidFileType(_) | AppError::InvalidFolderName(_) => {
+                (StatusCode::BAD_REQUEST, e.to_string())
+            }
+            AppError::FileTooLarge(_, _) | AppError::StorageQuotaExceeded(_, _) => {
+                (StatusCode::PAYLOAD_TOO_LARGE, e.to_string())
+            }
             /* ········································································ [ Email ] */
 
 
             /* ························································ [ Internal Server Error ] */
             AppError::Io(_)
-            | AppError::FileTooLarge(_, _)
-            | AppError::InvalidFileType(_)
-            | AppError::InvalidFolderName(_)
             | AppError::UserDirectoryCreationFailed(_)
-            | AppError::FileNotFound(_)
             | AppError::Other(_)
             | AppError::UserDirectoryDeletionFailed(_)
             | AppError::UserDirectoryNotFound(_)
             | AppError::EmailSendingFailed(_)
+            | AppError::JwtConfigurationError(_)
             | AppError::EmailConfigurationError(_)
-            | AppError::EmailInvalidAddress(_) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
-            }
+            | AppError::EmailInvalidAddress(_)
+            | AppError::EmailTemplateError(_) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
 
             _ => (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -49,6 +54,11 @@ pub async fn handle_app_rejection(err: Rejection) -> Result<impl Reply, Rejectio
         }
     } else if let Some(e) = err.find::<warp::filters::body::BodyDeserializeError>() {
         (StatusCode::BAD_REQUEST, format!("bad_request::{e}"))
+    } else if let Some(e) = err.find::<warp::reject::PayloadTooLarge>() {
+        (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("file_too_large::{e}"),
+        )
     } else if let Some(e) = err.find::<warp::reject::MethodNotAllowed>() {
         (StatusCode::METHOD_NOT_ALLOWED, e.to_string())
     } else {
@@ -13,6 +13,15 @@ pub struct UserRegisteredEvent {
 pub struct UserDeletedEvent {
     pub user_id: String,
 }
+/// An already used refresh token was presented again: it leaked, and its family got revoked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshTokenReusedEvent {
+    pub user_id: String,
+    pub token_id: String,
+    pub family_id: String,
+    /// Tokens of the family that were still active
+    pub revoked_tokens: u64,
+}
 
 #[async_trait]
 pub trait CoreEventHandler: Send + Sync {
@@ -22,6 +31,14 @@ pub trait CoreEventHandler: Send + Sync {
     ) -> Result<(), AppError>;
 
     async fn on_user_deleted(self: Arc<Self>, event: &UserDeletedEvent) -> Result<(), AppError>;
+
+    /// Security event, e.g. to alert the user or lock the account. Does nothing by default
+    async fn on_refresh_token_reused(
+        self: Arc<Self>,
+        _event: &RefreshTokenReusedEvent,
+    ) -> Result<(), AppError> {
+        Ok(())
+    }
 }
 
 // Optional: A no-op handler for when no handler is needed
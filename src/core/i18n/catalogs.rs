@@ -0,0 +1,267 @@
+// Copyright (c) 2026 Dr. Younss Ait Mou. All rights reserved.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{RwLock, RwLockReadGuard},
+};
+
+use regex::{Captures, Regex};
+
+use crate::core::{locale_chain, normalize_locale, parse_accept_language, AppError, Validators};
+
+/// The catalogs shipped with the crate, by locale
+const BUILT_IN_CATALOGS: [(&str, &str); 2] = [
+    ("fr", include_str!("locales/fr.json")),
+    ("ar", include_str!("locales/ar.json")),
+];
+
+/// Translates the user facing strings: the `MsgBuilder` outputs, the `AppError` messages and the
+/// `t()` calls of the email templates.
+///
+/// The English strings are the message ids of the catalogs (`{ "Account activated successfully":
+/// "Compte activé avec succès" }`). A message id may hold `{placeholders}`:
+/// `"{entity} loaded successfully!"` translates `"User loaded successfully!"`, the captured
+/// `User` being translated too when the catalog has it.
+///
+/// Lookups fall back along the locale chain (`fr-CA`, `fr`, then the default locale) and end with
+/// the English string itself.
+pub struct I18n {
+    default_locale: String,
+    catalogs: RwLock<HashMap<String, Catalog>>,
+}
+
+#[derive(Default)]
+struct Catalog {
+    messages: HashMap<String, String>,
+    // Message ids holding placeholders, the most specific first
+    patterns: Vec<MessagePattern>,
+}
+
+struct MessagePattern {
+    msgid: String,
+    regex: Regex,
+    // Length of the text around the placeholders
+    specificity: usize,
+}
+
+impl I18n {
+    pub fn new(default_locale: &str) -> Self {
+        let i18n = Self {
+            default_locale: normalize_locale(default_locale).unwrap_or_else(|| "en".to_string()),
+            catalogs: RwLock::new(HashMap::new()),
+        };
+        for (locale, source) in BUILT_IN_CATALOGS {
+            let messages =
+                serde_json::from_str(source).expect("The built-in message catalogs are valid");
+            i18n.add_messages(locale, messages)
+                .expect("The built-in message catalogs are valid");
+        }
+        i18n
+    }
+
+    pub fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+
+    /// The locales having a catalog, and the default one
+    pub fn supported_locales(&self) -> Vec<String> {
+        let mut locales: Vec<String> = self.read().keys().cloned().collect();
+        if !locales.contains(&self.default_locale) {
+            locales.push(self.default_locale.clone());
+        }
+        locales.sort();
+        locales
+    }
+
+    /// Adds (or overrides) translations of the `locale` catalog
+    pub fn add_messages(
+        &self,
+        locale: &str,
+        messages: HashMap<String, String>,
+    ) -> Result<(), AppError> {
+        let locale = Validators::validate_locale(locale)?;
+
+        let mut catalogs = self.catalogs.write().unwrap_or_else(|e| e.into_inner());
+        let catalog = catalogs.entry(locale).or_default();
+        for (msgid, translation) in messages {
+            if !catalog.messages.contains_key(&msgid) {
+                if let Some(pattern) = MessagePattern::new(&msgid) {
+                    catalog.patterns.push(pattern);
+                }
+            }
+            catalog.messages.insert(msgid, translation);
+        }
+        catalog
+            .patterns
+            .sort_by_key(|pattern| Reverse(pattern.specificity));
+
+        Ok(())
+    }
+
+    /// Loads the `<locale>.json` catalogs of `dir` (e.g. `fr.json`, `pt-BR.json`)
+    pub fn load_dir(&self, dir: impl AsRef<Path>) -> Result<(), AppError> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+
+            let locale = path.file_stem().unwrap_or_default().to_string_lossy();
+            let messages = serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+                AppError::Other(format!("Invalid message catalog {}: {}", path.display(), e))
+            })?;
+            self.add_messages(&locale, messages)?;
+        }
+
+        Ok(())
+    }
+
+    /// The first locale of an `Accept-Language` header having a catalog (directly or through its
+    /// parents), the default locale otherwise
+    pub fn negotiate(&self, accept_language: &str) -> String {
+        let catalogs = self.read();
+        parse_accept_language(accept_language)
+            .into_iter()
+            .find(|locale| {
+                locale_chain(locale).iter().any(|candidate| {
+                    *candidate == self.default_locale || catalogs.contains_key(candidate)
+                })
+            })
+            .unwrap_or_else(|| self.default_locale.clone())
+    }
+
+    /// The locales looked up for `locale`, e.g. `["fr-CA", "fr", "en"]`
+    pub fn fallback_chain(&self, locale: &str) -> Vec<String> {
+        let mut chain = normalize_locale(locale)
+            .map(|locale| locale_chain(&locale))
+            .unwrap_or_default();
+        for locale in locale_chain(&self.default_locale) {
+            if !chain.contains(&locale) {
+                chain.push(locale);
+            }
+        }
+        chain
+    }
+
+    /// Translates a message, e.g. `"User loaded successfully!"`
+    pub fn translate(&self, locale: &str, message: &str) -> String {
+        let catalogs = self.read();
+        self.fallback_chain(locale)
+            .iter()
+            .filter_map(|locale| catalogs.get(locale))
+            .find_map(|catalog| catalog.translate(message))
+            .unwrap_or_else(|| message.to_string())
+    }
+
+    /// Translates the message of an `AppError` (`code::message`), keeping its code
+    pub fn translate_error(&self, locale: &str, error: &str) -> String {
+        match error.split_once("::") {
+            Some((code, message)) => format!("{}::{}", code, self.translate(locale, message)),
+            None => self.translate(locale, error),
+        }
+    }
+
+    /// Translates the `msgid` message id then fills its placeholders with `args`, e.g.
+    /// `("Hello {name}", {"name": "Jane"})`
+    pub fn format(&self, locale: &str, msgid: &str, args: &HashMap<String, String>) -> String {
+        let catalogs = self.read();
+        let translation = self
+            .fallback_chain(locale)
+            .iter()
+            .filter_map(|locale| catalogs.get(locale))
+            .find_map(|catalog| catalog.messages.get(msgid))
+            .map_or(msgid, String::as_str);
+
+        fill_placeholders(translation, |name| args.get(name).cloned())
+    }
+
+    /* ····································································· [ Helper functions ] */
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, Catalog>> {
+        self.catalogs.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Catalog {
+    fn translate(&self, message: &str) -> Option<String> {
+        if let Some(translation) = self.messages.get(message) {
+            return Some(translation.clone());
+        }
+
+        let (pattern, captures) = self.patterns.iter().find_map(|pattern| {
+            pattern
+                .regex
+                .captures(message)
+                .map(|captures| (pattern, captures))
+        })?;
+        let translation = self.messages.get(&pattern.msgid)?;
+
+        Some(fill_placeholders(translation, |name| {
+            let value = captures.name(name)?.as_str();
+            Some(
+                self.messages
+                    .get(value)
+                    .cloned()
+                    .unwrap_or_else(|| value.to_string()),
+            )
+        }))
+    }
+}
+
+impl MessagePattern {
+    /// `None` when `msgid` holds no placeholder
+    fn new(msgid: &str) -> Option<Self> {
+        let placeholders = placeholder_regex();
+        if !placeholders.is_match(msgid) {
+            return None;
+        }
+
+        let mut pattern = String::from("^");
+        let mut specificity = 0;
+        let mut names = vec![];
+        let mut last = 0;
+        for captures in placeholders.captures_iter(msgid) {
+            let placeholder = captures.get(0)?;
+            let text = &msgid[last..placeholder.start()];
+            pattern.push_str(&regex::escape(text));
+            specificity += text.len();
+
+            // A placeholder repeated in the message id matches anything the second time
+            let name = captures.get(1)?.as_str();
+            if names.contains(&name) {
+                pattern.push_str("(?:.+?)");
+            } else {
+                pattern.push_str(&format!("(?P<{}>.+?)", name));
+                names.push(name);
+            }
+            last = placeholder.end();
+        }
+        pattern.push_str(&regex::escape(&msgid[last..]));
+        pattern.push('$');
+        specificity += msgid.len() - last;
+
+        Some(Self {
+            msgid: msgid.to_string(),
+            regex: Regex::new(&pattern).ok()?,
+            specificity,
+        })
+    }
+}
+
+fn placeholder_regex() -> Regex {
+    Regex::new(r"\{([A-Za-z_][A-Za-z0-9_]*)\}").expect("valid placeholder pattern")
+}
+
+/// Replaces the `{name}` placeholders of `text`, the unknown ones are kept as is
+fn fill_placeholders(text: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    placeholder_regex()
+        .replace_all(text, |captures: &Captures| {
+            value(&captures[1]).unwrap_or_else(|| captures[0].to_string())
+        })
+        .into_owned()
+}
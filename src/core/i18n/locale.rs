@@ -0,0 +1,76 @@
+// Copyright (c) 2026 Dr. Younss Ait Mou. All rights reserved.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+/// Normalizes a BCP 47 language tag (`fr_ca` or `FR-ca` become `fr-CA`), `None` if it is not
+/// one
+pub fn normalize_locale(tag: &str) -> Option<String> {
+    let mut subtags = tag.trim().split(['-', '_']);
+    let language = subtags.next()?;
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut locale = language.to_ascii_lowercase();
+    for subtag in subtags {
+        if subtag.is_empty()
+            || subtag.len() > 8
+            || !subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return None;
+        }
+
+        locale.push('-');
+        // Regions are upper case (fr-CA), scripts title case (zh-Hant), the rest lower case
+        match subtag.len() {
+            2 => locale.push_str(&subtag.to_ascii_uppercase()),
+            4 => {
+                locale.push_str(&subtag[..1].to_ascii_uppercase());
+                locale.push_str(&subtag[1..].to_ascii_lowercase());
+            }
+            _ => locale.push_str(&subtag.to_ascii_lowercase()),
+        }
+    }
+    Some(locale)
+}
+
+/// The locale followed by its parents, e.g. `["zh-Hant-TW", "zh-Hant", "zh"]`
+pub fn locale_chain(locale: &str) -> Vec<String> {
+    let mut chain = vec![locale.to_string()];
+    let mut current = locale;
+    while let Some(index) = current.rfind('-') {
+        current = &current[..index];
+        chain.push(current.to_string());
+    }
+    chain
+}
+
+/// The locales of an `Accept-Language` header, the preferred one first. `*`, invalid tags and
+/// `q=0` are skipped
+pub fn parse_accept_language(header: &str) -> Vec<String> {
+    let mut locales: Vec<(String, f32)> = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut params = entry.split(';');
+            let locale = normalize_locale(params.next()?)?;
+            let quality = match params.find_map(|param| param.trim().strip_prefix("q=")) {
+                Some(quality) => quality.trim().parse().ok()?,
+                None => 1.0,
+            };
+            (quality > 0.0).then_some((locale, quality))
+        })
+        .collect();
+
+    // Stable sort, the locales with the same quality keep the header order
+    locales.sort_by(|a, b| b.1.total_cmp(&a.1));
+    locales.into_iter().map(|(locale, _)| locale).collect()
+}
+
+/// `rtl` for the languages written from right to left, `ltr` otherwise
+pub fn text_direction(locale: &str) -> &'static str {
+    match locale.split('-').next() {
+        Some("ar" | "fa" | "he" | "ur" | "ps" | "yi" | "dv" | "ckb") => "rtl",
+        _ => "ltr",
+    }
+}
@@ -0,0 +1,5 @@
+mod locale;
+pub use locale::*;
+
+mod catalogs;
+pub use catalogs::*;
@@ -7,7 +7,7 @@ use crate::{
 /// It requires an auth middleware and should be used as fellows
 /// ```rust
 ///  warp::path!("admin-route")
-///      .and(auth_middleware(self.sl.jwt_service()))
+///      .and(auth_middleware(self.sl.authenticator()))
 ///      .and_then(admin_middleware)
 ///      .and_then(move |claims: Claims| {
 ///          let handler = self.clone();
@@ -1,23 +1,59 @@
 use std::sync::Arc;
 
+use async_trait::async_trait;
 use warp::{filters::header::headers_cloned, http::HeaderMap, reject::Rejection, Filter};
 
-use crate::{api::auth::domain::entities::Claims, core::jwt_service::JwtService};
+use crate::{
+    api::{auth::domain::entities::Claims, auth_token::domain::services::Authenticator},
+    core::{jwt_service::JwtService, middleware::scope_middleware, AppError},
+};
 
-// Authentication middleware
-pub fn auth_middleware(
-    jwt_service: Arc<JwtService>,
+/// What `auth_middleware` resolves the credentials of a request with
+#[async_trait]
+pub trait Authenticate: Send + Sync {
+    async fn authenticate(&self, headers: &HeaderMap) -> Result<Claims, AppError>;
+}
+
+/// Rejects the revoked access tokens, and accepts the API keys
+#[async_trait]
+impl Authenticate for Authenticator {
+    async fn authenticate(&self, headers: &HeaderMap) -> Result<Claims, AppError> {
+        Authenticator::authenticate(self, headers).await
+    }
+}
+
+/// Checks the signature of the access token only, as `auth_middleware` used to. Pass
+/// `sl.authenticator()` instead for the revoked tokens to be rejected
+#[async_trait]
+impl Authenticate for JwtService {
+    async fn authenticate(&self, headers: &HeaderMap) -> Result<Claims, AppError> {
+        self.decode_jwt(headers)
+    }
+}
+
+// Authentication middleware, the revoked access tokens are rejected when given the
+// `Authenticator` (see `Authenticate`)
+pub fn auth_middleware<A: Authenticate + 'static>(
+    authenticator: Arc<A>,
 ) -> impl Filter<Extract = (Claims,), Error = Rejection> + Clone {
     headers_cloned().and_then(move |headers: HeaderMap| {
-        let jwt_service = jwt_service.clone();
+        let authenticator = authenticator.clone();
         async move {
-            // Read the JWT from the headers ('Authorization')
-            match jwt_service.decode_jwt(&headers) {
+            // Read the JWT from the headers ('Authorization'), or the API key from 'X-API-KEY'
+            match authenticator.authenticate(&headers).await {
                 Ok(claims) => Ok(claims),
                 Err(e) => Err(warp::reject::custom(e)),
             }
-            // if the jwt is not present in the auth header
-            // we check if we have x-api-key and decode it
         }
     })
 }
+
+/// `auth_middleware` for the routes an API key must hold `scope` to reach, e.g. `storage:write`.
+/// The API keys may be given `scope` from then on (see `Authenticator::register_scope`)
+pub fn scoped_auth_middleware(
+    authenticator: Arc<Authenticator>,
+    scope: &'static str,
+) -> impl Filter<Extract = (Claims,), Error = Rejection> + Clone {
+    authenticator.register_scope(scope);
+    auth_middleware(authenticator).and_then(move |claims: Claims| scope_middleware(scope, claims))
+}
@@ -0,0 +1,32 @@
+use warp::{reject::Rejection, Filter};
+
+/// What a request tells about its client, e.g. to describe the sessions of a user
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// The `ClientInfo` of the request. The IP address is the one the reverse proxy reports, in the
+/// `X-Forwarded-For` (its first address) or `X-Real-IP` header, and is unknown without one. Any
+/// client may set these headers too: it is informative only
+pub fn client_middleware() -> impl Filter<Extract = (ClientInfo,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("user-agent")
+        .and(warp::header::optional::<String>("x-forwarded-for"))
+        .and(warp::header::optional::<String>("x-real-ip"))
+        .map(
+            |user_agent: Option<String>, forwarded_for: Option<String>, real_ip: Option<String>| {
+                let ip_address = forwarded_for
+                    .as_deref()
+                    .and_then(|header| header.split(',').next())
+                    .or(real_ip.as_deref())
+                    .map(|ip| ip.trim().to_string())
+                    .filter(|ip| !ip.is_empty());
+
+                ClientInfo {
+                    user_agent,
+                    ip_address,
+                }
+            },
+        )
+}
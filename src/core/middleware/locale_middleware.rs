@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use http_body_util::BodyExt;
+use warp::{
+    filters::header::headers_cloned,
+    http::{
+        header::{
+            ACCEPT_LANGUAGE, ACCEPT_RANGES, CONTENT_LANGUAGE, CONTENT_LENGTH, CONTENT_RANGE,
+            CONTENT_TYPE,
+        },
+        HeaderMap, HeaderValue, StatusCode,
+    },
+    reject::Rejection,
+    reply::{Reply, Response},
+    Filter,
+};
+
+use crate::{api::auth_token::domain::services::Authenticator, core::I18n};
+
+// Locale of the request: the one of the authenticated user, then the `Accept-Language` header,
+// then the default locale. The user's one is read from the claims `auth_middleware` authenticated
+// (see `Authenticator::locale`), the token is not decoded a second time
+pub fn locale_middleware(
+    authenticator: Arc<Authenticator>,
+    i18n: Arc<I18n>,
+) -> impl Filter<Extract = (String,), Error = std::convert::Infallible> + Clone {
+    headers_cloned().and_then(move |headers: HeaderMap| {
+        let authenticator = Arc::clone(&authenticator);
+        let i18n = Arc::clone(&i18n);
+        async move {
+            if let Some(locale) = authenticator.locale(&headers).await {
+                return Ok::<_, std::convert::Infallible>(locale);
+            }
+
+            let accept_language = headers
+                .get(ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default();
+            Ok(i18n.negotiate(accept_language))
+        }
+    })
+}
+
+// Translates the `message` of the app's JSON responses (`ApiResponse`), the rejections included,
+// into the locale of the request. The locale is resolved once the routes ran, so after `auth_middleware`
+pub fn localize_responses<F, R>(
+    routes: F,
+    authenticator: Arc<Authenticator>,
+    i18n: Arc<I18n>,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone
+where
+    F: Filter<Extract = (R,), Error = Rejection> + Clone + Send + Sync + 'static,
+    R: Reply,
+{
+    routes
+        .and(locale_middleware(authenticator, Arc::clone(&i18n)))
+        .and_then(move |reply: R, locale: String| {
+            let i18n = Arc::clone(&i18n);
+            let response = reply.into_response();
+            async move { Ok::<_, Rejection>(localize_response(&i18n, &locale, response).await) }
+        })
+}
+
+async fn localize_response(i18n: &I18n, locale: &str, response: Response) -> Response {
+    let headers = response.headers();
+    let is_json = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    // Files, websocket upgrades... are passed through without being buffered. The files served by
+    // the storage routes (`Accept-Ranges`), JSON ones included, are the users' data
+    if !is_json || headers.contains_key(ACCEPT_RANGES) || headers.contains_key(CONTENT_RANGE) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(e) => {
+            eprintln!("Could not read the response to localize: {}", e);
+            parts.status = StatusCode::INTERNAL_SERVER_ERROR;
+            return Response::from_parts(parts, Default::default());
+        }
+    };
+
+    let mut json: serde_json::Value = match serde_json::from_slice(&bytes) {
+        Ok(json) => json,
+        Err(_) => return Response::from_parts(parts, bytes.into()),
+    };
+    if !is_api_response(&json) {
+        return Response::from_parts(parts, bytes.into());
+    }
+    let Some(serde_json::Value::String(message)) = json.get_mut("message") else {
+        return Response::from_parts(parts, bytes.into());
+    };
+
+    *message = i18n.translate_error(locale, message);
+    if let Ok(locale) = HeaderValue::from_str(locale) {
+        parts.headers.insert(CONTENT_LANGUAGE, locale);
+    }
+    parts.headers.remove(CONTENT_LENGTH);
+    let body = serde_json::to_vec(&json).unwrap_or_else(|_| bytes.to_vec());
+    Response::from_parts(parts, body.into())
+}
+
+// Whether a JSON body has the shape of an `ApiResponse`, i.e. was built by the app
+fn is_api_response(json: &serde_json::Value) -> bool {
+    let Some(fields) = json.as_object() else {
+        return false;
+    };
+
+    fields
+        .get("success")
+        .is_some_and(|success| success.is_boolean())
+        && fields
+            .get("message")
+            .is_some_and(|message| message.is_string())
+        && fields
+            .keys()
+            .all(|field| matches!(field.as_str(), "success" | "message" | "data"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::response::ApiResponse;
+
+    const MESSAGE: &str = "Invalid two-factor authentication code";
+
+    async fn localize(response: Response) -> (warp::http::response::Parts, Vec<u8>) {
+        let response = localize_response(&I18n::new("en"), "fr", response).await;
+        let (parts, body) = response.into_parts();
+        (parts, body.collect().await.unwrap().to_bytes().to_vec())
+    }
+
+    #[tokio::test]
+    async fn translates_the_api_responses() {
+        let response = warp::reply::json(&ApiResponse::<()>::error(MESSAGE.to_string()));
+        let response = response.into_response();
+
+        let (parts, body) = localize(response).await;
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let translated = I18n::new("en").translate("fr", MESSAGE);
+        assert_ne!(translated, MESSAGE);
+        assert_eq!(json["message"], translated.as_str());
+        assert_eq!(parts.headers[CONTENT_LANGUAGE], "fr");
+    }
+
+    #[tokio::test]
+    async fn passes_other_json_bodies_through() {
+        let body = format!(r#"{{"message":"{MESSAGE}","from":"a user"}}"#);
+        let response = warp::http::Response::builder()
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.clone().into())
+            .unwrap();
+
+        let (_, localized) = localize(response).await;
+        assert_eq!(localized, body.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn serves_uploaded_json_files_byte_for_byte() {
+        // An uploaded file that happens to look like an `ApiResponse`, as served by the storage
+        // routes of the `FileSystem` backend
+        let dir = std::env::temp_dir().join(format!("locale-middleware-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let content = format!("{{ \"success\": false,\n  \"message\": \"{MESSAGE}\" }}\n");
+        std::fs::write(dir.join("upload.json"), &content).unwrap();
+
+        let files = warp::fs::dir(dir.clone())
+            .map(Reply::into_response)
+            .then(|response| async move { localize(response).await });
+        let (parts, body) = warp::test::request()
+            .path("/upload.json")
+            .filter(&files)
+            .await
+            .unwrap();
+        assert_eq!(body, content.as_bytes());
+        assert_eq!(
+            parts.headers[CONTENT_LENGTH],
+            content.len().to_string().as_str()
+        );
+
+        let files = warp::fs::dir(dir.clone())
+            .map(Reply::into_response)
+            .then(|response| async move { localize(response).await });
+        let (parts, body) = warp::test::request()
+            .path("/upload.json")
+            .header("range", "bytes=0-9")
+            .filter(&files)
+            .await
+            .unwrap();
+        assert_eq!(parts.status, StatusCode::PARTIAL_CONTENT);
+        assert_eq!(body, content.as_bytes()[..10]);
+        assert_eq!(parts.headers[CONTENT_LENGTH], "10");
+        assert!(parts.headers.contains_key(ACCEPT_RANGES));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
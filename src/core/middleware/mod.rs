@@ -6,3 +6,12 @@ pub use admin_middleware::*;
 
 pub mod owner_or_admin_middleware;
 pub use owner_or_admin_middleware::*;
+
+pub mod locale_middleware;
+pub use locale_middleware::*;
+
+pub mod scope_middleware;
+pub use scope_middleware::*;
+
+pub mod client_middleware;
+pub use client_middleware::*;
@@ -0,0 +1,26 @@
+use crate::{
+    api::auth::domain::entities::Claims,
+    core::{AppError, MsgBuilder},
+};
+
+/// Rejects the API keys lacking `scope`, see `Claims::has_scope`. The access tokens go through.
+/// Most routes use it through `scoped_auth_middleware`
+pub async fn scope_middleware(scope: &str, claims: Claims) -> Result<Claims, warp::Rejection> {
+    if claims.has_scope(scope) {
+        Ok(claims)
+    } else {
+        let msg = MsgBuilder::no_permission_to("perform this action with this API key");
+        Err(warp::reject::custom(AppError::Forbidden(msg)))
+    }
+}
+
+/// Rejects the requests authenticated with an API key, for the routes requiring a user session,
+/// e.g. the ones managing the API keys. Should be used as `admin_middleware`
+pub async fn session_middleware(claims: Claims) -> Result<Claims, warp::Rejection> {
+    if claims.api_key.is_none() {
+        Ok(claims)
+    } else {
+        let msg = MsgBuilder::no_permission_to("perform this action with an API key");
+        Err(warp::reject::custom(AppError::Forbidden(msg)))
+    }
+}
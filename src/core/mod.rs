@@ -17,6 +17,10 @@ pub use entities::*;
 pub mod services;
 pub use services::*;
 
+// localization
+pub mod i18n;
+pub use i18n::*;
+
 // templates
 pub mod templates;
 pub use templates::*;
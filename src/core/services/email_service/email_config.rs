@@ -0,0 +1,126 @@
+// Copyright (c) 2026 Dr. Younss Ait Mou. All rights reserved.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::time::Duration;
+
+/// Selects the `EmailService` implementation
+#[derive(Debug, Clone, Default)]
+pub enum EmailBackendConfig {
+    /// Emails go through the Resend HTTP API, see `Config::resend_token`
+    #[default]
+    Resend,
+    /// Emails go through an SMTP server, e.g. a self-hosted mail server or a corporate relay
+    Smtp(SmtpConfig),
+    /// Nothing is sent, emails are kept to be read from the `dev/emails` routes. Meant for local
+    /// development and integration tests
+    Capture(CaptureEmailConfig),
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    /// Defaults to the usual port of `tls` when `None` (587, 465 or 25)
+    pub port: Option<u16>,
+    /// No authentication unless both the username and the password are set
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub tls: SmtpTls,
+    /// Connections kept open and reused between emails
+    pub pool_size: u32,
+    /// Seconds before a connection or a command times out
+    pub timeout: u64,
+}
+
+impl SmtpConfig {
+    pub fn new(host: &str) -> Self {
+        Self {
+            host: host.to_string(),
+            port: None,
+            username: None,
+            password: None,
+            tls: SmtpTls::default(),
+            pool_size: 10,
+            timeout: 60,
+        }
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port.unwrap_or(match self.tls {
+            SmtpTls::StartTls => 587,
+            SmtpTls::Implicit => 465,
+            SmtpTls::None => 25,
+        })
+    }
+}
+
+/// How the connection to the SMTP server is secured
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SmtpTls {
+    /// Plain connection upgraded with `STARTTLS`, which the server must support
+    #[default]
+    StartTls,
+    /// TLS from the start, a.k.a. SMTPS
+    Implicit,
+    /// Plain text. Only meant for local SMTP sinks (e.g. MailHog, Mailpit) and trusted networks
+    None,
+}
+
+impl SmtpTls {
+    pub fn from_env_value(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "starttls" | "start_tls" | "start-tls" => Some(Self::StartTls),
+            "tls" | "ssl" | "implicit" | "smtps" => Some(Self::Implicit),
+            "none" | "plain" | "off" => Some(Self::None),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CaptureEmailConfig {
+    /// Mailbox directory where each email is written as a JSON file. Emails are kept in memory
+    /// (and lost on restart) when `None`
+    pub dir: Option<String>,
+}
+
+/// Delivery of the emails queued in the outbox (see `OutboxEmailService`)
+#[derive(Debug, Clone)]
+pub struct EmailOutboxConfig {
+    /// Seconds between two checks for emails due, new emails are delivered right away
+    pub poll_interval: u64,
+    /// Attempts before an email is dead-lettered
+    pub max_attempts: u32,
+    /// Seconds before the first retry, doubled after each failed attempt
+    pub base_delay: u64,
+    /// Longest wait between two attempts, in seconds
+    pub max_delay: u64,
+    /// Seconds a worker owns the email it is delivering. The email is tried again once it
+    /// expires, e.g. if the server stopped mid-delivery
+    pub lease: u64,
+}
+
+impl Default for EmailOutboxConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: 5,
+            max_attempts: 8,
+            base_delay: 30,
+            max_delay: 60 * 60, // 1 hour
+            lease: 5 * 60,      // 5 minutes
+        }
+    }
+}
+
+impl EmailOutboxConfig {
+    /// Wait after the given number of failed attempts
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u64.saturating_pow(attempts.saturating_sub(1));
+        Duration::from_secs(self.base_delay.saturating_mul(factor).min(self.max_delay))
+    }
+
+    pub fn lease_duration(&self) -> Duration {
+        Duration::from_secs(self.lease)
+    }
+}
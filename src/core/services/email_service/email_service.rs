@@ -4,15 +4,75 @@
 // LICENSE file in the root directory of this source tree.
 
 use async_trait::async_trait;
+use serde_json::{json, Value};
 
-use crate::core::{AppError, Email};
+use crate::core::{AppError, Email, EmailAddress, TemplateRegistry};
 
 pub type EmailServiceResult<T> = Result<T, AppError>;
 
 #[async_trait]
 pub trait EmailService: Send + Sync {
     async fn send(&self, email: &Email) -> EmailServiceResult<()>;
-    async fn send_activation_email(&self, to: &str, token: &str) -> EmailServiceResult<()>;
-    async fn send_reset_pwd_email(&self, to: &str, token: &str) -> EmailServiceResult<()>;
-    async fn send_pwd_reset_confirmation_email(&self, to: &str) -> EmailServiceResult<()>;
+
+    /// The templates the emails are rendered with, the built-in ones unless overridden
+    fn templates(&self) -> &TemplateRegistry {
+        TemplateRegistry::built_in()
+    }
+
+    /// Renders the `template` email with the `context` variables and sends it to `to`. The email is
+    /// written in the `locale` of the context, see `TemplateRegistry::render_email`
+    async fn send_template(
+        &self,
+        to: &str,
+        template: &str,
+        context: Value,
+    ) -> EmailServiceResult<()> {
+        let content = self.templates().render_email(template, context)?;
+        let email = Email::new(EmailAddress::new(to)?, content);
+        self.send(&email).await
+    }
+
+    async fn send_activation_email(&self, to: &str, token: &str) -> EmailServiceResult<()> {
+        self.send_activation_email_localized(to, token, None).await
+    }
+    async fn send_reset_pwd_email(&self, to: &str, token: &str) -> EmailServiceResult<()> {
+        self.send_reset_pwd_email_localized(to, token, None).await
+    }
+    async fn send_pwd_reset_confirmation_email(&self, to: &str) -> EmailServiceResult<()> {
+        self.send_pwd_reset_confirmation_email_localized(to, None)
+            .await
+    }
+
+    /// `send_activation_email` written in `locale`, the default locale when `None`
+    async fn send_activation_email_localized(
+        &self,
+        to: &str,
+        token: &str,
+        locale: Option<&str>,
+    ) -> EmailServiceResult<()> {
+        let context = json!({ "email": to, "token": token, "locale": locale });
+        self.send_template(to, TemplateRegistry::ACTIVATION, context)
+            .await
+    }
+    /// `send_reset_pwd_email` written in `locale`, the default locale when `None`
+    async fn send_reset_pwd_email_localized(
+        &self,
+        to: &str,
+        token: &str,
+        locale: Option<&str>,
+    ) -> EmailServiceResult<()> {
+        let context = json!({ "email": to, "token": token, "locale": locale });
+        self.send_template(to, TemplateRegistry::RESET_PASSWORD, context)
+            .await
+    }
+    /// `send_pwd_reset_confirmation_email` written in `locale`, the default locale when `None`
+    async fn send_pwd_reset_confirmation_email_localized(
+        &self,
+        to: &str,
+        locale: Option<&str>,
+    ) -> EmailServiceResult<()> {
+        let context = json!({ "email": to, "locale": locale });
+        self.send_template(to, TemplateRegistry::RESET_PASSWORD_CONFIRMATION, context)
+            .await
+    }
 }
@@ -0,0 +1,253 @@
+// Copyright (c) 2026 Dr. Younss Ait Mou. All rights reserved.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::{
+    AppError, CaptureEmailConfig, Config, Email, EmailAddress, EmailService, EmailServiceResult,
+    TemplateRegistry,
+};
+
+/// An email kept by `EmailServiceCaptureImpl` instead of being sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturedEmail {
+    /// Sorts in capture order
+    pub id: String,
+    pub captured_at: DateTime<Utc>,
+    /// `From`, `To`, `Cc`, `Reply-To`, `Subject`, `Date`, `Message-ID` and the custom headers as
+    /// they would have been sent
+    pub headers: BTreeMap<String, String>,
+    pub from: String,
+    /// The recipients, comma separated
+    pub to: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cc: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bcc: Vec<String>,
+    pub subject: String,
+    pub html: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<CapturedAttachment>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+}
+
+/// An attachment of a `CapturedEmail`, without its content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturedAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_id: Option<String>,
+}
+
+/// Captures the emails instead of sending them, in a mailbox directory or in memory.
+///
+/// Lets the activation and password reset flows run offline: the tokens are read back from the
+/// captured emails (see the `dev/emails` routes).
+pub struct EmailServiceCaptureImpl {
+    config: Config,
+    templates: Arc<TemplateRegistry>,
+    dir: Option<PathBuf>,
+    emails: RwLock<Vec<CapturedEmail>>,
+}
+
+impl EmailServiceCaptureImpl {
+    pub fn new(
+        config: &Config,
+        capture: &CaptureEmailConfig,
+        templates: Arc<TemplateRegistry>,
+    ) -> Self {
+        Self {
+            config: config.clone(),
+            templates,
+            dir: capture.dir.as_ref().map(PathBuf::from),
+            emails: RwLock::new(vec![]),
+        }
+    }
+
+    /// Captured emails, the most recent first
+    pub async fn list(&self) -> EmailServiceResult<Vec<CapturedEmail>> {
+        let mut emails = match &self.dir {
+            Some(dir) => Self::read_dir(dir).await?,
+            None => self
+                .emails
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+        };
+        emails.sort_by(|a, b| b.id.cmp(&a.id));
+        Ok(emails)
+    }
+
+    pub async fn get(&self, id: &str) -> EmailServiceResult<CapturedEmail> {
+        let not_found = || AppError::NotFound(format!("No captured email with id {}", id));
+
+        match &self.dir {
+            Some(dir) => {
+                // Ids are generated by `capture`, anything else could escape the mailbox
+                if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                    return Err(not_found());
+                }
+                match tokio::fs::read(dir.join(format!("{}.json", id))).await {
+                    Ok(bytes) => Self::parse(&bytes),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(not_found()),
+                    Err(e) => Err(Self::io_error(e)),
+                }
+            }
+            None => self
+                .emails
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .find(|email| email.id == id)
+                .cloned()
+                .ok_or_else(not_found),
+        }
+    }
+
+    async fn capture(&self, email: &Email) -> EmailServiceResult<CapturedEmail> {
+        let captured_at = Utc::now();
+        let id = format!(
+            "{}-{}",
+            captured_at.timestamp_micros(),
+            Uuid::new_v4().simple()
+        );
+        let from = email.sender(&self.config).to_string();
+        let to = Self::join(&email.to);
+        let cc: Vec<String> = email.cc.iter().map(ToString::to_string).collect();
+
+        let mut headers = email.headers.clone();
+        headers.extend([
+            ("From".to_string(), from.clone()),
+            ("To".to_string(), to.clone()),
+            ("Subject".to_string(), email.subject.clone()),
+            ("Date".to_string(), captured_at.to_rfc2822()),
+            (
+                "Message-ID".to_string(),
+                format!("<{}@{}>", id, self.config.app_name.replace(' ', "-")),
+            ),
+        ]);
+        if !cc.is_empty() {
+            headers.insert("Cc".to_string(), cc.join(", "));
+        }
+        if !email.reply_to.is_empty() {
+            headers.insert("Reply-To".to_string(), Self::join(&email.reply_to));
+        }
+
+        let attachments = email
+            .attachments
+            .iter()
+            .map(|attachment| CapturedAttachment {
+                filename: attachment.filename.clone(),
+                content_type: attachment.mime_type(),
+                size: attachment.content.len(),
+                content_id: attachment.content_id.clone(),
+            })
+            .collect();
+
+        let captured = CapturedEmail {
+            id,
+            captured_at,
+            headers,
+            from,
+            to,
+            cc,
+            bcc: email.bcc.iter().map(ToString::to_string).collect(),
+            subject: email.subject.clone(),
+            html: email.content.html_content.clone(),
+            text: email.content.text_content.clone(),
+            attachments,
+            tags: email.tags.clone(),
+        };
+
+        match &self.dir {
+            Some(dir) => {
+                let json = serde_json::to_vec_pretty(&captured)
+                    .map_err(|e| AppError::EmailSendingFailed(e.to_string()))?;
+                tokio::fs::create_dir_all(dir)
+                    .await
+                    .map_err(Self::io_error)?;
+                tokio::fs::write(dir.join(format!("{}.json", captured.id)), json)
+                    .await
+                    .map_err(Self::io_error)?;
+            }
+            None => self
+                .emails
+                .write()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(captured.clone()),
+        }
+
+        Ok(captured)
+    }
+
+    /* ····································································· [ Helper functions ] */
+    fn join(addresses: &[EmailAddress]) -> String {
+        addresses
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    async fn read_dir(dir: &PathBuf) -> EmailServiceResult<Vec<CapturedEmail>> {
+        let mut entries = match tokio::fs::read_dir(dir).await {
+            Ok(entries) => entries,
+            // Nothing captured yet
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(Self::io_error(e)),
+        };
+
+        let mut emails = vec![];
+        while let Some(entry) = entries.next_entry().await.map_err(Self::io_error)? {
+            if entry.path().extension().is_some_and(|ext| ext == "json") {
+                let bytes = tokio::fs::read(entry.path())
+                    .await
+                    .map_err(Self::io_error)?;
+                emails.push(Self::parse(&bytes)?);
+            }
+        }
+        Ok(emails)
+    }
+
+    fn parse(bytes: &[u8]) -> EmailServiceResult<CapturedEmail> {
+        serde_json::from_slice(bytes)
+            .map_err(|e| AppError::InternalServer(format!("Invalid captured email: {}", e)))
+    }
+
+    fn io_error(e: std::io::Error) -> AppError {
+        AppError::InternalServer(format!("Email mailbox error: {}", e))
+    }
+}
+
+#[async_trait]
+impl EmailService for EmailServiceCaptureImpl {
+    async fn send(&self, email: &Email) -> EmailServiceResult<()> {
+        let captured = self.capture(email).await?;
+        eprintln!(
+            "Captured email {} to {}: {}",
+            captured.id, captured.to, captured.subject
+        );
+
+        Ok(())
+    }
+
+    fn templates(&self) -> &TemplateRegistry {
+        &self.templates
+    }
+}
@@ -3,27 +3,29 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use async_trait::async_trait;
-use resend_rs::{types::CreateEmailBaseOptions, Resend};
+use std::sync::Arc;
 
-use crate::core::{
-    activate_account_email_template::activation_email_template,
-    password_reset_email_template::password_reset_confirmation_email_template,
-    reset_pwd_token_sent_template::reset_password_email_template, AppError, Config, Email,
-    EmailAddress, EmailService, EmailServiceResult,
+use async_trait::async_trait;
+use resend_rs::{
+    types::{CreateAttachment, CreateEmailBaseOptions, Tag},
+    Resend,
 };
 
+use crate::core::{AppError, Config, Email, EmailService, EmailServiceResult, TemplateRegistry};
+
 pub struct EmailServicerResendImpl {
     client: Resend,
     config: Config,
+    templates: Arc<TemplateRegistry>,
 }
 
 impl EmailServicerResendImpl {
-    pub fn new(config: &Config) -> Self {
+    pub fn new(config: &Config, templates: Arc<TemplateRegistry>) -> Self {
         let client = Resend::new(&config.resend_token);
         Self {
             client,
             config: config.clone(),
+            templates,
         }
     }
 }
@@ -31,54 +33,50 @@ impl EmailServicerResendImpl {
 #[async_trait]
 impl EmailService for EmailServicerResendImpl {
     async fn send(&self, email: &Email) -> EmailServiceResult<()> {
-        let from = format!("{} <{}>", self.config.app_name, self.config.email_from)
-            .trim()
-            .to_string();
+        let from = email.sender(&self.config).to_string();
+        let to = email.to.iter().map(ToString::to_string);
 
-        let to = format!(
-            "{} <{}>",
-            email.to.full_name.as_deref().unwrap_or(&email.to.address),
-            email.to.address
-        )
-        .trim()
-        .to_string();
-
-        let email = CreateEmailBaseOptions::new(from, [to], email.subject.clone())
+        let mut options = CreateEmailBaseOptions::new(from, to, email.subject.clone())
             .with_html(&email.content.html_content);
+        if let Some(text) = &email.content.text_content {
+            options = options.with_text(text);
+        }
+        for cc in &email.cc {
+            options = options.with_cc(&cc.to_string());
+        }
+        for bcc in &email.bcc {
+            options = options.with_bcc(&bcc.to_string());
+        }
+        if !email.reply_to.is_empty() {
+            let reply_to: Vec<String> = email.reply_to.iter().map(ToString::to_string).collect();
+            options = options.with_reply_multiple(&reply_to);
+        }
+        for attachment in &email.attachments {
+            let mut file = CreateAttachment::from_content(attachment.content.clone())
+                .with_filename(&attachment.filename)
+                .with_content_type(&attachment.mime_type());
+            if let Some(content_id) = &attachment.content_id {
+                file = file.with_content_id(content_id);
+            }
+            options = options.with_attachment(file);
+        }
+        for (name, value) in &email.tags {
+            options = options.with_tag(Tag::new(name, value));
+        }
+        for (name, value) in &email.headers {
+            options = options.with_header(name, value);
+        }
 
         self.client
             .emails
-            .send(email)
+            .send(options)
             .await
             .map_err(|e| AppError::EmailSendingFailed(e.to_string()))?;
 
         Ok(())
     }
 
-    async fn send_activation_email(&self, to: &str, token: &str) -> EmailServiceResult<()> {
-        let content = activation_email_template(to, token, &self.config.app_name);
-        let email_address = EmailAddress::new(to)?;
-        let email = Email::new(email_address, content);
-        self.send(&email).await?;
-
-        Ok(())
-    }
-    async fn send_reset_pwd_email(&self, to: &str, token: &str) -> EmailServiceResult<()> {
-        let content = reset_password_email_template(to, token, &self.config.app_name);
-
-        let email_address = EmailAddress::new(to)?;
-        let email = Email::new(email_address, content);
-        self.send(&email).await?;
-
-        Ok(())
-    }
-    async fn send_pwd_reset_confirmation_email(&self, to: &str) -> EmailServiceResult<()> {
-        let content = password_reset_confirmation_email_template(to, &self.config.app_name);
-
-        let email_address = EmailAddress::new(to)?;
-        let email = Email::new(email_address, content);
-        self.send(&email).await?;
-
-        Ok(())
+    fn templates(&self) -> &TemplateRegistry {
+        &self.templates
     }
 }
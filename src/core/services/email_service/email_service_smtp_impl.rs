@@ -0,0 +1,302 @@
+// Copyright (c) 2026 Dr. Younss Ait Mou. All rights reserved.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use lettre::{
+    message::{
+        header::{ContentType, HeaderName, HeaderValue},
+        Attachment, Mailbox, MultiPart, SinglePart,
+    },
+    transport::smtp::{authentication::Credentials, PoolConfig},
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+
+use crate::core::{
+    AppError, Config, Email, EmailAddress, EmailAttachment, EmailService, EmailServiceResult,
+    SmtpConfig, SmtpTls, TemplateRegistry,
+};
+
+/// Sends the emails through an SMTP server, reusing pooled connections
+pub struct EmailServiceSmtpImpl {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    config: Config,
+    templates: Arc<TemplateRegistry>,
+}
+
+impl EmailServiceSmtpImpl {
+    pub fn new(
+        config: &Config,
+        smtp: &SmtpConfig,
+        templates: Arc<TemplateRegistry>,
+    ) -> EmailServiceResult<Self> {
+        let builder = match smtp.tls {
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host),
+            SmtpTls::Implicit => AsyncSmtpTransport::<Tokio1Executor>::relay(&smtp.host),
+            SmtpTls::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                &smtp.host,
+            )),
+        }
+        .map_err(|e| {
+            AppError::EmailConfigurationError(format!("Invalid SMTP configuration: {}", e))
+        })?;
+
+        let mut builder = builder
+            .port(smtp.port())
+            .timeout(Some(Duration::from_secs(smtp.timeout)))
+            .pool_config(PoolConfig::new().max_size(smtp.pool_size.max(1)));
+
+        if let (Some(username), Some(password)) = (&smtp.username, &smtp.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            config: config.clone(),
+            templates,
+        })
+    }
+
+    /* ····································································· [ Helper functions ] */
+    fn mailbox(address: &EmailAddress) -> EmailServiceResult<Mailbox> {
+        let email = address
+            .address
+            .parse()
+            .map_err(|e| AppError::EmailSendingFailed(format!("{}: {}", address.address, e)))?;
+        Ok(Mailbox::new(address.full_name.clone(), email))
+    }
+
+    fn header(name: &str, value: &str) -> EmailServiceResult<HeaderValue> {
+        let name = HeaderName::new_from_ascii(name.to_string())
+            .map_err(|e| AppError::EmailSendingFailed(format!("{}: {}", name, e)))?;
+        Ok(HeaderValue::new(name, value.to_string()))
+    }
+
+    // The HTML body and its plain text alternative, then the inline attachments it references, then
+    // the attached files
+    fn body(email: &Email) -> EmailServiceResult<MultiPart> {
+        let html = email.content.html_content.clone();
+        let mut body = match &email.content.text_content {
+            Some(text) => MultiPart::alternative_plain_html(text.clone(), html),
+            None => MultiPart::alternative().singlepart(SinglePart::html(html)),
+        };
+
+        let (inline, attached): (Vec<_>, Vec<_>) = email
+            .attachments
+            .iter()
+            .partition(|attachment| attachment.content_id.is_some());
+
+        if !inline.is_empty() {
+            body = inline.into_iter().try_fold(
+                MultiPart::related().multipart(body),
+                |related, attachment| {
+                    let content_id = attachment.content_id.clone().unwrap_or_default();
+                    let part = Attachment::new_inline(content_id)
+                        .body(attachment.content.clone(), Self::content_type(attachment)?);
+                    Ok::<_, AppError>(related.singlepart(part))
+                },
+            )?;
+        }
+        if !attached.is_empty() {
+            body = attached.into_iter().try_fold(
+                MultiPart::mixed().multipart(body),
+                |mixed, attachment| {
+                    let part = Attachment::new(attachment.filename.clone())
+                        .body(attachment.content.clone(), Self::content_type(attachment)?);
+                    Ok::<_, AppError>(mixed.singlepart(part))
+                },
+            )?;
+        }
+
+        Ok(body)
+    }
+
+    fn content_type(attachment: &EmailAttachment) -> EmailServiceResult<ContentType> {
+        ContentType::parse(&attachment.mime_type())
+            .map_err(|e| AppError::EmailSendingFailed(format!("{}: {}", attachment.filename, e)))
+    }
+
+    /// The MIME message of `email`, the `bcc` recipients are only part of its envelope
+    fn message(&self, email: &Email) -> EmailServiceResult<Message> {
+        let mut builder = Message::builder()
+            .from(Self::mailbox(&email.sender(&self.config))?)
+            .subject(email.subject.clone());
+        for to in &email.to {
+            builder = builder.to(Self::mailbox(to)?);
+        }
+        for cc in &email.cc {
+            builder = builder.cc(Self::mailbox(cc)?);
+        }
+        for bcc in &email.bcc {
+            builder = builder.bcc(Self::mailbox(bcc)?);
+        }
+        for reply_to in &email.reply_to {
+            builder = builder.reply_to(Self::mailbox(reply_to)?);
+        }
+        if !email.tags.is_empty() {
+            let tags: Vec<String> = email
+                .tags
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            builder = builder.raw_header(Self::header("X-Tags", &tags.join(", "))?);
+        }
+        for (name, value) in &email.headers {
+            builder = builder.raw_header(Self::header(name, value)?);
+        }
+
+        if email.content.text_content.is_none() && email.attachments.is_empty() {
+            builder
+                .header(ContentType::TEXT_HTML)
+                .body(email.content.html_content.clone())
+        } else {
+            builder.multipart(Self::body(email)?)
+        }
+        .map_err(|e| AppError::EmailSendingFailed(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl EmailService for EmailServiceSmtpImpl {
+    async fn send(&self, email: &Email) -> EmailServiceResult<()> {
+        let message = self.message(email)?;
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| AppError::EmailSendingFailed(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn templates(&self) -> &TemplateRegistry {
+        &self.templates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::core::{EmailContent, I18n};
+
+    fn address(address: &str) -> EmailAddress {
+        EmailAddress::new(address).unwrap()
+    }
+
+    fn service(port: u16) -> EmailServiceSmtpImpl {
+        let config = Config::new(true).unwrap();
+        let smtp = SmtpConfig {
+            port: Some(port),
+            tls: SmtpTls::None,
+            timeout: 5,
+            ..SmtpConfig::new("127.0.0.1")
+        };
+        let templates = Arc::new(TemplateRegistry::new(
+            &config.app_name,
+            Arc::new(I18n::new("en")),
+        ));
+        EmailServiceSmtpImpl::new(&config, &smtp, templates).unwrap()
+    }
+
+    fn email() -> Email {
+        let content = EmailContent::new(
+            "Welcome".to_string(),
+            "<p>Hello <img src=\"cid:logo\"></p>".to_string(),
+        )
+        .with_text("Hello".to_string());
+        Email::new(address("jane@example.com"), content)
+            .with_cc(address("john@example.com"))
+            .with_bcc(address("audit@example.com"))
+            .with_tag("category", "activation")
+            .with_header("List-Unsubscribe", "<https://example.com/unsubscribe>")
+            .with_attachment(
+                EmailAttachment::new("logo.png", vec![1, 2, 3]).with_content_id("logo"),
+            )
+            .with_attachment(EmailAttachment::new("invoice.pdf", b"%PDF".to_vec()))
+    }
+
+    /// Accepts one email and returns the commands received and its content
+    async fn smtp_sink(listener: TcpListener) -> (Vec<String>, String) {
+        let (socket, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = socket.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut commands = vec![];
+        let mut data = String::new();
+
+        writer.write_all(b"220 sink ESMTP\r\n").await.unwrap();
+        while let Some(line) = lines.next_line().await.unwrap() {
+            if line != "DATA" {
+                commands.push(line);
+                writer.write_all(b"250 OK\r\n").await.unwrap();
+                continue;
+            }
+
+            writer.write_all(b"354 Go ahead\r\n").await.unwrap();
+            while let Some(line) = lines.next_line().await.unwrap() {
+                if line == "." {
+                    break;
+                }
+                data.push_str(&line);
+                data.push('\n');
+            }
+            writer.write_all(b"250 Queued\r\n").await.unwrap();
+            break;
+        }
+
+        (commands, data)
+    }
+
+    // The pooled transport needs a runtime
+    #[tokio::test]
+    async fn builds_the_message() {
+        let message = service(25).message(&email()).unwrap();
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+
+        assert!(formatted.contains("From: younss_core_server <no-reply@mail.com>"));
+        assert!(formatted.contains("To: jane@example.com"));
+        assert!(formatted.contains("Cc: john@example.com"));
+        assert!(!formatted.contains("audit@example.com"));
+        assert!(formatted.contains("Subject: Welcome"));
+        assert!(formatted.contains("X-Tags: category=activation"));
+        assert!(formatted.contains("List-Unsubscribe: <https://example.com/unsubscribe>"));
+        assert!(formatted.contains("multipart/mixed"));
+        assert!(formatted.contains("multipart/related"));
+        assert!(formatted.contains("multipart/alternative"));
+        assert!(formatted.contains("Content-ID: <logo>"));
+        assert!(formatted.contains("filename=\"invoice.pdf\""));
+
+        let envelope = message.envelope();
+        assert_eq!(envelope.to().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_headers() {
+        let email = email().with_header("Bad Header", "value");
+        assert!(service(25).message(&email).is_err());
+    }
+
+    #[tokio::test]
+    async fn sends_through_an_smtp_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let sink = tokio::spawn(smtp_sink(listener));
+
+        service(port).send(&email()).await.unwrap();
+        let (commands, data) = sink.await.unwrap();
+
+        assert!(commands.contains(&"MAIL FROM:<no-reply@mail.com>".to_string()));
+        for recipient in ["jane", "john", "audit"] {
+            let rcpt = format!("RCPT TO:<{}@example.com>", recipient);
+            assert!(commands.contains(&rcpt), "{:?}", commands);
+        }
+        assert!(data.contains("Subject: Welcome"));
+        assert!(data.contains("filename=\"invoice.pdf\""));
+    }
+}
@@ -3,14 +3,35 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::core::{EmailAddress, EmailContent};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::core::{Config, EmailAddress, EmailAttachment, EmailContent};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Email {
     pub from: Option<EmailAddress>,
-    pub to: EmailAddress,
+    // Emails queued before several recipients were supported hold a single address
+    #[serde(deserialize_with = "one_or_many")]
+    pub to: Vec<EmailAddress>,
+    #[serde(default)]
+    pub cc: Vec<EmailAddress>,
+    #[serde(default)]
+    pub bcc: Vec<EmailAddress>,
+    #[serde(default)]
+    pub reply_to: Vec<EmailAddress>,
     pub subject: String,
     pub content: EmailContent,
+    #[serde(default)]
+    pub attachments: Vec<EmailAttachment>,
+    /// Labels for the provider analytics, e.g. `category: activation`. Sent as the `X-Tags`
+    /// header over SMTP
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
+    /// Extra headers, e.g. `List-Unsubscribe`
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
 }
 
 impl Email {
@@ -20,9 +41,80 @@ impl Email {
             // accounts then we can set this each time. Otherwise it will be pulled from the config
             // env
             from: None,
-            to,
+            to: vec![to],
+            cc: vec![],
+            bcc: vec![],
+            reply_to: vec![],
             subject: content.subject.clone(),
             content,
+            attachments: vec![],
+            tags: BTreeMap::new(),
+            headers: BTreeMap::new(),
         }
     }
+
+    /// `from`, the app address (`email_from` named `app_name`) by default
+    pub fn sender(&self, config: &Config) -> EmailAddress {
+        self.from.clone().unwrap_or_else(|| EmailAddress {
+            address: config.email_from.clone(),
+            full_name: Some(config.app_name.clone()),
+        })
+    }
+
+    pub fn with_from(mut self, from: EmailAddress) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    pub fn with_to(mut self, to: EmailAddress) -> Self {
+        self.to.push(to);
+        self
+    }
+
+    pub fn with_cc(mut self, cc: EmailAddress) -> Self {
+        self.cc.push(cc);
+        self
+    }
+
+    pub fn with_bcc(mut self, bcc: EmailAddress) -> Self {
+        self.bcc.push(bcc);
+        self
+    }
+
+    pub fn with_reply_to(mut self, reply_to: EmailAddress) -> Self {
+        self.reply_to.push(reply_to);
+        self
+    }
+
+    pub fn with_attachment(mut self, attachment: EmailAttachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    pub fn with_tag(mut self, name: &str, value: &str) -> Self {
+        self.tags.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<EmailAddress>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(EmailAddress),
+        Many(Vec<EmailAddress>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(address) => vec![address],
+        OneOrMany::Many(addresses) => addresses,
+    })
 }
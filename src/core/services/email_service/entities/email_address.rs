@@ -3,11 +3,15 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
 use crate::core::{AppError, Validators};
 
 pub type EmailAddressResult<T> = Result<T, AppError>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmailAddress {
     pub address: String,
     pub full_name: Option<String>,
@@ -21,4 +25,19 @@ impl EmailAddress {
             full_name: None,
         })
     }
+
+    pub fn with_name(mut self, full_name: &str) -> Self {
+        self.full_name = Some(full_name.to_string());
+        self
+    }
+}
+
+/// `Jane Doe <jane@mail.com>`, or the bare address without a name
+impl fmt::Display for EmailAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.full_name {
+            Some(name) => write!(f, "{} <{}>", name, self.address),
+            None => write!(f, "{}", self.address),
+        }
+    }
 }
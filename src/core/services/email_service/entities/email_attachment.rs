@@ -0,0 +1,62 @@
+// Copyright (c) 2026 Dr. Younss Ait Mou. All rights reserved.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{AppError, StorageService};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailAttachment {
+    pub filename: String,
+    /// e.g. `application/pdf`, guessed from `filename` when `None`
+    pub content_type: Option<String>,
+    #[serde(with = "serde_bytes")]
+    pub content: Vec<u8>,
+    /// Makes the attachment inline, referenced from the HTML body as `<img src="cid:logo">`
+    #[serde(default)]
+    pub content_id: Option<String>,
+}
+
+impl EmailAttachment {
+    pub fn new(filename: &str, content: Vec<u8>) -> Self {
+        Self {
+            filename: filename.to_string(),
+            content_type: None,
+            content,
+            content_id: None,
+        }
+    }
+
+    /// Attaches a file of the storage, e.g. an invoice kept under `documents`
+    pub async fn from_storage(
+        storage: &StorageService,
+        entity_id: &str,
+        entity_dir: &str,
+        filename: &str,
+    ) -> Result<Self, AppError> {
+        let content = storage
+            .read_entity_file(entity_id, entity_dir, filename)
+            .await?;
+        Ok(Self::new(filename, content))
+    }
+
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    pub fn with_content_id(mut self, content_id: &str) -> Self {
+        self.content_id = Some(content_id.to_string());
+        self
+    }
+
+    pub fn mime_type(&self) -> String {
+        self.content_type.clone().unwrap_or_else(|| {
+            mime_guess::from_path(&self.filename)
+                .first_or_octet_stream()
+                .to_string()
+        })
+    }
+}
@@ -3,10 +3,15 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmailContent {
     pub subject: String,
     pub html_content: String,
+    /// Plain text alternative of `html_content`
+    #[serde(default)]
+    pub text_content: Option<String>,
 }
 
 impl EmailContent {
@@ -14,6 +19,12 @@ impl EmailContent {
         Self {
             subject,
             html_content: html,
+            text_content: None,
         }
     }
+
+    pub fn with_text(mut self, text: String) -> Self {
+        self.text_content = Some(text);
+        self
+    }
 }
@@ -4,5 +4,8 @@ pub use email_address::*;
 mod email_content;
 pub use email_content::*;
 
+mod email_attachment;
+pub use email_attachment::*;
+
 mod email;
 pub use email::*;
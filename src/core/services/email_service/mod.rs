@@ -1,8 +1,17 @@
 mod entities;
 pub use entities::*;
 
+mod email_config;
+pub use email_config::*;
+
 mod email_service;
 pub use email_service::*;
 
 mod email_service_resend_impl;
 pub use email_service_resend_impl::*;
+
+mod email_service_smtp_impl;
+pub use email_service_smtp_impl::*;
+
+mod email_service_capture_impl;
+pub use email_service_capture_impl::*;
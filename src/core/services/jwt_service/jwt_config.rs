@@ -0,0 +1,79 @@
+// Copyright (c) 2026 Dr. Younss Ait Mou. All rights reserved.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use chrono::{DateTime, Utc};
+use jsonwebtoken::Algorithm;
+use serde::Deserialize;
+
+/// How the access tokens are signed
+#[derive(Debug, Clone, Default)]
+pub struct JwtSigningConfig {
+    pub algorithm: JwtAlgorithm,
+    /// PEM file of the private key, required by the asymmetric algorithms. RSA keys may be PKCS#1
+    /// or PKCS#8, EC and Ed25519 keys must be PKCS#8
+    pub private_key_path: Option<String>,
+    /// `kid` header of the tokens, defaults to the RFC 7638 thumbprint of the public key
+    pub key_id: Option<String>,
+    /// Former signing keys, still verifying the tokens they signed until their window closes
+    pub retired_keys: Vec<JwtRetiredKeyConfig>,
+}
+
+/// A key that no longer signs tokens. Once the active key is replaced, the previous one is retired
+/// here so that the sessions it signed survive the rotation
+#[derive(Debug, Clone, Deserialize)]
+pub struct JwtRetiredKeyConfig {
+    pub algorithm: JwtAlgorithm,
+    /// The former `jwt_secret`, for HS256 keys
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// PEM file of an asymmetric key, its public key is enough
+    #[serde(default)]
+    pub key_path: Option<String>,
+    /// Defaults to the RFC 7638 thumbprint of the public key, like `JwtSigningConfig::key_id`
+    #[serde(default)]
+    pub key_id: Option<String>,
+    /// The key is ignored (and unpublished) from then on. Set it to the rotation time plus the
+    /// lifetime of the access tokens (`jwt_expiration`)
+    pub verify_until: DateTime<Utc>,
+}
+
+/// The algorithms the access tokens may be signed with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum JwtAlgorithm {
+    /// HMAC with `Config::jwt_secret`. Whoever verifies the tokens can also mint them
+    #[default]
+    HS256,
+    /// RSASSA-PKCS1-v1_5 with SHA-256
+    RS256,
+    /// ECDSA on the P-256 curve
+    ES256,
+    /// Ed25519
+    EdDSA,
+}
+
+impl JwtAlgorithm {
+    pub fn from_env_value(value: &str) -> Option<Self> {
+        match value.to_uppercase().as_str() {
+            "HS256" => Some(Self::HS256),
+            "RS256" => Some(Self::RS256),
+            "ES256" => Some(Self::ES256),
+            "EDDSA" | "ED25519" => Some(Self::EdDSA),
+            _ => None,
+        }
+    }
+
+    pub fn is_asymmetric(&self) -> bool {
+        *self != Self::HS256
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            Self::HS256 => Algorithm::HS256,
+            Self::RS256 => Algorithm::RS256,
+            Self::ES256 => Algorithm::ES256,
+            Self::EdDSA => Algorithm::EdDSA,
+        }
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright (c) 2026 Dr. Younss Ait Mou. All rights reserved.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use chrono::Utc;
+use jsonwebtoken::{jwk::JwkSet, Header};
+
+use crate::core::{
+    jwt_service::{JwtKey, JwtRetiredKeyConfig},
+    AppError, Config,
+};
+
+/// The key signing the new access tokens, plus the retired keys still verifying the tokens they
+/// signed. Rotating the active key therefore does not log anybody out
+pub struct JwtKeyRing {
+    active: JwtKey,
+    retired: Vec<JwtKey>,
+}
+
+impl JwtKeyRing {
+    pub fn new(active: JwtKey, retired: Vec<JwtKey>) -> Self {
+        Self { active, retired }
+    }
+
+    /// Loads the keys of `Config::jwt_signing`
+    pub fn from_config(config: &Config) -> Result<Self, AppError> {
+        let signing = &config.jwt_signing;
+        let active = if signing.algorithm.is_asymmetric() {
+            let path = signing.private_key_path.as_ref().ok_or_else(|| {
+                AppError::JwtConfigurationError(format!(
+                    "{:?} requires the path of a PEM private key",
+                    signing.algorithm
+                ))
+            })?;
+            JwtKey::from_private_pem(
+                signing.algorithm,
+                &Self::read(path)?,
+                signing.key_id.clone(),
+            )?
+        } else {
+            JwtKey::from_secret(&config.jwt_secret, signing.key_id.clone())
+        };
+
+        let retired = signing
+            .retired_keys
+            .iter()
+            .map(Self::retired_key)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new(active, retired))
+    }
+
+    /// The key new tokens are signed with
+    pub fn active(&self) -> &JwtKey {
+        &self.active
+    }
+
+    /// The keys that may have signed a token with this header, the active one first. Tokens
+    /// without `kid` (e.g. issued before the keys had one) are tried against every key of their
+    /// algorithm that has no `kid` either
+    pub fn verification_keys<'a>(&'a self, header: &'a Header) -> impl Iterator<Item = &'a JwtKey> {
+        let now = Utc::now();
+
+        std::iter::once(&self.active)
+            .chain(self.retired.iter().filter(move |key| key.verifies_at(now)))
+            .filter(|key| key.algorithm.algorithm() == header.alg && key.kid == header.kid)
+    }
+
+    /// The public keys of the active and the retired asymmetric keys
+    pub fn jwks(&self) -> JwkSet {
+        let now = Utc::now();
+
+        JwkSet {
+            keys: std::iter::once(&self.active)
+                .chain(self.retired.iter().filter(|key| key.verifies_at(now)))
+                .filter_map(|key| key.jwk().cloned())
+                .collect(),
+        }
+    }
+
+    /* ··································································· [ Helper functions ] */
+    fn retired_key(config: &JwtRetiredKeyConfig) -> Result<JwtKey, AppError> {
+        let key = match (&config.secret, &config.key_path) {
+            (Some(secret), _) if !config.algorithm.is_asymmetric() => {
+                JwtKey::from_secret(secret, config.key_id.clone())
+            }
+            (_, Some(path)) if config.algorithm.is_asymmetric() => {
+                JwtKey::from_pem(config.algorithm, &Self::read(path)?, config.key_id.clone())?
+            }
+            _ => {
+                return Err(AppError::JwtConfigurationError(format!(
+                    "A retired {:?} key requires a {}",
+                    config.algorithm,
+                    match config.algorithm.is_asymmetric() {
+                        true => "key_path",
+                        false => "secret",
+                    }
+                )))
+            }
+        };
+
+        Ok(key.with_verify_until(config.verify_until))
+    }
+
+    fn read(path: &str) -> Result<Vec<u8>, AppError> {
+        std::fs::read(path)
+            .map_err(|e| AppError::JwtConfigurationError(format!("Could not read {path}: {e}")))
+    }
+}
@@ -0,0 +1,145 @@
+use jsonwebtoken::{
+    decode, decode_header, encode, errors::ErrorKind, jwk::JwkSet, Header, Validation,
+};
+use warp::http::HeaderMap;
+
+use crate::{
+    api::auth::domain::entities::{Claims, User},
+    core::{jwt_service::JwtKeyRing, AppError, Config},
+};
+
+pub struct JwtService {
+    config: Config,
+    keys: JwtKeyRing,
+}
+
+impl JwtService {
+    pub fn new(config: Config) -> Result<Self, AppError> {
+        let keys = JwtKeyRing::from_config(&config)?;
+        Ok(Self { config, keys })
+    }
+
+    /// The public keys verifying the access tokens, served at `/.well-known/jwks.json`. Only the
+    /// asymmetric keys are published, the retired ones until their window closes
+    pub fn jwks(&self) -> JwkSet {
+        self.keys.jwks()
+    }
+
+    pub fn generate_jwt(&self, user: &User) -> Result<String, AppError> {
+        self.encode_jwt(user, None)
+    }
+
+    /// An access token of the session `session_id` (see `Session`), rejected once it is revoked
+    pub fn generate_session_jwt(&self, user: &User, session_id: &str) -> Result<String, AppError> {
+        self.encode_jwt(user, Some(session_id.to_string()))
+    }
+
+    /// Decodes and validates a JWT token
+    ///
+    /// # Arguments
+    /// * `token` - The JWT token string to decode
+    ///
+    /// # Returns
+    /// * `Ok(Claims)` - The decoded claims if the token is valid
+    /// * `Err(AppError)` - If the token is invalid or expired
+    pub fn decode_jwt(&self, headers: &HeaderMap) -> Result<Claims, AppError> {
+        let token = self
+            .get_jwt_from_auth_header(headers)
+            .ok_or(AppError::InvalidInput(
+                "An error has occurred. Please log out and back in, then try again.".to_string(),
+            ))?;
+        // The token is checked against the keys that may have signed it only: the active key or
+        // a retired one, with the same `kid` and algorithm
+        let header = decode_header(&token)
+            .map_err(|e| AppError::Unauthorized(format!("Invalid access token! {:?}", e)))?;
+
+        // Set up validation requirements
+        let mut validation = Validation::new(header.alg);
+        validation.validate_nbf = true;
+        // Tokens of another deployment sharing our key are rejected, as well as the ones lacking
+        // the expected issuer or audience
+        if let Some(issuer) = &self.config.jwt_issuer {
+            validation.set_issuer(&[issuer]);
+            validation.required_spec_claims.insert("iss".to_string());
+        }
+        if let Some(audience) = &self.config.jwt_audience {
+            validation.set_audience(&[audience]);
+            validation.required_spec_claims.insert("aud".to_string());
+        }
+
+        let mut result = None;
+        for key in self.keys.verification_keys(&header) {
+            let decoded = decode::<Claims>(&token, key.decoding_key(), &validation);
+            let wrong_key = matches!(&decoded, Err(e) if *e.kind() == ErrorKind::InvalidSignature);
+            result = Some(decoded);
+            // Several kid-less HS256 secrets may be in use during a rotation
+            if !wrong_key {
+                break;
+            }
+        }
+        let result = result.ok_or_else(|| {
+            AppError::Unauthorized("Invalid access token! Unknown signing key".to_string())
+        })?;
+
+        match result {
+            Ok(token_data) => Ok(token_data.claims),
+            Err(e) => match e.kind() {
+                ErrorKind::ExpiredSignature => {
+                    // we need this error to be more specific so that at the front end we can
+                    // properly handle it!
+                    Err(AppError::ExpiredAccessToken)
+                }
+                _ => Err(AppError::Unauthorized(format!(
+                    "Invalid access token! {:?}",
+                    e
+                ))),
+            },
+        }
+    }
+
+    // Signs the claims of the user, see `generate_jwt`
+    fn encode_jwt(&self, user: &User, session_id: Option<String>) -> Result<String, AppError> {
+        let expiration = chrono::Utc::now()
+            .checked_add_signed(chrono::Duration::seconds(self.config.jwt_expiration))
+            .expect("valid timestamp")
+            .timestamp();
+
+        let mut claims = Claims::new(
+            user.id.to_string(),
+            user.role.clone(),
+            user.first_name.to_string(),
+            user.last_name.to_string(),
+            user.email.to_string(),
+            expiration as usize,
+        );
+        claims.locale = user.locale.clone();
+        claims.iss = self.config.jwt_issuer.clone();
+        claims.aud = self.config.jwt_audience.clone();
+        claims.sid = session_id;
+
+        let key = self.keys.active();
+        let mut header = Header::new(key.algorithm.algorithm());
+        header.kid = key.kid.clone();
+        let encoding_key = key
+            .encoding_key()
+            .ok_or_else(|| AppError::JWTError("The active key cannot sign the jwt!".to_string()))?;
+
+        let token = match encode(&header, &claims, encoding_key) {
+            Ok(result) => result,
+            Err(_) => {
+                return Err(AppError::JWTError("Could not create the jwt!".to_string()));
+            }
+        };
+
+        Ok(token)
+    }
+
+    // A helper function to get the jwt from the Authorization header. This handles only Bearer JWT,
+    // the API keys are resolved by the `Authenticator`
+    pub(crate) fn get_jwt_from_auth_header(&self, headers: &HeaderMap) -> Option<String> {
+        let jwt_str = headers.get("Authorization")?.to_str().ok()?;
+        jwt_str
+            .strip_prefix("Bearer ")
+            .map(|token| token.to_string())
+    }
+}
@@ -0,0 +1,11 @@
+mod jwt_config;
+pub use jwt_config::*;
+
+mod jwt_key;
+pub use jwt_key::*;
+
+mod jwt_key_ring;
+pub use jwt_key_ring::*;
+
+mod jwt_service;
+pub use jwt_service::*;
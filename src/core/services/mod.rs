@@ -3,5 +3,8 @@ pub use email_service::*;
 pub mod jwt_service;
 pub mod rand_token_service;
 
+mod totp_service;
+pub use totp_service::*;
+
 mod storage_service;
 pub use storage_service::*;
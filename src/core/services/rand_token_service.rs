@@ -2,7 +2,9 @@
 // - account verification
 // - reset password
 // - etc.
-use rand::Rng;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::{rngs::OsRng, Rng, RngCore};
+use sha2::{Digest, Sha256};
 // pub trait TokenService: Send + Sync {
 //     fn generate_token(&self) -> String;
 // }
@@ -15,4 +17,29 @@ impl TokenService {
         let token: u32 = rng.gen_range(0..1000000); // Generate a number between 0 and 999999
         format!("{:06}", token) // Format as a 6-digit string, padding with leading zeros
     }
+
+    /// An unguessable opaque token (256 bits, base64url) for credentials such as the refresh
+    /// tokens. Only its `hash_token` should be stored
+    pub fn generate_secret_token() -> String {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// SHA-256 (hex) of a secret token. The tokens are random and long enough for a fast unsalted
+    /// hash, which can be looked up
+    pub fn hash_token(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+
+    /// Whether `token` hashes to `hash`, compared in constant time
+    pub fn verify_token(token: &str, hash: &str) -> bool {
+        let token_hash = Self::hash_token(token);
+        token_hash.len() == hash.len()
+            && token_hash
+                .bytes()
+                .zip(hash.bytes())
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
 }
@@ -0,0 +1,258 @@
+use std::io::SeekFrom;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{stream, StreamExt};
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
+use uuid::Uuid;
+
+use crate::core::{AppError, ByteStream, FileStat, StorageBackend, StorageResult};
+use std::os::unix::fs::PermissionsExt;
+
+/// Stores files on the local filesystem under `base_path`.
+///
+/// This is the default backend. Only suitable when a single server instance handles uploads (or
+/// when `base_path` is a shared mount).
+///
+/// Files are written to a hidden temporary file next to their destination and renamed once
+/// complete, so readers never see a partially written file.
+pub struct FileSystemStorageBackend {
+    base_path: PathBuf,
+}
+
+impl FileSystemStorageBackend {
+    pub fn new(base_path: &str) -> Self {
+        // Ensure base directory exists
+        let _ = std::fs::create_dir_all(base_path);
+        Self {
+            base_path: PathBuf::from(base_path),
+        }
+    }
+
+    fn path_of(&self, key: &str) -> PathBuf {
+        self.base_path.join(key)
+    }
+
+    /// Create a directory (and its parents) if it doesn't exist
+    async fn create_directory(&self, dir: &Path) -> StorageResult<()> {
+        if dir.exists() {
+            return Ok(());
+        }
+
+        fs::create_dir_all(dir)
+            .await
+            .map_err(|e| AppError::UserDirectoryCreationFailed(e.to_string()))?;
+
+        // Set permissions (Unix specific)
+        #[cfg(unix)]
+        {
+            let mut perms = fs::metadata(dir).await?.permissions();
+            perms.set_mode(0o755); // rwxr-xr-x
+            fs::set_permissions(dir, perms).await?;
+        }
+
+        Ok(())
+    }
+
+    /// `.{filename}.{uuid}.tmp` in the same directory, so the final rename is atomic
+    fn temp_path_of(file_path: &Path) -> PathBuf {
+        let filename = file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        file_path.with_file_name(format!(
+            ".{}.{}{}",
+            filename,
+            Uuid::new_v4(),
+            TEMP_EXTENSION
+        ))
+    }
+
+    fn is_temp_file(filename: &str) -> bool {
+        filename.starts_with('.') && filename.ends_with(TEMP_EXTENSION)
+    }
+
+    async fn write_stream(path: &Path, mut stream: ByteStream) -> StorageResult<u64> {
+        let mut file = fs::File::create(path).await?;
+        let mut size = 0;
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk).await?;
+            size += chunk.len() as u64;
+        }
+
+        file.flush().await?;
+        file.sync_all().await?;
+
+        // Set file permissions (Unix specific)
+        #[cfg(unix)]
+        {
+            let mut perms = file.metadata().await?.permissions();
+            perms.set_mode(0o644); // rw-r--r--
+            fs::set_permissions(path, perms).await?;
+        }
+
+        Ok(size)
+    }
+}
+
+const TEMP_EXTENSION: &str = ".tmp";
+
+#[async_trait]
+impl StorageBackend for FileSystemStorageBackend {
+    async fn save(&self, key: &str, content: &[u8]) -> StorageResult<()> {
+        let chunk = Bytes::copy_from_slice(content);
+        self.save_stream(key, Box::pin(stream::once(async { Ok(chunk) })))
+            .await?;
+        Ok(())
+    }
+
+    async fn save_stream(&self, key: &str, stream: ByteStream) -> StorageResult<u64> {
+        let file_path = self.path_of(key);
+
+        if let Some(parent) = file_path.parent() {
+            self.create_directory(parent).await?;
+        }
+
+        let temp_path = Self::temp_path_of(&file_path);
+        let written = match Self::write_stream(&temp_path, stream).await {
+            Ok(size) => fs::rename(&temp_path, &file_path)
+                .await
+                .map(|_| size)
+                .map_err(AppError::Io),
+            Err(err) => Err(err),
+        };
+
+        if written.is_err() {
+            let _ = fs::remove_file(&temp_path).await;
+        }
+        written
+    }
+
+    async fn read(&self, key: &str) -> StorageResult<Vec<u8>> {
+        let file_path = self.path_of(key);
+        if !file_path.is_file() {
+            return Err(AppError::FileNotFound(key.to_string()));
+        }
+
+        Ok(fs::read(&file_path).await?)
+    }
+
+    async fn stream(&self, key: &str) -> StorageResult<ByteStream> {
+        let file_path = self.path_of(key);
+        if !file_path.is_file() {
+            return Err(AppError::FileNotFound(key.to_string()));
+        }
+
+        let file = fs::File::open(&file_path).await?;
+        let stream = ReaderStream::new(file).map(|chunk| chunk.map_err(AppError::Io));
+
+        Ok(Box::pin(stream))
+    }
+
+    async fn stream_range(
+        &self,
+        key: &str,
+        range: RangeInclusive<u64>,
+    ) -> StorageResult<ByteStream> {
+        let file_path = self.path_of(key);
+        if !file_path.is_file() {
+            return Err(AppError::FileNotFound(key.to_string()));
+        }
+
+        let mut file = fs::File::open(&file_path).await?;
+        file.seek(SeekFrom::Start(*range.start())).await?;
+
+        let length = range.end() - range.start() + 1;
+        let stream = ReaderStream::new(file.take(length)).map(|chunk| chunk.map_err(AppError::Io));
+
+        Ok(Box::pin(stream))
+    }
+
+    async fn delete(&self, key: &str) -> StorageResult<()> {
+        let file_path = self.path_of(key);
+        if !file_path.is_file() {
+            return Err(AppError::FileNotFound(key.to_string()));
+        }
+
+        Ok(fs::remove_file(&file_path).await?)
+    }
+
+    async fn delete_dir(&self, prefix: &str) -> StorageResult<()> {
+        let dir = self.path_of(prefix);
+
+        if !dir.is_dir() {
+            return Err(AppError::UserDirectoryNotFound(format!(
+                "User directory does not exist: {:?}",
+                dir
+            )));
+        }
+
+        fs::remove_dir_all(&dir)
+            .await
+            .map_err(|e| AppError::UserDirectoryDeletionFailed(e.to_string()))
+    }
+
+    async fn list(&self, prefix: &str) -> StorageResult<Vec<String>> {
+        let dir = self.path_of(prefix);
+
+        if !dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut entries = fs::read_dir(&dir).await?;
+        let mut files = Vec::new();
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.is_file() {
+                if let Some(filename) = path.file_name() {
+                    let filename = filename.to_string_lossy().to_string();
+                    // Uploads in progress
+                    if !Self::is_temp_file(&filename) {
+                        files.push(filename);
+                    }
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
+    async fn list_dirs(&self, prefix: &str) -> StorageResult<Vec<String>> {
+        let dir = self.path_of(prefix);
+
+        if !dir.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let mut entries = fs::read_dir(&dir).await?;
+        let mut dirs = Vec::new();
+
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_dir() {
+                dirs.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+
+        Ok(dirs)
+    }
+
+    async fn stat(&self, key: &str) -> StorageResult<Option<FileStat>> {
+        let file_path = self.path_of(key);
+        if !file_path.is_file() {
+            return Ok(None);
+        }
+
+        let metadata = fs::metadata(&file_path).await?;
+        Ok(Some(FileStat {
+            size: metadata.len(),
+            modified_at: metadata.modified().ok().map(Into::into),
+        }))
+    }
+}
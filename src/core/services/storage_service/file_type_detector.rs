@@ -0,0 +1,45 @@
+/// Checks that the content of a file is what its extension claims, from its magic bytes.
+///
+/// Formats without a signature (plain text, csv, json...) can't be identified. They are accepted
+/// as long as their content isn't recognized as something else (e.g. an executable renamed to
+/// `notes.txt`).
+pub struct FileTypeDetector;
+
+/// Bytes needed at the start of a file to identify it
+pub const FILE_TYPE_HEAD_SIZE: usize = 8192;
+
+/// Formats stored as a zip archive, they may not be told apart from a plain zip
+const ZIP_CONTAINERS: [&str; 9] = [
+    "docx", "xlsx", "pptx", "odt", "ods", "odp", "epub", "jar", "apk",
+];
+
+impl FileTypeDetector {
+    /// MIME type detected from the first bytes of a file (see `FILE_TYPE_HEAD_SIZE`)
+    pub fn detect(head: &[u8]) -> Option<&'static str> {
+        infer::get(head).map(|kind| kind.mime_type())
+    }
+
+    /// Whether a file starting with `head` is a valid `extension` file
+    pub fn matches_extension(extension: &str, head: &[u8]) -> bool {
+        let extension = extension.to_lowercase();
+        let expected_mimes: Vec<String> = mime_guess::from_ext(&extension)
+            .iter()
+            .map(|mime| mime.essence_str().to_string())
+            .collect();
+
+        match infer::get(head) {
+            Some(kind) => {
+                kind.extension() == extension
+                    || expected_mimes.iter().any(|mime| mime == kind.mime_type())
+                    || (kind.extension() == "zip" && ZIP_CONTAINERS.contains(&extension.as_str()))
+            }
+            // Nothing recognized: only fine if this kind of file has no signature anyway
+            None => {
+                !infer::is_supported(&extension)
+                    && !expected_mimes
+                        .iter()
+                        .any(|mime| infer::is_mime_supported(mime))
+            }
+        }
+    }
+}
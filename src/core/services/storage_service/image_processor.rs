@@ -0,0 +1,184 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use image::{
+    codecs::jpeg::JpegEncoder, imageops::FilterType, metadata::Orientation, DynamicImage,
+    ImageDecoder, ImageFormat, ImageReader,
+};
+
+use crate::core::{
+    AppError, ImageProcessingConfig, ImageVariantConfig, ImageVariantFormat, StorageResult,
+};
+
+/// An image ready to be stored
+#[derive(Debug)]
+pub struct ProcessedImage {
+    /// The original re-encoded without its metadata
+    pub content: Vec<u8>,
+    /// `(file name, content)` of every configured variant
+    pub variants: Vec<(String, Vec<u8>)>,
+}
+
+/// Validates, cleans and derives the images saved by the `StorageService`.
+///
+/// Images are decoded whatever their extension claims, so anything that is not really an image
+/// is rejected. Encoders of the `image` crate never write metadata, re-encoding is what strips
+/// EXIF and GPS data. The EXIF orientation is applied beforehand so pictures keep their rotation.
+///
+/// Decoding is CPU bound, call `process` from a blocking task.
+pub struct ImageProcessor {
+    config: ImageProcessingConfig,
+}
+
+impl ImageProcessor {
+    pub fn new(config: ImageProcessingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Whether saving `filename` goes through `process`
+    pub fn handles(&self, filename: &str) -> bool {
+        self.config.enabled && Self::format_of(filename).is_some()
+    }
+
+    /// `{stem}.{variant}.{extension}`, e.g. `photo.thumbnail.jpg` for `photo.jpg`
+    pub fn variant_filename(filename: &str, variant: &ImageVariantConfig) -> String {
+        let path = Path::new(filename);
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let extension = match variant.format {
+            Some(format) => Self::extension_of(format).to_string(),
+            None => path
+                .extension()
+                .map(|e| e.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        };
+
+        format!("{}.{}.{}", stem, variant.name, extension)
+    }
+
+    /// File names of the variants generated for `filename` with the current configuration
+    pub fn variant_filenames(&self, filename: &str) -> Vec<String> {
+        if !self.handles(filename) {
+            return vec![];
+        }
+
+        self.config
+            .variants
+            .iter()
+            .map(|variant| Self::variant_filename(filename, variant))
+            .collect()
+    }
+
+    /// Returns `None` for files that are not images (or when processing is disabled)
+    pub fn process(&self, filename: &str, content: &[u8]) -> StorageResult<Option<ProcessedImage>> {
+        let Some(format) = Self::format_of(filename).filter(|_| self.config.enabled) else {
+            return Ok(None);
+        };
+
+        let image = Self::decode(filename, content)?;
+
+        // Re-encoding a GIF would only keep its first frame, and GIFs don't carry EXIF data
+        let content = match format {
+            ImageFormat::Gif => content.to_vec(),
+            _ => self.encode(&image, format)?,
+        };
+
+        let mut variants = Vec::with_capacity(self.config.variants.len());
+        for variant in &self.config.variants {
+            let resized = Self::fit(&image, variant.max_width, variant.max_height);
+            let variant_format = variant.format.map(Self::image_format).unwrap_or(format);
+
+            variants.push((
+                Self::variant_filename(filename, variant),
+                self.encode(&resized, variant_format)?,
+            ));
+        }
+
+        Ok(Some(ProcessedImage { content, variants }))
+    }
+
+    /* ····································································· [ Helper functions ] */
+    /// The format is sniffed from the content, the extension is not trusted
+    fn decode(filename: &str, content: &[u8]) -> StorageResult<DynamicImage> {
+        let invalid = || {
+            let extension = Path::new(filename)
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            AppError::InvalidFileType(vec![extension])
+        };
+
+        let mut decoder = ImageReader::new(Cursor::new(content))
+            .with_guessed_format()?
+            .into_decoder()
+            .map_err(|_| invalid())?;
+        let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+
+        let mut image = DynamicImage::from_decoder(decoder).map_err(|_| invalid())?;
+        image.apply_orientation(orientation);
+
+        Ok(image)
+    }
+
+    fn encode(&self, image: &DynamicImage, format: ImageFormat) -> StorageResult<Vec<u8>> {
+        let mut buffer = Cursor::new(Vec::new());
+
+        let encoded = match format {
+            // No alpha channel in JPEG
+            ImageFormat::Jpeg => {
+                let encoder = JpegEncoder::new_with_quality(&mut buffer, self.config.jpeg_quality);
+                DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(encoder)
+            }
+            ImageFormat::WebP | ImageFormat::Gif => {
+                DynamicImage::ImageRgba8(image.to_rgba8()).write_to(&mut buffer, format)
+            }
+            _ => image.write_to(&mut buffer, format),
+        };
+        encoded.map_err(|e| AppError::Other(format!("Could not encode image: {}", e)))?;
+
+        Ok(buffer.into_inner())
+    }
+
+    /// Scales down to fit the bounds keeping the aspect ratio, never up
+    fn fit(image: &DynamicImage, max_width: Option<u32>, max_height: Option<u32>) -> DynamicImage {
+        let max_width = max_width.unwrap_or(image.width());
+        let max_height = max_height.unwrap_or(image.height());
+
+        if image.width() <= max_width && image.height() <= max_height {
+            return image.clone();
+        }
+        image.resize(max_width, max_height, FilterType::CatmullRom)
+    }
+
+    fn format_of(filename: &str) -> Option<ImageFormat> {
+        let extension = Path::new(filename).extension()?.to_string_lossy();
+
+        match extension.to_lowercase().as_str() {
+            "jpg" | "jpeg" => Some(ImageFormat::Jpeg),
+            "png" => Some(ImageFormat::Png),
+            "gif" => Some(ImageFormat::Gif),
+            "webp" => Some(ImageFormat::WebP),
+            _ => None,
+        }
+    }
+
+    fn image_format(format: ImageVariantFormat) -> ImageFormat {
+        match format {
+            ImageVariantFormat::Jpeg => ImageFormat::Jpeg,
+            ImageVariantFormat::Png => ImageFormat::Png,
+            ImageVariantFormat::Gif => ImageFormat::Gif,
+            ImageVariantFormat::WebP => ImageFormat::WebP,
+        }
+    }
+
+    fn extension_of(format: ImageVariantFormat) -> &'static str {
+        match format {
+            ImageVariantFormat::Jpeg => "jpg",
+            ImageVariantFormat::Png => "png",
+            ImageVariantFormat::Gif => "gif",
+            ImageVariantFormat::WebP => "webp",
+        }
+    }
+}
@@ -1,5 +1,26 @@
 mod storage_config;
 pub use storage_config::*;
 
+mod storage_backend;
+pub use storage_backend::*;
+
+mod file_system_storage_backend;
+pub use file_system_storage_backend::*;
+
+mod s3_storage_backend;
+pub use s3_storage_backend::*;
+
+mod file_type_detector;
+pub use file_type_detector::*;
+
+mod image_processor;
+pub use image_processor::*;
+
+mod url_signer;
+pub use url_signer::*;
+
+mod storage_usage;
+pub use storage_usage::*;
+
 mod storage_service;
 pub use storage_service::*;
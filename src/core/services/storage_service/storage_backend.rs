@@ -0,0 +1,69 @@
+use std::ops::RangeInclusive;
+use std::pin::Pin;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::Stream;
+
+use crate::core::StorageResult;
+
+/// A stream of file chunks as returned by `StorageBackend::stream`
+pub type ByteStream = Pin<Box<dyn Stream<Item = StorageResult<Bytes>> + Send>>;
+
+/// Metadata of a stored file
+#[derive(Debug, Clone)]
+pub struct FileStat {
+    pub size: u64,
+    pub modified_at: Option<DateTime<Utc>>,
+}
+
+/// Where the `StorageService` physically keeps its files.
+///
+/// Files are addressed by keys shaped as `{entity_dir}/{entity_id}/{filename}`. A "directory" is
+/// a key prefix (e.g. `{entity_dir}/{entity_id}`) and has no existence of its own, which lets the
+/// same contract be implemented by a local filesystem and by an object store.
+///
+/// Keys are built and validated by the `StorageService`, implementations can trust them.
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    /// Write (or overwrite) a file
+    async fn save(&self, key: &str, content: &[u8]) -> StorageResult<()>;
+
+    /// Write (or overwrite) a file chunk by chunk and return its size.
+    ///
+    /// The first error yielded by `stream` aborts the write and is returned as is. A failed write
+    /// never leaves a partial file behind.
+    async fn save_stream(&self, key: &str, stream: ByteStream) -> StorageResult<u64>;
+
+    /// Read a whole file. Fails with `AppError::FileNotFound` if missing
+    async fn read(&self, key: &str) -> StorageResult<Vec<u8>>;
+
+    /// Read a file chunk by chunk. Fails with `AppError::FileNotFound` if missing
+    async fn stream(&self, key: &str) -> StorageResult<ByteStream>;
+
+    /// Read the bytes `range` (both ends included) of a file chunk by chunk. The range must fit
+    /// in the file. Fails with `AppError::FileNotFound` if missing
+    async fn stream_range(
+        &self,
+        key: &str,
+        range: RangeInclusive<u64>,
+    ) -> StorageResult<ByteStream>;
+
+    /// Delete a file. Fails with `AppError::FileNotFound` if missing
+    async fn delete(&self, key: &str) -> StorageResult<()>;
+
+    /// Delete every file under a directory prefix.
+    /// Fails with `AppError::UserDirectoryNotFound` if there is nothing to delete
+    async fn delete_dir(&self, prefix: &str) -> StorageResult<()>;
+
+    /// Names of the files stored directly under a directory prefix
+    async fn list(&self, prefix: &str) -> StorageResult<Vec<String>>;
+
+    /// Names of the directories directly under a directory prefix, `""` being the root
+    /// (e.g. the `entity_dir`s at the root and the entity ids under an `entity_dir`)
+    async fn list_dirs(&self, prefix: &str) -> StorageResult<Vec<String>>;
+
+    /// File metadata, `None` if the file does not exist
+    async fn stat(&self, key: &str) -> StorageResult<Option<FileStat>>;
+}
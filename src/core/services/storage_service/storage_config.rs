@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -5,6 +7,25 @@ pub struct StorageConfig {
     pub base_path: String,
     pub max_file_size: u64,              // in bytes
     pub allowed_extensions: Vec<String>, // e.g., ["jpg", "png", "jpeg", "gif"]
+    /// Per `entity_dir` rules, replacing `max_file_size` and `allowed_extensions` for that dir
+    #[serde(default)]
+    pub policies: HashMap<String, StoragePolicy>,
+    /// Bytes an entity may store across all `entity_dir`s, unlimited if `None`
+    #[serde(default)]
+    pub entity_quota: Option<u64>,
+    /// `entity_dir`s whose entity ids are user ids. They are deleted along with the user and
+    /// swept for orphans (see `DeleteOrphanStorageDirs`)
+    #[serde(default)]
+    pub user_dirs: Vec<String>,
+    /// Seconds between two orphan sweeps of the `user_dirs`, no sweep if `None`
+    #[serde(default)]
+    pub orphan_sweep_interval: Option<u64>,
+    #[serde(default)]
+    pub backend: StorageBackendConfig,
+    #[serde(default)]
+    pub image_processing: ImageProcessingConfig,
+    #[serde(default)]
+    pub signed_urls: SignedUrlConfig,
 }
 
 impl Default for StorageConfig {
@@ -19,6 +40,159 @@ impl Default for StorageConfig {
                 "png".to_string(),
                 "gif".to_string(),
             ],
+            policies: HashMap::new(),
+            entity_quota: None,
+            user_dirs: vec![],
+            orphan_sweep_interval: None,
+            backend: StorageBackendConfig::default(),
+            image_processing: ImageProcessingConfig::default(),
+            signed_urls: SignedUrlConfig::default(),
         }
     }
 }
+
+impl StorageConfig {
+    /// The policy of an `entity_dir`, the global limits if it has none
+    pub fn policy_for(&self, entity_dir: &str) -> StoragePolicy {
+        self.policies
+            .get(entity_dir)
+            .cloned()
+            .unwrap_or_else(|| self.default_policy())
+    }
+
+    /// `max_file_size` and `allowed_extensions` as a policy
+    pub fn default_policy(&self) -> StoragePolicy {
+        StoragePolicy {
+            max_file_size: self.max_file_size,
+            allowed_extensions: self.allowed_extensions.clone(),
+            quota: None,
+        }
+    }
+
+    /// The largest file accepted in any `entity_dir`
+    pub fn largest_max_file_size(&self) -> u64 {
+        self.policies
+            .values()
+            .map(|policy| policy.max_file_size)
+            .fold(self.max_file_size, u64::max)
+    }
+}
+
+/// What an `entity_dir` accepts, e.g. `avatars` takes images up to 2 MB and `documents` PDFs
+/// up to 20 MB.
+///
+/// Files must have one of the `allowed_extensions` and their content must be what the extension
+/// claims (see `FileTypeDetector`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct StoragePolicy {
+    /// In bytes
+    pub max_file_size: u64,
+    pub allowed_extensions: Vec<String>,
+    /// Bytes an entity may store in this dir (image variants included), unlimited if `None`
+    #[serde(default)]
+    pub quota: Option<u64>,
+}
+
+/// Selects the `StorageBackend` used by the `StorageService`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub enum StorageBackendConfig {
+    /// Files are stored on the local disk under `StorageConfig::base_path`
+    #[default]
+    FileSystem,
+    /// Files are stored in an S3 compatible object store shared by all server instances
+    S3(S3StorageConfig),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct S3StorageConfig {
+    /// e.g. `https://s3.eu-west-3.amazonaws.com` or `http://127.0.0.1:9000` for a local MinIO
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key: String,
+    pub secret_key: String,
+    /// Address the bucket as `{endpoint}/{bucket}` instead of `{bucket}.{endpoint}`
+    #[serde(default)]
+    pub path_style: bool,
+}
+
+/// Links to files that work without an `Authorization` header (see `UrlSigner`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignedUrlConfig {
+    /// HMAC key, links can't be signed while empty
+    pub key: String,
+    /// Seconds a link stays valid
+    pub ttl: i64,
+}
+
+impl Default for SignedUrlConfig {
+    fn default() -> Self {
+        Self {
+            key: String::new(),
+            ttl: 60 * 60, // 1 hour
+        }
+    }
+}
+
+/// What happens to images (files with a `jpg`, `jpeg`, `png`, `gif` or `webp` extension) when
+/// they are saved
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageProcessingConfig {
+    /// Decode images to reject files that are not what their extension says, and re-encode them
+    /// without their metadata (EXIF, GPS position, camera...). When disabled images are stored
+    /// as uploaded and no variant is generated.
+    pub enabled: bool,
+    /// Quality (1-100) of the JPEG files written
+    pub jpeg_quality: u8,
+    /// Derived images stored alongside the original
+    pub variants: Vec<ImageVariantConfig>,
+}
+
+impl Default for ImageProcessingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            jpeg_quality: 85,
+            variants: vec![
+                ImageVariantConfig {
+                    name: "thumbnail".to_string(),
+                    max_width: Some(200),
+                    max_height: Some(200),
+                    format: None,
+                },
+                ImageVariantConfig {
+                    name: "medium".to_string(),
+                    max_width: Some(800),
+                    max_height: Some(800),
+                    format: None,
+                },
+                ImageVariantConfig {
+                    name: "webp".to_string(),
+                    max_width: None,
+                    max_height: None,
+                    format: Some(ImageVariantFormat::WebP),
+                },
+            ],
+        }
+    }
+}
+
+/// A derived image, stored as `{original_stem}.{name}.{extension}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageVariantConfig {
+    pub name: String,
+    /// The image is scaled down to fit the bounds keeping its aspect ratio, never up
+    pub max_width: Option<u32>,
+    pub max_height: Option<u32>,
+    /// `None` keeps the format of the original
+    pub format: Option<ImageVariantFormat>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ImageVariantFormat {
+    Jpeg,
+    Png,
+    Gif,
+    /// Lossless WebP
+    WebP,
+}
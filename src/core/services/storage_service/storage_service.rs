@@ -1,28 +1,88 @@
+use bytes::Bytes;
+use chrono::{DateTime, Duration, Utc};
+use futures::{future, Stream, StreamExt};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
 use uuid::Uuid;
 
-use crate::core::{AppError, StorageConfig, Validators};
+use crate::core::{
+    AppError, ByteStream, EntityStorageUsage, FileStat, FileSystemStorageBackend, FileTypeDetector,
+    ImageProcessor, Quotas, S3StorageBackend, StorageBackend, StorageBackendConfig, StorageConfig,
+    StoragePolicy, StorageUsage, StorageUsageTracker, UrlSigner, UsageReservation, Validators,
+    FILE_TYPE_HEAD_SIZE,
+};
 use std::os::unix::fs::PermissionsExt;
 
 pub type StorageResult<T> = Result<T, AppError>;
 
+/// Characters escaped in a path segment, the unreserved ones (RFC 3986) are kept
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// A file written by `StorageService::save_entity_file_stream`
+#[derive(Debug, Clone)]
+pub struct SavedFile {
+    /// The generated safe file name
+    pub filename: String,
+    pub size: u64,
+    /// File names of the image variants stored alongside (see `ImageProcessingConfig`)
+    pub variants: Vec<String>,
+}
+
 pub struct StorageService {
     config: StorageConfig,
+    backend: Arc<dyn StorageBackend>,
+    image_processor: Arc<ImageProcessor>,
+    usage: Arc<StorageUsageTracker>,
+    url_signer: UrlSigner,
 }
 
 impl StorageService {
+    /// Create the service with the backend selected in `config.backend`
     pub fn new(config: StorageConfig) -> Self {
-        // Ensure base directory exists
-        let _ = std::fs::create_dir_all(&config.base_path);
-        Self { config }
+        let backend: Arc<dyn StorageBackend> = match &config.backend {
+            StorageBackendConfig::FileSystem => {
+                Arc::new(FileSystemStorageBackend::new(&config.base_path))
+            }
+            StorageBackendConfig::S3(s3_config) => {
+                Arc::new(S3StorageBackend::new(s3_config.clone()))
+            }
+        };
+        Self::with_backend(config, backend)
+    }
+
+    /// Create the service with a custom `StorageBackend` implementation
+    pub fn with_backend(config: StorageConfig, backend: Arc<dyn StorageBackend>) -> Self {
+        let image_processor = Arc::new(ImageProcessor::new(config.image_processing.clone()));
+        let url_signer = UrlSigner::new(&config.signed_urls.key);
+        Self {
+            config,
+            backend,
+            image_processor,
+            usage: Arc::new(StorageUsageTracker::new()),
+            url_signer,
+        }
     }
 
     pub fn with_default_config() -> Self {
         Self::new(StorageConfig::default())
     }
 
+    pub fn config(&self) -> &StorageConfig {
+        &self.config
+    }
+
+    pub fn backend(&self) -> Arc<dyn StorageBackend> {
+        Arc::clone(&self.backend)
+    }
+
     /// Get the full path for a entity's file. For a given file
     pub fn get_entity_file_path(
         &self,
@@ -57,7 +117,8 @@ impl StorageService {
     /// Create a entity's directory
     ///
     /// The directory is created only if it doesn't exist
-    /// This will be created under
+    /// This will be created under `base_path` on the local filesystem whatever the configured
+    /// backend. Saving a file creates its directory when needed, so this is rarely required.
     pub async fn create_entity_directory(
         &self,
         entity_id: &str,
@@ -74,15 +135,10 @@ impl StorageService {
             // Set permissions (Unix specific)
             #[cfg(unix)]
             {
-                let mut perms = fs::metadata(&user_dir)
-                    .await
-                    .map_err(|e| AppError::Io(e.into()))?
-                    .permissions();
+                let mut perms = fs::metadata(&user_dir).await?.permissions();
 
                 perms.set_mode(0o755); // rwxr-xr-x
-                fs::set_permissions(&user_dir, perms)
-                    .await
-                    .map_err(|e| AppError::Io(e.into()))?;
+                fs::set_permissions(&user_dir, perms).await?;
             }
         }
 
@@ -90,29 +146,63 @@ impl StorageService {
     }
 
     /// Validate file based on config rules
+    ///
+    /// Uses the global `max_file_size` and `allowed_extensions`, see `validate_entity_file` to
+    /// apply the policy of an `entity_dir`
     pub fn validate_file(&self, filename: &str, size: u64) -> StorageResult<()> {
+        Self::check_policy(&self.config.default_policy(), filename, size)
+    }
+
+    /// Validate file based on the policy of `entity_dir` (see `StorageConfig::policies`)
+    pub fn validate_entity_file(
+        &self,
+        entity_dir: &str,
+        filename: &str,
+        size: u64,
+    ) -> StorageResult<()> {
+        Self::check_policy(&self.config.policy_for(entity_dir), filename, size)
+    }
+
+    /// Validate that the first bytes of a file match its extension
+    ///
+    /// `head` is the start of the file, `FILE_TYPE_HEAD_SIZE` bytes are enough to identify it
+    pub fn validate_file_content(
+        &self,
+        entity_dir: &str,
+        filename: &str,
+        head: &[u8],
+    ) -> StorageResult<()> {
+        let extension = Path::new(filename)
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        if !FileTypeDetector::matches_extension(&extension, head) {
+            let policy = self.config.policy_for(entity_dir);
+            return Err(AppError::InvalidFileType(policy.allowed_extensions));
+        }
+
+        Ok(())
+    }
+
+    fn check_policy(policy: &StoragePolicy, filename: &str, size: u64) -> StorageResult<()> {
         // Check file size
-        if size > self.config.max_file_size {
-            return Err(AppError::FileTooLarge(size, self.config.max_file_size));
+        if size > policy.max_file_size {
+            return Err(AppError::FileTooLarge(size, policy.max_file_size));
         }
 
         // Check file extension
         if let Some(ext) = Path::new(filename).extension() {
             let ext_str = ext.to_string_lossy().to_lowercase();
-            if !self
-                .config
+            if !policy
                 .allowed_extensions
                 .iter()
                 .any(|e| e.to_lowercase() == ext_str)
             {
-                return Err(AppError::InvalidFileType(
-                    self.config.allowed_extensions.clone(),
-                ));
+                return Err(AppError::InvalidFileType(policy.allowed_extensions.clone()));
             }
         } else {
-            return Err(AppError::InvalidFileType(
-                self.config.allowed_extensions.clone(),
-            ));
+            return Err(AppError::InvalidFileType(policy.allowed_extensions.clone()));
         }
 
         Ok(())
@@ -125,8 +215,51 @@ impl StorageService {
         format!("{}_{}", uuid, clean_name)
     }
 
+    /// Build the backend key of an entity's file: `{entity_dir}/{entity_id}/{filename}`
+    ///
+    /// Rejects ids and file names that would escape the entity directory
+    pub fn get_entity_file_key(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+        filename: &str,
+    ) -> StorageResult<String> {
+        let dir_key = self.get_entity_directory_key(entity_id, entity_dir)?;
+        if !Self::is_safe_segment(filename) {
+            return Err(AppError::FileNotFound(filename.to_string()));
+        }
+
+        Ok(format!("{}/{}", dir_key, filename))
+    }
+
+    /// Build the backend key prefix of an entity's directory: `{entity_dir}/{entity_id}`
+    pub fn get_entity_directory_key(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+    ) -> StorageResult<String> {
+        let entity_dir = Validators::validate_dir_name(entity_dir)?;
+        if !Self::is_safe_segment(entity_dir) || !Self::is_safe_segment(entity_id) {
+            return Err(AppError::InvalidFolderName(format!(
+                "{}/{}",
+                entity_dir, entity_id
+            )));
+        }
+
+        Ok(format!("{}/{}", entity_dir, entity_id))
+    }
+
+    fn is_safe_segment(segment: &str) -> bool {
+        !segment.is_empty()
+            && segment != "."
+            && segment != ".."
+            && !segment.contains(['/', '\\', '\0'])
+    }
+
     /// Save a file for a entity
     ///
+    /// Images are validated, stripped of their metadata and their variants are stored alongside
+    /// (see `ImageProcessingConfig`).
     /// Returns the newly created safe file name
     pub async fn save_entity_file(
         &self,
@@ -136,41 +269,210 @@ impl StorageService {
         content: &[u8],
     ) -> StorageResult<String> {
         // Validate file
-        self.validate_file(original_filename, content.len() as u64)?;
+        self.validate_entity_file(entity_dir, original_filename, content.len() as u64)?;
+        let head = &content[..content.len().min(FILE_TYPE_HEAD_SIZE)];
+        self.validate_file_content(entity_dir, original_filename, head)?;
+        let reservation = self
+            .reserve(entity_id, entity_dir, content.len() as u64)
+            .await?;
 
         // Generate safe filename
         let safe_filename = Self::generate_safe_filename(original_filename);
 
-        // Ensure entity directory exists
-        let user_dir = self.create_entity_directory(entity_id, entity_dir).await?;
+        if self.image_processor.handles(original_filename) {
+            let saved = self
+                .save_image(
+                    entity_id,
+                    entity_dir,
+                    safe_filename,
+                    content.to_vec(),
+                    &reservation,
+                )
+                .await?;
+            return Ok(saved.filename);
+        }
 
-        // Create full file path
-        let file_path = user_dir.join(&safe_filename);
+        let key = self.get_entity_file_key(entity_id, entity_dir, &safe_filename)?;
+        self.backend.save(&key, content).await?;
+        let usage = StorageUsage::new(content.len() as u64, 1);
+        self.usage.add(entity_dir, entity_id, usage);
 
-        // Write file
-        let mut file = fs::File::create(&file_path)
-            .await
-            .map_err(|e| AppError::Io(e))?;
+        Ok(safe_filename)
+    }
+
+    /// Save a file for a entity without loading it in memory
+    ///
+    /// The extension and the first bytes are validated before anything is written and the
+    /// `max_file_size` of the `entity_dir` policy is enforced as the chunks come in: the upload is
+    /// aborted with `AppError::FileTooLarge` as soon as the limit is crossed and nothing is kept.
+    /// The quotas are enforced the same way with `AppError::StorageQuotaExceeded`.
+    ///
+    /// Images have to be decoded, they are gathered in memory (up to `max_file_size`) and go
+    /// through the same processing as in `save_entity_file`.
+    pub async fn save_entity_file_stream<S>(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+        original_filename: &str,
+        stream: S,
+    ) -> StorageResult<SavedFile>
+    where
+        S: Stream<Item = StorageResult<Bytes>> + Send + 'static,
+    {
+        // Validate file type, the size is checked while streaming
+        self.validate_entity_file(entity_dir, original_filename, 0)?;
 
-        file.write_all(content).await.map_err(|e| AppError::Io(e))?;
+        let safe_filename = Self::generate_safe_filename(original_filename);
+        let key = self.get_entity_file_key(entity_id, entity_dir, &safe_filename)?;
+
+        let max_size = self.config.policy_for(entity_dir).max_file_size;
+        let stream = Self::limit_size(stream, max_size, move |size| {
+            AppError::FileTooLarge(size, max_size)
+        });
+        let reservation = Arc::new(self.reserve(entity_id, entity_dir, 0).await?);
+        let stream = Self::hold_chunks(stream, Arc::clone(&reservation));
+        let mut stream = self
+            .validate_stream_content(entity_dir, original_filename, stream)
+            .await?;
+
+        if self.image_processor.handles(original_filename) {
+            let mut content = Vec::new();
+            while let Some(chunk) = stream.next().await {
+                content.extend_from_slice(&chunk?);
+            }
+            return self
+                .save_image(entity_id, entity_dir, safe_filename, content, &reservation)
+                .await;
+        }
 
-        file.flush().await.map_err(|e| AppError::Io(e))?;
+        let size = self.backend.save_stream(&key, stream).await?;
+        self.usage
+            .add(entity_dir, entity_id, StorageUsage::new(size, 1));
 
-        // Set file permissions (Unix specific)
-        #[cfg(unix)]
-        {
-            let mut perms = file
-                .metadata()
-                .await
-                .map_err(|e| AppError::Io(e))?
-                .permissions();
-            perms.set_mode(0o644); // rw-r--r--
-            tokio::fs::set_permissions(&file_path, perms)
-                .await
-                .map_err(|e| AppError::Io(e))?;
+        Ok(SavedFile {
+            filename: safe_filename,
+            size,
+            variants: vec![],
+        })
+    }
+
+    /// Stores a processed image and its variants, all or nothing. The `reservation` of the upload
+    /// is grown to hold the variants too
+    async fn save_image(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+        safe_filename: String,
+        content: Vec<u8>,
+        reservation: &UsageReservation,
+    ) -> StorageResult<SavedFile> {
+        let processor = Arc::clone(&self.image_processor);
+        let filename = safe_filename.clone();
+        let processed = tokio::task::spawn_blocking(move || processor.process(&filename, &content))
+            .await
+            .map_err(|e| AppError::InternalServer(format!("Image processing failed: {}", e)))??;
+
+        // Not an image after all (processing disabled in between)
+        let Some(processed) = processed else {
+            return Err(AppError::InternalServer(
+                "Image processing is disabled".to_string(),
+            ));
+        };
+
+        let mut written: Vec<String> = Vec::new();
+        let files = std::iter::once((safe_filename.clone(), processed.content))
+            .chain(processed.variants)
+            .collect::<Vec<_>>();
+        let size = files[0].1.len() as u64;
+
+        // Variants count in the quotas
+        let total = files.iter().map(|(_, content)| content.len() as u64).sum();
+        reservation.grow_to(total)?;
+
+        for (filename, content) in files {
+            let key = self.get_entity_file_key(entity_id, entity_dir, &filename)?;
+            if let Err(err) = self.backend.save(&key, &content).await {
+                for key in &written {
+                    let _ = self.backend.delete(key).await;
+                }
+                return Err(err);
+            }
+            written.push(key);
         }
+        let usage = StorageUsage::new(total, written.len() as u64);
+        self.usage.add(entity_dir, entity_id, usage);
+
+        let variants = self.image_processor.variant_filenames(&safe_filename);
+        Ok(SavedFile {
+            filename: safe_filename,
+            size,
+            variants,
+        })
+    }
 
-        Ok(safe_filename)
+    /// Validates the first bytes of the stream, returns a stream yielding the whole content
+    async fn validate_stream_content(
+        &self,
+        entity_dir: &str,
+        filename: &str,
+        mut stream: ByteStream,
+    ) -> StorageResult<ByteStream> {
+        let mut head = Vec::new();
+        while head.len() < FILE_TYPE_HEAD_SIZE {
+            match stream.next().await {
+                Some(chunk) => head.extend_from_slice(&chunk?),
+                None => break,
+            }
+        }
+
+        self.validate_file_content(entity_dir, filename, &head)?;
+
+        let head = futures::stream::once(future::ready(Ok(Bytes::from(head))));
+        Ok(Box::pin(head.chain(stream)))
+    }
+
+    /// Holds each chunk of the stream against the quotas before it goes through, fails the stream
+    /// with `AppError::StorageQuotaExceeded` once they would be crossed
+    fn hold_chunks<S>(stream: S, reservation: Arc<UsageReservation>) -> ByteStream
+    where
+        S: Stream<Item = StorageResult<Bytes>> + Send + 'static,
+    {
+        let held = stream.scan(false, move |failed, chunk| {
+            if *failed {
+                return future::ready(None);
+            }
+
+            let chunk = chunk.and_then(|bytes| reservation.grow(bytes.len() as u64).map(|_| bytes));
+            *failed = chunk.is_err();
+            future::ready(Some(chunk))
+        });
+
+        Box::pin(held)
+    }
+
+    /// Fails the stream with `exceeded(bytes so far)` once more than `max_size` bytes went through
+    fn limit_size<S, E>(stream: S, max_size: u64, exceeded: E) -> ByteStream
+    where
+        S: Stream<Item = StorageResult<Bytes>> + Send + 'static,
+        E: Fn(u64) -> AppError + Send + 'static,
+    {
+        let limited = stream.scan(0u64, move |total, chunk| {
+            // The limit was crossed by the previous chunk
+            if *total > max_size {
+                return future::ready(None);
+            }
+
+            let chunk = chunk.and_then(|bytes| {
+                *total += bytes.len() as u64;
+                match *total > max_size {
+                    true => Err(exceeded(*total)),
+                    false => Ok(bytes),
+                }
+            });
+            future::ready(Some(chunk))
+        });
+
+        Box::pin(limited)
     }
 
     /// Read one entity's file
@@ -183,46 +485,89 @@ impl StorageService {
         entity_dir: &str,
         filename: &str,
     ) -> StorageResult<Vec<u8>> {
-        let file_path = self.get_entity_file_path(entity_id, entity_dir, filename)?;
+        let key = self.get_entity_file_key(entity_id, entity_dir, filename)?;
 
-        // Security check: ensure file is within entity directory
-        let user_dir = self.get_entity_directory_path(entity_id, entity_dir)?;
-        if !file_path.starts_with(&user_dir) {
-            return Err(AppError::FileNotFound(filename.to_string()));
-        }
+        self.backend
+            .read(&key)
+            .await
+            .map_err(|e| Self::file_not_found_as(e, filename))
+    }
 
-        // Check if file exists
-        if !file_path.exists() {
-            return Err(AppError::FileNotFound(filename.to_string()));
-        }
+    /// Read one entity's file as a stream of chunks
+    pub async fn stream_entity_file(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+        filename: &str,
+    ) -> StorageResult<ByteStream> {
+        let key = self.get_entity_file_key(entity_id, entity_dir, filename)?;
+
+        self.backend
+            .stream(&key)
+            .await
+            .map_err(|e| Self::file_not_found_as(e, filename))
+    }
+
+    /// Read the bytes `range` (both ends included) of one entity's file as a stream of chunks
+    ///
+    /// The range must fit in the file, see `stat_entity_file` for its size
+    pub async fn stream_entity_file_range(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+        filename: &str,
+        range: RangeInclusive<u64>,
+    ) -> StorageResult<ByteStream> {
+        let key = self.get_entity_file_key(entity_id, entity_dir, filename)?;
 
-        fs::read(&file_path).await.map_err(|e| AppError::Io(e))
+        self.backend
+            .stream_range(&key, range)
+            .await
+            .map_err(|e| Self::file_not_found_as(e, filename))
+    }
+
+    /// Metadata of one entity's file, `None` if it does not exist
+    pub async fn stat_entity_file(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+        filename: &str,
+    ) -> StorageResult<Option<FileStat>> {
+        let key = self.get_entity_file_key(entity_id, entity_dir, filename)?;
+        self.backend.stat(&key).await
     }
 
     /// Delete one entity's file
     ///
-    /// Takes the file_name to be deleted
+    /// Takes the file_name to be deleted. The variants of an image are deleted along
     pub async fn delete_entity_file(
         &self,
         entity_id: &str,
         entity_dir: &str,
         filename: &str,
     ) -> StorageResult<()> {
-        let file_path = self.get_entity_file_path(entity_id, entity_dir, filename)?;
+        let key = self.get_entity_file_key(entity_id, entity_dir, filename)?;
+        // Sizes are only needed when the usage of the dir is already known
+        let tracked = self.usage.get(entity_dir, entity_id).is_some();
 
-        // Security check
-        let user_dir = self.get_entity_directory_path(entity_id, entity_dir)?;
-        if !file_path.starts_with(&user_dir) {
-            return Err(AppError::FileNotFound(filename.to_string()));
-        }
-
-        if !file_path.exists() {
-            return Err(AppError::FileNotFound(filename.to_string()));
+        let size = self.stored_size(&key, tracked).await;
+        self.backend
+            .delete(&key)
+            .await
+            .map_err(|e| Self::file_not_found_as(e, filename))?;
+        let mut released = StorageUsage::new(size, 1);
+
+        // Variants may not exist (e.g. saved before they were configured)
+        for variant in self.image_processor.variant_filenames(filename) {
+            let key = self.get_entity_file_key(entity_id, entity_dir, &variant)?;
+            let size = self.stored_size(&key, tracked).await;
+            if self.backend.delete(&key).await.is_ok() {
+                released.add(StorageUsage::new(size, 1));
+            }
         }
 
-        fs::remove_file(&file_path)
-            .await
-            .map_err(|e| AppError::Io(e))
+        self.usage.release(entity_dir, entity_id, released);
+        Ok(())
     }
 
     /// List all files in a entity's directory
@@ -233,26 +578,16 @@ impl StorageService {
         entity_id: &str,
         entity_dir: &str,
     ) -> StorageResult<Vec<String>> {
-        let user_dir = self.get_entity_directory_path(entity_id, entity_dir)?;
-
-        if !user_dir.exists() {
-            return Ok(vec![]);
-        }
-
-        let mut entries = fs::read_dir(&user_dir).await.map_err(|e| AppError::Io(e))?;
-
-        let mut files = Vec::new();
+        let dir_key = self.get_entity_directory_key(entity_id, entity_dir)?;
+        self.backend.list(&dir_key).await
+    }
 
-        while let Some(entry) = entries.next_entry().await.map_err(|e| AppError::Io(e))? {
-            let path = entry.path();
-            if path.is_file() {
-                if let Some(filename) = path.file_name() {
-                    files.push(filename.to_string_lossy().to_string());
-                }
-            }
+    /// Backends report missing files with their key, callers only know the file name
+    fn file_not_found_as(err: AppError, filename: &str) -> AppError {
+        match err {
+            AppError::FileNotFound(_) => AppError::FileNotFound(filename.to_string()),
+            _ => err,
         }
-
-        Ok(files)
     }
 
     /// Get the public URL/path for a file (for serving)
@@ -263,9 +598,59 @@ impl StorageService {
         format!("/uploads/{}/{}/{}", entity_dir, entity_id, filename)
     }
 
+    /// A link to a file that can be fetched without an `Authorization` header for
+    /// `signed_urls.ttl` seconds, e.g. to embed images in a mobile app
+    ///
+    /// `/uploads/signed/{dir}/{id}/{filename}?expires={timestamp}&signature={hmac}`
+    pub fn get_signed_path(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+        filename: &str,
+    ) -> StorageResult<String> {
+        let expires_at = Utc::now() + Duration::seconds(self.config.signed_urls.ttl);
+        self.get_signed_path_until(entity_id, entity_dir, filename, expires_at)
+    }
+
+    /// Same as `get_signed_path` with a custom expiry
+    pub fn get_signed_path_until(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+        filename: &str,
+        expires_at: DateTime<Utc>,
+    ) -> StorageResult<String> {
+        let key = self.get_entity_file_key(entity_id, entity_dir, filename)?;
+        let expires = expires_at.timestamp();
+        let signature = self.url_signer.sign(&key, expires)?;
+
+        Ok(format!(
+            "/uploads/signed/{}/{}/{}?expires={}&signature={}",
+            entity_dir,
+            entity_id,
+            utf8_percent_encode(filename, PATH_SEGMENT),
+            expires,
+            signature
+        ))
+    }
+
+    /// Checks a link built by `get_signed_path`.
+    /// Fails with `AppError::Forbidden` if it expired or was tampered with
+    pub fn verify_signed_path(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+        filename: &str,
+        expires: i64,
+        signature: &str,
+    ) -> StorageResult<()> {
+        let key = self.get_entity_file_key(entity_id, entity_dir, filename)?;
+        self.url_signer.verify(&key, expires, signature)
+    }
+
     /// Get the absolute filesystem path for a file
     ///
-    /// Should be used with care!
+    /// Should be used with care! Only meaningful with the `FileSystem` backend
     pub fn get_filesystem_path(
         &self,
         entity_id: &str,
@@ -282,26 +667,228 @@ impl StorageService {
         entity_id: &str,
         entity_dir: &str,
     ) -> StorageResult<()> {
-        let user_dir = self.get_entity_directory_path(entity_id, entity_dir)?;
+        let dir_key = self.get_entity_directory_key(entity_id, entity_dir)?;
+        self.backend.delete_dir(&dir_key).await?;
 
-        // Check if directory exists
-        if !user_dir.exists() {
-            return Err(AppError::UserDirectoryNotFound(format!(
-                "User directory does not exist: {:?}",
-                user_dir
-            )));
+        self.usage.clear(entity_dir, entity_id);
+        Ok(())
+    }
+
+    /// Delete the directories of an entity in each of `entity_dirs`, those it doesn't have are
+    /// skipped (e.g. deleting a user's `StorageConfig::user_dirs`)
+    pub async fn delete_entity_storage_dirs(
+        &self,
+        entity_id: &str,
+        entity_dirs: &[String],
+    ) -> StorageResult<()> {
+        for entity_dir in entity_dirs {
+            match self.delete_entity_storage_dir(entity_id, entity_dir).await {
+                Ok(()) | Err(AppError::UserDirectoryNotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(())
+    }
+
+    /* ································································· [ Usage and quotas ] */
+    /// What an entity stores in `entity_dir`, computed from the backend the first time
+    pub async fn entity_dir_usage(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+    ) -> StorageResult<StorageUsage> {
+        if let Some(usage) = self.usage.get(entity_dir, entity_id) {
+            return Ok(usage);
         }
 
-        // Security check: Ensure we're only deleting within our base users directory
-        let users_base_dir = Path::new(&self.config.base_path).join(entity_dir);
-        if !user_dir.starts_with(&users_base_dir) {
-            return Err(AppError::Other("Invalid entity directory path".to_string()));
+        let usage = self.compute_usage(entity_id, entity_dir).await?;
+        self.usage.set(entity_dir, entity_id, usage);
+        Ok(usage)
+    }
+
+    /// What an entity stores across every `entity_dir`
+    pub async fn entity_usage(&self, entity_id: &str) -> StorageResult<EntityStorageUsage> {
+        let mut usage = EntityStorageUsage::new(entity_id, self.config.entity_quota);
+
+        for entity_dir in self.backend.list_dirs("").await? {
+            // Not a dir created by this service
+            if self
+                .get_entity_directory_key(entity_id, &entity_dir)
+                .is_err()
+            {
+                continue;
+            }
+            let dir_usage = self.entity_dir_usage(entity_id, &entity_dir).await?;
+            usage.add_dir(&entity_dir, dir_usage);
         }
 
-        // Delete the entire entity directory and all contents
-        if let Err(e) = fs::remove_dir_all(&user_dir).await {
-            return Err(AppError::UserDirectoryDeletionFailed(e.to_string()));
+        Ok(usage)
+    }
+
+    /// The usage of every entity having files, largest first.
+    ///
+    /// Always computed from the backend, which also refreshes the counters (e.g. with the files
+    /// saved by another server instance sharing the same backend).
+    pub async fn usage_report(&self) -> StorageResult<Vec<EntityStorageUsage>> {
+        let mut entities: HashMap<String, EntityStorageUsage> = HashMap::new();
+
+        for entity_dir in self.backend.list_dirs("").await? {
+            for entity_id in self.backend.list_dirs(&entity_dir).await? {
+                let usage = match self.compute_usage(&entity_id, &entity_dir).await {
+                    Ok(usage) => usage,
+                    Err(AppError::InvalidFolderName(_)) => continue,
+                    Err(err) => return Err(err),
+                };
+                self.usage.set(&entity_dir, &entity_id, usage);
+
+                entities
+                    .entry(entity_id.clone())
+                    .or_insert_with(|| {
+                        EntityStorageUsage::new(&entity_id, self.config.entity_quota)
+                    })
+                    .add_dir(&entity_dir, usage);
+            }
         }
-        Ok(())
+
+        let mut report: Vec<EntityStorageUsage> = entities
+            .into_values()
+            .filter(|usage| usage.total.files > 0)
+            .collect();
+        report.sort_by(|a, b| {
+            b.total
+                .bytes
+                .cmp(&a.total.bytes)
+                .then_with(|| a.entity_id.cmp(&b.entity_id))
+        });
+
+        Ok(report)
+    }
+
+    /// Sums the sizes of the files stored under `{entity_dir}/{entity_id}`
+    async fn compute_usage(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+    ) -> StorageResult<StorageUsage> {
+        let dir_key = self.get_entity_directory_key(entity_id, entity_dir)?;
+        let mut usage = StorageUsage::default();
+
+        for filename in self.backend.list(&dir_key).await? {
+            let key = format!("{}/{}", dir_key, filename);
+            // Deleted in the meantime
+            if let Some(stat) = self.backend.stat(&key).await? {
+                usage.add(StorageUsage::new(stat.size, 1));
+            }
+        }
+
+        Ok(usage)
+    }
+
+    /// Holds `size` bytes against the quotas of an upload of the entity to `entity_dir` (see
+    /// `UsageReservation`), once the counters they are checked against are computed
+    async fn reserve(
+        &self,
+        entity_id: &str,
+        entity_dir: &str,
+        size: u64,
+    ) -> StorageResult<UsageReservation> {
+        let quotas = Quotas {
+            dir: self.config.policy_for(entity_dir).quota,
+            entity: self.config.entity_quota,
+        };
+        if quotas.dir.is_some() {
+            self.entity_dir_usage(entity_id, entity_dir).await?;
+        }
+        if quotas.entity.is_some() {
+            self.entity_usage(entity_id).await?;
+        }
+
+        let usage = Arc::clone(&self.usage);
+        let reservation = UsageReservation::new(usage, entity_dir, entity_id, quotas);
+        reservation.grow(size)?;
+        Ok(reservation)
+    }
+
+    /// Size of a stored file when `needed`, 0 otherwise or if it can't be known
+    async fn stored_size(&self, key: &str, needed: bool) -> u64 {
+        if !needed {
+            return 0;
+        }
+        match self.backend.stat(key).await {
+            Ok(Some(stat)) => stat.size,
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUOTA: u64 = 150;
+
+    // A service storing under a dir of its own, with an entity quota of `QUOTA` bytes
+    fn service(name: &str) -> (StorageService, PathBuf) {
+        let base_path =
+            std::env::temp_dir().join(format!("storage-service-{}-{}", name, std::process::id()));
+        let config = StorageConfig {
+            base_path: base_path.to_string_lossy().to_string(),
+            allowed_extensions: vec!["txt".to_string()],
+            entity_quota: Some(QUOTA),
+            ..Default::default()
+        };
+        (StorageService::new(config), base_path)
+    }
+
+    fn chunks(size: usize) -> impl Stream<Item = StorageResult<Bytes>> + Send + 'static {
+        let chunks = (0..size / 25).map(|_| Ok(Bytes::from(vec![b'a'; 25])));
+        futures::stream::iter(chunks)
+    }
+
+    #[tokio::test]
+    async fn concurrent_uploads_cannot_cross_the_quota() {
+        let (storage, base_path) = service("concurrent");
+        let content = vec![b'a'; 100];
+
+        let (first, second) = tokio::join!(
+            storage.save_entity_file("entity", "files", "first.txt", &content),
+            storage.save_entity_file("entity", "files", "second.txt", &content),
+        );
+        assert!(first.is_ok() != second.is_ok(), "{first:?} {second:?}");
+        let failed = first.err().or(second.err()).unwrap();
+        assert!(matches!(failed, AppError::StorageQuotaExceeded(200, QUOTA)));
+
+        // The failed upload gave its bytes back
+        let usage = storage.entity_usage("entity").await.unwrap();
+        assert_eq!(usage.total.bytes, 100);
+        let saved = storage
+            .save_entity_file("entity", "files", "third.txt", &content[..50])
+            .await;
+        assert!(saved.is_ok(), "{saved:?}");
+
+        fs::remove_dir_all(base_path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn concurrent_streamed_uploads_cannot_cross_the_quota() {
+        let (storage, base_path) = service("concurrent-streams");
+
+        let (first, second) = tokio::join!(
+            storage.save_entity_file_stream("entity", "files", "first.txt", chunks(100)),
+            storage.save_entity_file_stream("entity", "files", "second.txt", chunks(100)),
+        );
+        assert!(first.is_ok() != second.is_ok(), "{first:?} {second:?}");
+        let failed = first.as_ref().err().or(second.as_ref().err()).unwrap();
+        assert!(matches!(failed, AppError::StorageQuotaExceeded(_, QUOTA)));
+
+        let usage = storage.entity_usage("entity").await.unwrap();
+        assert_eq!(usage.total, StorageUsage::new(100, 1));
+        let saved = storage
+            .save_entity_file_stream("entity", "files", "third.txt", chunks(50))
+            .await;
+        assert!(saved.is_ok(), "{saved:?}");
+
+        fs::remove_dir_all(base_path).await.unwrap();
     }
 }
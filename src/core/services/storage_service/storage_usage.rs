@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::core::AppError;
+
+/// What an entity stores, in one `entity_dir` or overall
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct StorageUsage {
+    pub bytes: u64,
+    pub files: u64,
+}
+
+impl StorageUsage {
+    pub fn new(bytes: u64, files: u64) -> Self {
+        Self { bytes, files }
+    }
+
+    pub fn add(&mut self, other: StorageUsage) {
+        self.bytes += other.bytes;
+        self.files += other.files;
+    }
+
+    pub fn release(&mut self, other: StorageUsage) {
+        self.bytes = self.bytes.saturating_sub(other.bytes);
+        self.files = self.files.saturating_sub(other.files);
+    }
+}
+
+/// The usage of one entity broken down by `entity_dir`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EntityStorageUsage {
+    pub entity_id: String,
+    pub total: StorageUsage,
+    /// Only the dirs where the entity has files
+    pub dirs: HashMap<String, StorageUsage>,
+    /// `StorageConfig::entity_quota`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quota: Option<u64>,
+}
+
+impl EntityStorageUsage {
+    pub fn new(entity_id: &str, quota: Option<u64>) -> Self {
+        Self {
+            entity_id: entity_id.to_string(),
+            quota,
+            ..Default::default()
+        }
+    }
+
+    pub fn add_dir(&mut self, entity_dir: &str, usage: StorageUsage) {
+        if usage.files == 0 {
+            return;
+        }
+        self.total.add(usage);
+        self.dirs.insert(entity_dir.to_string(), usage);
+    }
+}
+
+/// In memory counters of the bytes stored by `(entity_dir, entity_id)`.
+///
+/// The backend stays the source of truth: an entry is computed from the stored files when it is
+/// needed (see `StorageService::entity_dir_usage`), then kept up to date by saves and deletes.
+/// Entries not computed yet are left alone, they will count the files once computed. Entries are
+/// computed again after `USAGE_MAX_AGE`, so that the files stored by the other instances sharing
+/// the backend are counted too.
+///
+/// The uploads in progress hold their bytes against the quotas (see `UsageReservation`), so that
+/// concurrent uploads cannot cross them together.
+#[derive(Default)]
+pub struct StorageUsageTracker {
+    usages: RwLock<HashMap<(String, String), (StorageUsage, Instant)>>,
+    reserved: Mutex<HashMap<(String, String), u64>>,
+}
+
+/// How long the counters computed from the backend are trusted
+pub const USAGE_MAX_AGE: Duration = Duration::from_secs(60);
+
+/// The quotas an upload to `entity_dir` counts against, see `StoragePolicy::quota` and
+/// `StorageConfig::entity_quota`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Quotas {
+    pub dir: Option<u64>,
+    pub entity: Option<u64>,
+}
+
+impl StorageUsageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The usage computed from the backend, unless it is older than `USAGE_MAX_AGE`
+    pub fn get(&self, entity_dir: &str, entity_id: &str) -> Option<StorageUsage> {
+        self.read()
+            .get(&Self::key(entity_dir, entity_id))
+            .filter(|(_, computed_at)| computed_at.elapsed() < USAGE_MAX_AGE)
+            .map(|(usage, _)| *usage)
+    }
+
+    /// Sets the usage computed from the backend
+    pub fn set(&self, entity_dir: &str, entity_id: &str, usage: StorageUsage) {
+        self.write()
+            .insert(Self::key(entity_dir, entity_id), (usage, Instant::now()));
+    }
+
+    pub fn add(&self, entity_dir: &str, entity_id: &str, usage: StorageUsage) {
+        if let Some((current, _)) = self.write().get_mut(&Self::key(entity_dir, entity_id)) {
+            current.add(usage);
+        }
+    }
+
+    pub fn release(&self, entity_dir: &str, entity_id: &str, usage: StorageUsage) {
+        if let Some((current, _)) = self.write().get_mut(&Self::key(entity_dir, entity_id)) {
+            current.release(usage);
+        }
+    }
+
+    /// The entity has nothing left in `entity_dir`
+    pub fn clear(&self, entity_dir: &str, entity_id: &str) {
+        self.set(entity_dir, entity_id, StorageUsage::default());
+    }
+
+    /// Holds `bytes` more for an upload of the entity to `entity_dir`, unless they would cross
+    /// one of the `quotas`. The stored bytes are those of the counters, which must have been
+    /// computed beforehand (of `entity_dir` for its quota, of every dir for the entity's one)
+    pub fn reserve(
+        &self,
+        entity_dir: &str,
+        entity_id: &str,
+        bytes: u64,
+        quotas: Quotas,
+    ) -> Result<(), AppError> {
+        let usages = self.read();
+        let mut reserved = self.reserved();
+
+        // The bytes stored and held by the entity, in `entity_dir` only or in every dir
+        let used = |dir: Option<&str>| -> u64 {
+            let counts =
+                |key: &(String, String)| key.1 == entity_id && dir.is_none_or(|dir| key.0 == dir);
+            let stored: u64 = usages
+                .iter()
+                .filter(|(key, _)| counts(key))
+                .map(|(_, (usage, _))| usage.bytes)
+                .sum();
+            let held: u64 = reserved
+                .iter()
+                .filter(|(key, _)| counts(key))
+                .map(|(_, bytes)| bytes)
+                .sum();
+            stored + held
+        };
+
+        for (quota, dir) in [(quotas.dir, Some(entity_dir)), (quotas.entity, None)] {
+            let Some(quota) = quota else {
+                continue;
+            };
+            let used = used(dir);
+            if used + bytes > quota {
+                return Err(AppError::StorageQuotaExceeded(used + bytes, quota));
+            }
+        }
+
+        *reserved
+            .entry(Self::key(entity_dir, entity_id))
+            .or_default() += bytes;
+        Ok(())
+    }
+
+    /// Gives back bytes held by `reserve`, once the upload is over whatever its outcome
+    pub fn unreserve(&self, entity_dir: &str, entity_id: &str, bytes: u64) {
+        let mut reserved = self.reserved();
+        let key = Self::key(entity_dir, entity_id);
+        if let Some(held) = reserved.get_mut(&key) {
+            *held = held.saturating_sub(bytes);
+            if *held == 0 {
+                reserved.remove(&key);
+            }
+        }
+    }
+
+    /* ····································································· [ Helper functions ] */
+    fn key(entity_dir: &str, entity_id: &str) -> (String, String) {
+        (entity_dir.to_string(), entity_id.to_string())
+    }
+
+    // The counters stay consistent even if a thread panicked while holding the lock
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<(String, String), (StorageUsage, Instant)>> {
+        self.usages.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<(String, String), (StorageUsage, Instant)>> {
+        self.usages.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn reserved(&self) -> MutexGuard<'_, HashMap<(String, String), u64>> {
+        self.reserved.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Bytes an upload in progress holds against the quotas (see `StorageUsageTracker::reserve`),
+/// given back when dropped, once the upload succeeded and its file is counted or failed
+pub struct UsageReservation {
+    tracker: Arc<StorageUsageTracker>,
+    entity_dir: String,
+    entity_id: String,
+    quotas: Quotas,
+    bytes: AtomicU64,
+}
+
+impl UsageReservation {
+    pub fn new(
+        tracker: Arc<StorageUsageTracker>,
+        entity_dir: &str,
+        entity_id: &str,
+        quotas: Quotas,
+    ) -> Self {
+        Self {
+            tracker,
+            entity_dir: entity_dir.to_string(),
+            entity_id: entity_id.to_string(),
+            quotas,
+            bytes: AtomicU64::new(0),
+        }
+    }
+
+    /// Holds `bytes` more, e.g. the next chunk of a stream
+    pub fn grow(&self, bytes: u64) -> Result<(), AppError> {
+        self.tracker
+            .reserve(&self.entity_dir, &self.entity_id, bytes, self.quotas)?;
+        self.bytes.fetch_add(bytes, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Holds `bytes` in all, e.g. once the variants of an image are known
+    pub fn grow_to(&self, bytes: u64) -> Result<(), AppError> {
+        match bytes.checked_sub(self.bytes.load(Ordering::SeqCst)) {
+            Some(more) if more > 0 => self.grow(more),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for UsageReservation {
+    fn drop(&mut self) {
+        let bytes = *self.bytes.get_mut();
+        self.tracker
+            .unreserve(&self.entity_dir, &self.entity_id, bytes);
+    }
+}
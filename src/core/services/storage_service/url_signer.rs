@@ -0,0 +1,142 @@
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use ring::hkdf;
+use sha2::Sha256;
+
+use crate::core::{AppError, StorageResult};
+
+type HmacSha256 = Hmac<Sha256>;
+
+// HKDF info of the keys derived by `UrlSigner::derive_key`
+const DERIVED_KEY_LABEL: &[u8] = b"core_server storage url signing";
+
+/// Signs file paths so they can be fetched without an `Authorization` header until they expire.
+///
+/// The signature is an HMAC-SHA256 of `{path}:{expires}` where `expires` is a unix timestamp,
+/// changing the path or pushing the expiry back invalidates it.
+pub struct UrlSigner {
+    key: Vec<u8>,
+}
+
+impl UrlSigner {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.as_bytes().to_vec(),
+        }
+    }
+
+    /// A signing key derived from another secret (e.g. `jwt_secret`) with HKDF-SHA256, so that the
+    /// links never sign with that secret itself
+    pub fn derive_key(secret: &str) -> String {
+        let mut key = [0u8; 32];
+        hkdf::Salt::new(hkdf::HKDF_SHA256, &[])
+            .extract(secret.as_bytes())
+            .expand(&[DERIVED_KEY_LABEL], hkdf::HKDF_SHA256)
+            .and_then(|okm| okm.fill(&mut key))
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+
+        hex::encode(key)
+    }
+
+    /// Hex encoded signature of `path` valid until `expires`
+    pub fn sign(&self, path: &str, expires: i64) -> StorageResult<String> {
+        Ok(hex::encode(
+            self.mac(path, expires)?.finalize().into_bytes(),
+        ))
+    }
+
+    /// Fails with `AppError::Forbidden` if the link expired or the signature doesn't match
+    pub fn verify(&self, path: &str, expires: i64, signature: &str) -> StorageResult<()> {
+        if expires < Utc::now().timestamp() {
+            return Err(AppError::Forbidden("This link has expired".to_string()));
+        }
+
+        let invalid = || AppError::Forbidden("Invalid link signature".to_string());
+        let signature = hex::decode(signature).map_err(|_| invalid())?;
+
+        // Constant time comparison
+        self.mac(path, expires)?
+            .verify_slice(&signature)
+            .map_err(|_| invalid())
+    }
+
+    fn mac(&self, path: &str, expires: i64) -> StorageResult<HmacSha256> {
+        if self.key.is_empty() {
+            return Err(AppError::InternalServer(
+                "No key is configured to sign file URLs".to_string(),
+            ));
+        }
+
+        let mut mac = HmacSha256::new_from_slice(&self.key)
+            .map_err(|e| AppError::InternalServer(format!("Invalid signing key: {}", e)))?;
+        mac.update(format!("{}:{}", path, expires).as_bytes());
+        Ok(mac)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATH: &str = "/storage/users/42/avatars/me.png";
+
+    fn in_an_hour() -> i64 {
+        Utc::now().timestamp() + 3600
+    }
+
+    fn is_forbidden(result: StorageResult<()>) -> bool {
+        matches!(result, Err(AppError::Forbidden(_)))
+    }
+
+    #[test]
+    fn accepts_valid_links() {
+        let signer = UrlSigner::new("key");
+        let expires = in_an_hour();
+        let signature = signer.sign(PATH, expires).unwrap();
+
+        assert!(signer.verify(PATH, expires, &signature).is_ok());
+    }
+
+    #[test]
+    fn rejects_expired_links() {
+        let signer = UrlSigner::new("key");
+        let expires = Utc::now().timestamp() - 1;
+        let signature = signer.sign(PATH, expires).unwrap();
+
+        assert!(is_forbidden(signer.verify(PATH, expires, &signature)));
+    }
+
+    #[test]
+    fn rejects_tampered_links() {
+        let signer = UrlSigner::new("key");
+        let expires = in_an_hour();
+        let signature = signer.sign(PATH, expires).unwrap();
+
+        let other_path = "/storage/users/43/avatars/me.png";
+        assert!(is_forbidden(signer.verify(other_path, expires, &signature)));
+        assert!(is_forbidden(signer.verify(
+            PATH,
+            expires + 3600,
+            &signature
+        )));
+        assert!(is_forbidden(signer.verify(PATH, expires, "not hex")));
+        assert!(is_forbidden(signer.verify(PATH, expires, &signature[2..])));
+        assert!(is_forbidden(
+            UrlSigner::new("other key").verify(PATH, expires, &signature)
+        ));
+    }
+
+    #[test]
+    fn needs_a_key() {
+        assert!(UrlSigner::new("").sign(PATH, in_an_hour()).is_err());
+    }
+
+    #[test]
+    fn derives_keys() {
+        let key = UrlSigner::derive_key("jwt secret");
+
+        assert_eq!(key, UrlSigner::derive_key("jwt secret"));
+        assert_ne!(key, UrlSigner::derive_key("other secret"));
+        assert_eq!(key.len(), 64);
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright (c) 2026 Dr. Younss Ait Mou. All rights reserved.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use chrono::{DateTime, Utc};
+use data_encoding::BASE32_NOPAD;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use rand::{rngs::OsRng, RngCore};
+use ring::hmac;
+
+use crate::core::rand_token_service::TokenService;
+
+// RFC 6238 defaults, the only parameters that every authenticator app supports
+const DIGITS: u32 = 6;
+const PERIOD_SECONDS: i64 = 30;
+// The codes of the previous and next time steps are accepted too, for the clocks out of sync
+const ALLOWED_SKEW_STEPS: i64 = 1;
+// 160 bits, as recommended by RFC 4226
+const SECRET_BYTES: usize = 20;
+// 40 bits, enough for codes that are only tried a few times per login
+const RECOVERY_CODE_BYTES: usize = 5;
+
+/// Time-based one-time passwords (RFC 6238) of the authenticator apps, the second factor of the
+/// users who enable it
+pub struct TotpService {
+    // Shown by the authenticator apps next to the codes, e.g. the app name
+    issuer: String,
+}
+
+impl TotpService {
+    pub fn new(issuer: &str) -> Self {
+        Self {
+            issuer: issuer.to_string(),
+        }
+    }
+
+    /// A new random secret, base32 encoded as the authenticator apps expect it
+    pub fn generate_secret() -> String {
+        let mut secret = [0u8; SECRET_BYTES];
+        OsRng.fill_bytes(&mut secret);
+        BASE32_NOPAD.encode(&secret)
+    }
+
+    /// The `otpauth://` URI the authenticator apps import, usually scanned as a QR code
+    pub fn provisioning_uri(&self, secret: &str, account: &str) -> String {
+        let label = format!("{}:{}", self.issuer, account);
+        format!(
+            "otpauth://totp/{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            utf8_percent_encode(&label, NON_ALPHANUMERIC),
+            secret,
+            utf8_percent_encode(&self.issuer, NON_ALPHANUMERIC),
+            DIGITS,
+            PERIOD_SECONDS
+        )
+    }
+
+    /// The time step `code` is valid for at `now`, if any. The steps up to `last_step` were used
+    /// already and are skipped, so that a code cannot be replayed
+    pub fn verify(
+        secret: &str,
+        code: &str,
+        now: DateTime<Utc>,
+        last_step: Option<i64>,
+    ) -> Option<i64> {
+        let key = BASE32_NOPAD.decode(secret.as_bytes()).ok()?;
+        let code = code.trim();
+        if code.len() != DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let code: u32 = code.parse().ok()?;
+
+        let current_step = now.timestamp() / PERIOD_SECONDS;
+        (current_step - ALLOWED_SKEW_STEPS..=current_step + ALLOWED_SKEW_STEPS)
+            .filter(|step| last_step.is_none_or(|last_step| *step > last_step))
+            .find(|step| Self::code_at(&key, *step) == code)
+    }
+
+    /// Single use codes standing in for the authenticator app once it is lost, e.g.
+    /// `4f7a2-9c0de`. Only their `hash_recovery_code` should be stored
+    pub fn generate_recovery_codes(count: usize) -> Vec<String> {
+        (0..count)
+            .map(|_| {
+                let mut bytes = [0u8; RECOVERY_CODE_BYTES];
+                OsRng.fill_bytes(&mut bytes);
+                let code = hex::encode(bytes);
+                format!("{}-{}", &code[..5], &code[5..])
+            })
+            .collect()
+    }
+
+    /// The hash of a recovery code, whether it is typed with its dash and in upper case or not
+    pub fn hash_recovery_code(code: &str) -> String {
+        let code: String = code
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        TokenService::hash_token(&code)
+    }
+
+    /* ····································································· [ Helper functions ] */
+    // The HOTP value (RFC 4226) of a time step
+    fn code_at(key: &[u8], step: i64) -> u32 {
+        let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, key);
+        let tag = hmac::sign(&key, &(step as u64).to_be_bytes());
+        let digest = tag.as_ref();
+
+        let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            digest[offset],
+            digest[offset + 1],
+            digest[offset + 2],
+            digest[offset + 3],
+        ]) & 0x7fff_ffff;
+
+        binary % 10u32.pow(DIGITS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The SHA1 secret of the test vectors of RFC 6238 (Appendix B), "12345678901234567890"
+    const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    fn at(timestamp: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(timestamp, 0).unwrap()
+    }
+
+    fn code(timestamp: i64) -> String {
+        let key = BASE32_NOPAD.decode(SECRET.as_bytes()).unwrap();
+        format!(
+            "{:06}",
+            TotpService::code_at(&key, timestamp / PERIOD_SECONDS)
+        )
+    }
+
+    #[test]
+    fn rfc_6238_test_vectors() {
+        // The 8-digit codes of the RFC, of which the 6-digit ones are the last digits
+        let vectors = [
+            (59, "94287082"),
+            (1111111109, "07081804"),
+            (1111111111, "14050471"),
+            (1234567890, "89005924"),
+            (2000000000, "69279037"),
+            (20000000000, "65353130"),
+        ];
+
+        for (timestamp, expected) in vectors {
+            assert_eq!(code(timestamp), expected[2..], "T = {timestamp}");
+        }
+    }
+
+    #[test]
+    fn verify_returns_the_time_step() {
+        let now = at(1111111111);
+        let step = 1111111111 / PERIOD_SECONDS;
+
+        assert_eq!(TotpService::verify(SECRET, "050471", now, None), Some(step));
+        // As typed in, with spaces around it
+        assert_eq!(
+            TotpService::verify(SECRET, " 050471 ", now, None),
+            Some(step)
+        );
+    }
+
+    #[test]
+    fn verify_allows_one_step_of_skew() {
+        let now = at(1111111111);
+
+        let previous = code(1111111111 - PERIOD_SECONDS);
+        let next = code(1111111111 + PERIOD_SECONDS);
+        let too_old = code(1111111111 - 2 * PERIOD_SECONDS);
+        assert!(TotpService::verify(SECRET, &previous, now, None).is_some());
+        assert!(TotpService::verify(SECRET, &next, now, None).is_some());
+        assert_eq!(TotpService::verify(SECRET, &too_old, now, None), None);
+    }
+
+    #[test]
+    fn verify_rejects_replayed_codes() {
+        let now = at(1111111111);
+        let step = TotpService::verify(SECRET, "050471", now, None).unwrap();
+
+        assert_eq!(TotpService::verify(SECRET, "050471", now, Some(step)), None);
+        let previous = code(1111111111 - PERIOD_SECONDS);
+        assert_eq!(
+            TotpService::verify(SECRET, &previous, now, Some(step)),
+            None
+        );
+        // A code of a later step is still accepted
+        let next = code(1111111111 + PERIOD_SECONDS);
+        assert_eq!(
+            TotpService::verify(SECRET, &next, now, Some(step)),
+            Some(step + 1)
+        );
+    }
+
+    #[test]
+    fn verify_rejects_wrong_codes() {
+        let now = at(1111111111);
+
+        assert_eq!(TotpService::verify(SECRET, "050472", now, None), None);
+        assert_eq!(TotpService::verify(SECRET, "14050471", now, None), None);
+        assert_eq!(TotpService::verify(SECRET, "05047a", now, None), None);
+        assert_eq!(
+            TotpService::verify("not base32!", "050471", now, None),
+            None
+        );
+    }
+}
@@ -1,3 +1,23 @@
+// Copyright (c) 2026 Dr. Younss Ait Mou. All rights reserved.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! The former email builders, kept for compatibility. The emails are now rendered by
+//! `TemplateRegistry`, which the `EmailService` send methods use.
 pub mod activate_account_email_template;
 pub mod password_reset_email_template;
 pub mod reset_pwd_token_sent_template;
+
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::core::{EmailContent, I18n, TemplateRegistry};
+
+// Renders a built-in email in the default locale
+fn render_built_in(name: &str, app_name: &str, context: Value) -> EmailContent {
+    TemplateRegistry::new(app_name, Arc::new(I18n::new("en")))
+        .render_email(name, context)
+        .expect("The built-in email templates render")
+}
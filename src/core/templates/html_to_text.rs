@@ -0,0 +1,87 @@
+// Copyright (c) 2026 Dr. Younss Ait Mou. All rights reserved.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+// Applied in order, see `html_to_text`
+static RULES: LazyLock<Vec<(Regex, &str)>> = LazyLock::new(|| {
+    [
+        (
+            r"(?is)<(head|style|script)\b[^>]*>.*?</(head|style|script)>",
+            "",
+        ),
+        (
+            r#"(?is)<a\b[^>]*?href\s*=\s*["']([^"']*)["'][^>]*>(.*?)</a>"#,
+            "$2 ($1)",
+        ),
+        (r"(?i)<br\s*/?>", "\n"),
+        (r"(?i)<li\b[^>]*>", "\n- "),
+        (
+            r"(?i)</?(p|div|h[1-6]|tr|table|ul|ol|blockquote|hr)\b[^>]*>",
+            "\n\n",
+        ),
+        (r"(?s)<[^>]*>", ""),
+    ]
+    .into_iter()
+    .map(|(pattern, replacement)| {
+        let regex = Regex::new(pattern).expect("valid html_to_text pattern");
+        (regex, replacement)
+    })
+    .collect()
+});
+
+static ENTITY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").expect("valid entity pattern")
+});
+
+/// A readable plain text version of an HTML email, used as its text alternative when no text
+/// template is registered.
+///
+/// Drops the `<head>`, `<style>` and `<script>` elements, keeps the link targets
+/// (`text (url)`), turns block elements into line breaks and list items into `- ` lines.
+pub fn html_to_text(html: &str) -> String {
+    let mut text = html.to_string();
+    for (regex, replacement) in RULES.iter() {
+        text = regex.replace_all(&text, *replacement).into_owned();
+    }
+    let text = decode_entities(&text);
+
+    // Collapse the indentation of the source and the blank lines left by nested blocks
+    let mut lines: Vec<String> = vec![];
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n").trim().to_string()
+}
+
+fn decode_entities(text: &str) -> String {
+    ENTITY
+        .replace_all(text, |caps: &regex::Captures| {
+            let entity = &caps[1];
+            let decoded = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+                    u32::from_str_radix(&entity[2..], 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                }
+                _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+                _ => None,
+            };
+            decoded.map_or_else(|| caps[0].to_string(), String::from)
+        })
+        .into_owned()
+}
@@ -1,3 +1,8 @@
 pub mod email_templates;
-
 pub use email_templates::*;
+
+mod html_to_text;
+pub use html_to_text::*;
+
+mod template_registry;
+pub use template_registry::*;
@@ -0,0 +1,247 @@
+// Copyright (c) 2026 Dr. Younss Ait Mou. All rights reserved.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::Path,
+    sync::{Arc, LazyLock, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use minijinja::{context, value::Kwargs, Environment, ErrorKind, State, Value};
+use serde::Serialize;
+
+use crate::core::{html_to_text, text_direction, AppError, EmailContent, I18n};
+
+/// The templates shipped with the crate, by name
+const BUILT_IN_TEMPLATES: [(&str, &str); 8] = [
+    (
+        "layouts/base.html",
+        include_str!("email_templates/layouts/base.html"),
+    ),
+    (
+        "partials/footer.html",
+        include_str!("email_templates/partials/footer.html"),
+    ),
+    (
+        "activation.subject.txt",
+        include_str!("email_templates/activation.subject.txt"),
+    ),
+    (
+        "activation.html",
+        include_str!("email_templates/activation.html"),
+    ),
+    (
+        "reset_password.subject.txt",
+        include_str!("email_templates/reset_password.subject.txt"),
+    ),
+    (
+        "reset_password.html",
+        include_str!("email_templates/reset_password.html"),
+    ),
+    (
+        "reset_password_confirmation.subject.txt",
+        include_str!("email_templates/reset_password_confirmation.subject.txt"),
+    ),
+    (
+        "reset_password_confirmation.html",
+        include_str!("email_templates/reset_password_confirmation.html"),
+    ),
+];
+
+/// The email templates, rendered with [minijinja](https://docs.rs/minijinja) (Jinja2 syntax).
+///
+/// An email named `name` is made of:
+/// - `{name}.subject.txt`: the subject
+/// - `{name}.html`: the HTML body
+/// - `{name}.txt` (optional): the plain text body, derived from the HTML body when missing
+///
+/// Any other template can be used as a layout (`{% extends "layouts/base.html" %}`) or a partial
+/// (`{% include "partials/footer.html" %}`). Variables of `.html` templates are HTML escaped.
+/// `app_name`, `locale` and `dir` (`ltr` or `rtl`) are available to every template.
+///
+/// The emails are localized by the `locale` variable of their context:
+/// - `{{ t("Hello {name},", name=first_name) }}` translates a message through the `I18n` catalogs
+/// - `{locale}/{name}.html` (e.g. `fr/activation.html`) replaces `{name}.html` for that locale,
+///   following the same fallback chain (`fr-CA`, `fr`, then the default locale)
+///
+/// The built-in emails (`ACTIVATION`, `RESET_PASSWORD`, `RESET_PASSWORD_CONFIRMATION`) and their
+/// layout can be overridden by registering templates with the same names, from strings or from a
+/// directory (see `Config::email_templates_dir`).
+pub struct TemplateRegistry {
+    env: RwLock<Environment<'static>>,
+    i18n: Arc<I18n>,
+}
+
+impl TemplateRegistry {
+    /// Variables: `email`, `token`
+    pub const ACTIVATION: &'static str = "activation";
+    /// Variables: `email`, `token`
+    pub const RESET_PASSWORD: &'static str = "reset_password";
+    /// Variables: `email`
+    pub const RESET_PASSWORD_CONFIRMATION: &'static str = "reset_password_confirmation";
+
+    pub fn new(app_name: &str, i18n: Arc<I18n>) -> Self {
+        let mut env = Environment::new();
+        env.add_global("app_name", app_name);
+
+        let catalogs = Arc::clone(&i18n);
+        env.add_function(
+            "t",
+            move |state: &State,
+                  msgid: String,
+                  kwargs: Kwargs|
+                  -> Result<String, minijinja::Error> {
+                let locale = state
+                    .lookup("locale")
+                    .and_then(|locale| locale.as_str().map(String::from))
+                    .unwrap_or_else(|| catalogs.default_locale().to_string());
+
+                let mut args = HashMap::new();
+                for name in kwargs.args() {
+                    args.insert(name.to_string(), kwargs.get::<Value>(name)?.to_string());
+                }
+                Ok(catalogs.format(&locale, &msgid, &args))
+            },
+        );
+
+        for (name, source) in BUILT_IN_TEMPLATES {
+            env.add_template(name, source)
+                .expect("The built-in email templates are valid");
+        }
+
+        Self {
+            env: RwLock::new(env),
+            i18n,
+        }
+    }
+
+    /// Registers (or overrides) a template, e.g. a layout or a partial
+    pub fn add_template(&self, name: &str, source: &str) -> Result<(), AppError> {
+        self.write()
+            .add_template_owned(name.to_string(), source.to_string())
+            .map_err(|e| Self::template_error(name, e))
+    }
+
+    /// Registers (or overrides) the templates of the `name` email
+    pub fn add_email_template(
+        &self,
+        name: &str,
+        subject: &str,
+        html: &str,
+        text: Option<&str>,
+    ) -> Result<(), AppError> {
+        self.add_template(&format!("{}.subject.txt", name), subject)?;
+        self.add_template(&format!("{}.html", name), html)?;
+        match text {
+            Some(text) => self.add_template(&format!("{}.txt", name), text),
+            None => {
+                self.write().remove_template(&format!("{}.txt", name));
+                Ok(())
+            }
+        }
+    }
+
+    /// Registers every file under `dir`, named after its path relative to `dir` (e.g.
+    /// `activation.html` or `layouts/base.html`)
+    pub fn load_dir(&self, dir: impl AsRef<Path>) -> Result<(), AppError> {
+        let dir = dir.as_ref();
+        let mut pending = vec![dir.to_path_buf()];
+
+        while let Some(current) = pending.pop() {
+            for entry in fs::read_dir(&current)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+
+                let name = path
+                    .strip_prefix(dir)
+                    .unwrap_or(&path)
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                self.add_template(&name, &fs::read_to_string(&path)?)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn has_email_template(&self, name: &str) -> bool {
+        self.read().get_template(&format!("{}.html", name)).is_ok()
+    }
+
+    /// Renders the `name` email with the given variables, in the language of their `locale`
+    /// (the default locale when missing)
+    pub fn render_email<C: Serialize>(
+        &self,
+        name: &str,
+        context: C,
+    ) -> Result<EmailContent, AppError> {
+        let env = self.read();
+        let context = Value::from_serialize(context);
+        let locale = context
+            .get_attr("locale")
+            .ok()
+            .and_then(|locale| locale.as_str().map(String::from))
+            .unwrap_or_else(|| self.i18n.default_locale().to_string());
+        let chain = self.i18n.fallback_chain(&locale);
+        let context = context! { locale => locale, dir => text_direction(&locale), ..context };
+
+        // The most specific localized version of the template, if any
+        let resolve = |file: &str| {
+            chain
+                .iter()
+                .map(|locale| format!("{}/{}", locale, file))
+                .find(|template| env.get_template(template).is_ok())
+                .unwrap_or_else(|| file.to_string())
+        };
+        let render = |template: &str| {
+            env.get_template(template)
+                .and_then(|template| template.render(&context))
+                .map_err(|e| Self::template_error(template, e))
+        };
+
+        let subject = render(&resolve(&format!("{}.subject.txt", name)))?;
+        let subject = subject.split_whitespace().collect::<Vec<_>>().join(" ");
+        let html = render(&resolve(&format!("{}.html", name)))?;
+
+        let text_template = resolve(&format!("{}.txt", name));
+        let text = match env.get_template(&text_template) {
+            Ok(_) => render(&text_template)?,
+            Err(e) if e.kind() == ErrorKind::TemplateNotFound => html_to_text(&html),
+            Err(e) => return Err(Self::template_error(&text_template, e)),
+        };
+
+        Ok(EmailContent::new(subject, html).with_text(text))
+    }
+
+    /// The built-in templates with the built-in message catalogs, shared by the whole process.
+    /// `app_name` is read from `APP_NAME`, falling back to the crate name
+    pub fn built_in() -> &'static TemplateRegistry {
+        static BUILT_IN: LazyLock<TemplateRegistry> = LazyLock::new(|| {
+            let app_name = env::var("APP_NAME").unwrap_or_else(|_| env!("CARGO_PKG_NAME").into());
+            TemplateRegistry::new(&app_name, Arc::new(I18n::new("en")))
+        });
+        &BUILT_IN
+    }
+
+    /* ····································································· [ Helper functions ] */
+    fn template_error(name: &str, e: minijinja::Error) -> AppError {
+        AppError::EmailTemplateError(format!("{}: {}", name, e))
+    }
+
+    // The templates stay usable even if a thread panicked while holding the lock
+    fn read(&self) -> RwLockReadGuard<'_, Environment<'static>> {
+        self.env.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Environment<'static>> {
+        self.env.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -1,3 +1,5 @@
+/// The messages are written in English, they are translated into the request locale when the
+/// response is sent (see `I18n` and `localize_responses`)
 pub struct MsgBuilder {}
 impl MsgBuilder {
     /// <...> created successfully!
@@ -5,7 +5,7 @@ use regex::Regex;
 
 use crate::{
     api::auth::domain::entities::Claims,
-    core::{AppError, MsgBuilder},
+    core::{normalize_locale, AppError, MsgBuilder},
 };
 pub type ValidatorsResult<T> = Result<T, AppError>;
 
@@ -63,6 +63,12 @@ impl Validators {
         Ok(email.to_string())
     }
 
+    /// Normalizes a language tag, e.g. `fr_ca` becomes `fr-CA`
+    pub fn validate_locale(locale: &str) -> ValidatorsResult<String> {
+        normalize_locale(locale)
+            .ok_or_else(|| AppError::InvalidInput(format!("Invalid locale {}", locale)))
+    }
+
     pub fn validate_web_link(link: &str) -> ValidatorsResult<String> {
         let pattern = r"^(https?://)?[a-zA-Z0-9][-a-zA-Z0-9.]*\.[a-z]{2,}(/.*)?$";
         let re = Regex::new(pattern).map_err(|_| {
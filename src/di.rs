@@ -1,70 +1,218 @@
 use std::sync::Arc;
 
 use mongodb::Database;
+use tokio::sync::Notify;
 
 use crate::{
     api::{
+        api_key::{api_key_di::ApiKeyDi, domain::usecases::*},
         auth::{
             auth_di::AuthDi,
             domain::usecases::{add_one_user::AddOneUser, user_delete_many::DeleteManyUsers, *},
         },
-        auth_token::{auth_token_di::AuthTokenDi, domain::usecases::*},
+        auth_token::{
+            auth_token_di::AuthTokenDi,
+            domain::{services::Authenticator, usecases::*},
+        },
+        email_outbox::{
+            domain::{services::OutboxEmailService, usecases::*},
+            email_outbox_di::EmailOutboxDi,
+        },
+        storage::{domain::usecases::*, storage_di::StorageDi},
     },
     core::{
-        datasource::mongo_db::mongodb_connection::MongoConnection, jwt_service::JwtService,
-        AppError, Config, EmailService, EmailServicerResendImpl, StorageConfig, StorageService,
+        datasource::{
+            mongo_db::mongodb_connection::MongoConnection, transaction::TransactionRunner,
+        },
+        jwt_service::JwtService,
+        AppError, Config, DatabaseBackend, EmailBackendConfig, EmailService,
+        EmailServiceCaptureImpl, EmailServiceSmtpImpl, EmailServicerResendImpl, I18n,
+        StorageConfig, StorageService, TemplateRegistry, TotpService, UrlSigner,
     },
     websocket::ClientsManager,
 };
 
 pub struct ServiceLocator {
-    //
+    // Never connected to when the in-memory database backend is selected
     pub db: Database,
 
     // Global services
+    transactions: Arc<TransactionRunner>,
     jwt_service: Arc<JwtService>,
+    totp_service: Arc<TotpService>,
+    authenticator: Arc<Authenticator>,
     auth_di: Arc<AuthDi>,
     auth_token_di: Arc<AuthTokenDi>,
+    api_key_di: Arc<ApiKeyDi>,
+    storage_di: Arc<StorageDi>,
+    email_outbox_di: Arc<EmailOutboxDi>,
     ws_clients: Arc<ClientsManager>,
     email_service: Arc<dyn EmailService>,
+    email_templates: Arc<TemplateRegistry>,
+    i18n: Arc<I18n>,
+    // `Some` when the emails are captured instead of sent
+    email_capture: Option<Arc<EmailServiceCaptureImpl>>,
     storage_service: Arc<StorageService>,
 }
 
 impl ServiceLocator {
     pub async fn new(config: Config) -> Result<Self, AppError> {
         //---[ DB Config ]--------------------------------------------------------------------------
-        let db = MongoConnection::new(config.clone()).await?.database;
+        let mongo = match config.database_backend {
+            DatabaseBackend::MongoDb => Some(MongoConnection::new(config.clone()).await?),
+            DatabaseBackend::InMemory => None,
+        };
+        let transactions = Arc::new(TransactionRunner::new(
+            mongo
+                .as_ref()
+                .filter(|mongo| mongo.supports_transactions)
+                .map(|mongo| mongo.database.client().clone()),
+        ));
+        let mongo_db = mongo.map(|mongo| mongo.database);
 
         //---[ Global Services]---------------------------------------------------------------------
-        let jwt_service = Arc::new(JwtService::new(config.clone()));
-        let email_service = Arc::new(EmailServicerResendImpl::new(&config));
+        let jwt_service = Arc::new(JwtService::new(config.clone())?);
+        let totp_service = Arc::new(TotpService::new(&config.app_name));
+        let i18n = Arc::new(I18n::new(&config.default_locale));
+        if let Some(dir) = &config.locales_dir {
+            i18n.load_dir(dir)?;
+        }
+        let email_templates = Arc::new(TemplateRegistry::new(&config.app_name, i18n.clone()));
+        if let Some(dir) = &config.email_templates_dir {
+            email_templates.load_dir(dir)?;
+        }
+        // The provider the outbox worker delivers the emails with
+        let mut email_capture = None;
+        let templates = Arc::clone(&email_templates);
+        let email_transport: Arc<dyn EmailService> = match &config.email_backend {
+            EmailBackendConfig::Resend => {
+                Arc::new(EmailServicerResendImpl::new(&config, templates))
+            }
+            EmailBackendConfig::Smtp(smtp) => {
+                Arc::new(EmailServiceSmtpImpl::new(&config, smtp, templates)?)
+            }
+            EmailBackendConfig::Capture(_) if !config.is_dev => {
+                return Err(AppError::EmailConfigurationError(
+                    "The capture email backend is only available in development".to_string(),
+                ));
+            }
+            EmailBackendConfig::Capture(capture) => {
+                let capture = Arc::new(EmailServiceCaptureImpl::new(&config, capture, templates));
+                email_capture = Some(Arc::clone(&capture));
+                capture
+            }
+        };
         let mut storage_config = StorageConfig::default();
         storage_config.base_path = config.clone().uploads_base;
+        storage_config.backend = config.storage_backend.clone();
+        storage_config.policies = config.storage_policies.clone();
+        storage_config.entity_quota = config.storage_entity_quota;
+        storage_config.signed_urls.key = config
+            .storage_signing_key
+            .clone()
+            .unwrap_or_else(|| UrlSigner::derive_key(&config.jwt_secret));
+        storage_config.user_dirs = config.storage_user_dirs.clone();
+        storage_config.orphan_sweep_interval = config.storage_orphan_sweep_interval;
         let storage_service = Arc::new(StorageService::new(storage_config));
 
         //---[ Features ]---------------------------------------------------------------------------
-        let auth_di = Arc::new(AuthDi::new(&db));
-        let auth_token_di = Arc::new(AuthTokenDi::new(&db));
+        if let Some(db) = &mongo_db {
+            AuthDi::prepare_database(db).await?;
+            AuthTokenDi::prepare_database(db).await?;
+            ApiKeyDi::prepare_database(db).await?;
+            EmailOutboxDi::prepare_database(db).await?;
+        }
+        let (auth_di, auth_token_di, api_key_di) = match &mongo_db {
+            Some(db) => (AuthDi::new(db), AuthTokenDi::new(db), ApiKeyDi::new(db)),
+            None => (
+                AuthDi::in_memory(),
+                AuthTokenDi::in_memory(),
+                ApiKeyDi::in_memory(),
+            ),
+        };
+        let auth_di = Arc::new(auth_di);
+        let auth_token_di = Arc::new(auth_token_di);
+        let api_key_di = Arc::new(api_key_di);
+        // Checks the access tokens against their revocations, see `auth_middleware`
+        let authenticator = Arc::new(Authenticator::new(
+            jwt_service.clone(),
+            auth_token_di.revoke_access_token.clone(),
+            auth_token_di.is_access_token_revoked.clone(),
+            auth_token_di.is_session_revoked.clone(),
+            api_key_di.resolve_api_key.clone(),
+            auth_di.get_user_by_id.clone(),
+            config.access_token_revocation_cache_ttl,
+        ));
+        let storage_di = Arc::new(StorageDi::new(
+            storage_service.clone(),
+            auth_di.get_many_users.clone(),
+        ));
+        let email_outbox_di = Arc::new(match &mongo_db {
+            Some(db) => EmailOutboxDi::new(db, email_transport, config.email_outbox.clone()),
+            None => EmailOutboxDi::in_memory(email_transport, config.email_outbox.clone()),
+        });
+        // Handlers only queue the emails
+        let email_service = Arc::new(OutboxEmailService::new(
+            email_outbox_di.enqueue_email.clone(),
+            email_templates.clone(),
+        ));
         let ws_clients = Arc::new(ClientsManager::new());
+        let db = match mongo_db {
+            Some(db) => db,
+            None => MongoConnection::detached(&config)?.database,
+        };
 
         Ok(Self {
             db,
             email_service,
+            email_templates,
+            i18n,
+            email_capture,
+            transactions,
             jwt_service,
+            totp_service,
+            authenticator,
             auth_di,
             auth_token_di,
+            api_key_di,
+            storage_di,
+            email_outbox_di,
             ws_clients,
             storage_service,
         })
     }
 
     /* ········································································ [ Core Services ] */
+    /// Runs the writes of several features as a whole, see `TransactionRunner::run`
+    pub fn transactions(&self) -> Arc<TransactionRunner> {
+        Arc::clone(&self.transactions)
+    }
     pub fn jwt_service(&self) -> Arc<JwtService> {
         Arc::clone(&self.jwt_service)
     }
+    /// Two-factor authentication with authenticator apps
+    pub fn totp_service(&self) -> Arc<TotpService> {
+        Arc::clone(&self.totp_service)
+    }
+    /// Authenticates the requests, see `auth_middleware`
+    pub fn authenticator(&self) -> Arc<Authenticator> {
+        Arc::clone(&self.authenticator)
+    }
     pub fn email_service(&self) -> Arc<dyn EmailService> {
         Arc::clone(&self.email_service)
     }
+    /// Register or override email templates at runtime, see `TemplateRegistry`
+    pub fn email_templates(&self) -> Arc<TemplateRegistry> {
+        Arc::clone(&self.email_templates)
+    }
+    /// Add or override message catalogs at runtime, see `I18n`
+    pub fn i18n(&self) -> Arc<I18n> {
+        Arc::clone(&self.i18n)
+    }
+    /// The captured emails, `None` unless `EmailBackendConfig::Capture` is selected
+    pub fn email_capture(&self) -> Option<Arc<EmailServiceCaptureImpl>> {
+        self.email_capture.clone()
+    }
     pub fn storage_service(&self) -> Arc<StorageService> {
         Arc::clone(&self.storage_service)
     }
@@ -89,6 +237,63 @@ impl ServiceLocator {
     pub fn delete_many_refresh_tokens(&self) -> Arc<DeleteManyRefreshTokens> {
         Arc::clone(&self.auth_token_di.delete_many_refresh_tokens)
     }
+    pub fn revoke_refresh_token(&self) -> Arc<RevokeRefreshToken> {
+        Arc::clone(&self.auth_token_di.revoke_refresh_token)
+    }
+    pub fn revoke_refresh_token_family(&self) -> Arc<RevokeRefreshTokenFamily> {
+        Arc::clone(&self.auth_token_di.revoke_refresh_token_family)
+    }
+    pub fn claim_refresh_token(&self) -> Arc<ClaimRefreshToken> {
+        Arc::clone(&self.auth_token_di.claim_refresh_token)
+    }
+    pub fn get_sessions(&self) -> Arc<GetSessions> {
+        Arc::clone(&self.auth_token_di.get_sessions)
+    }
+    pub fn revoke_sessions(&self) -> Arc<RevokeSessions> {
+        Arc::clone(&self.auth_token_di.revoke_sessions)
+    }
+
+    /* ············································································ [ API Keys ] */
+    pub fn create_api_key(&self) -> Arc<CreateApiKey> {
+        Arc::clone(&self.api_key_di.create_api_key)
+    }
+    pub fn get_api_keys(&self) -> Arc<GetApiKeys> {
+        Arc::clone(&self.api_key_di.get_api_keys)
+    }
+    pub fn revoke_api_key(&self) -> Arc<RevokeApiKey> {
+        Arc::clone(&self.api_key_di.revoke_api_key)
+    }
+    pub fn delete_many_api_keys(&self) -> Arc<DeleteManyApiKeys> {
+        Arc::clone(&self.api_key_di.delete_many_api_keys)
+    }
+
+    /* ············································································· [ Storage ] */
+    pub fn find_orphan_storage_dirs(&self) -> Arc<FindOrphanStorageDirs> {
+        Arc::clone(&self.storage_di.find_orphan_storage_dirs)
+    }
+    pub fn delete_orphan_storage_dirs(&self) -> Arc<DeleteOrphanStorageDirs> {
+        Arc::clone(&self.storage_di.delete_orphan_storage_dirs)
+    }
+
+    /* ········································································ [ Email Outbox ] */
+    pub fn enqueue_email(&self) -> Arc<EnqueueEmail> {
+        Arc::clone(&self.email_outbox_di.enqueue_email)
+    }
+    pub fn deliver_next_outbox_email(&self) -> Arc<DeliverNextOutboxEmail> {
+        Arc::clone(&self.email_outbox_di.deliver_next_outbox_email)
+    }
+    pub fn get_outbox_emails(&self) -> Arc<GetOutboxEmails> {
+        Arc::clone(&self.email_outbox_di.get_outbox_emails)
+    }
+    pub fn get_outbox_email_by_id(&self) -> Arc<GetOutboxEmailById> {
+        Arc::clone(&self.email_outbox_di.get_outbox_email_by_id)
+    }
+    pub fn retry_outbox_email(&self) -> Arc<RetryOutboxEmail> {
+        Arc::clone(&self.email_outbox_di.retry_outbox_email)
+    }
+    pub fn email_outbox_wake_worker(&self) -> Arc<Notify> {
+        Arc::clone(&self.email_outbox_di.wake_worker)
+    }
 
     /* ············································································ [ Auth User ] */
     pub fn add_one_user(&self) -> Arc<AddOneUser> {
@@ -112,4 +317,10 @@ impl ServiceLocator {
     pub fn get_user(&self) -> Arc<GetUser> {
         Arc::clone(&self.auth_di.get_user)
     }
+    pub fn verify_second_factor(&self) -> Arc<VerifySecondFactor> {
+        Arc::clone(&self.auth_di.verify_second_factor)
+    }
+    pub fn set_second_factors(&self) -> Arc<SetSecondFactors> {
+        Arc::clone(&self.auth_di.set_second_factors)
+    }
 }
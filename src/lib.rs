@@ -9,13 +9,19 @@ use std::sync::Arc;
 
 use warp::Filter;
 
+use crate::api::api_key::ApiKeyFeature;
 use crate::api::auth::domain::entities::Claims;
 use crate::api::auth::UserFeature;
 use crate::api::auth_token::AuthTokenFeature;
+use crate::api::dev_mailbox::DevMailboxFeature;
+use crate::api::email_outbox::{spawn_email_outbox_worker, EmailOutboxFeature};
+use crate::api::storage::{spawn_orphan_storage_sweeper, StorageFeature};
 use crate::core::CoreEventHandler;
 use crate::core::{
-    check_server_status::check_server_status, errors::handle_app_rejection,
-    middleware::auth_middleware, Config,
+    check_server_status::check_server_status,
+    errors::handle_app_rejection,
+    middleware::{auth_middleware, localize_responses, session_middleware},
+    Config,
 };
 use crate::di::ServiceLocator;
 use crate::websocket::ws_handler;
@@ -29,6 +35,8 @@ impl CoreServer {
     pub async fn new(is_dev: bool) -> Result<Self, Box<dyn std::error::Error>> {
         let config = Config::new(is_dev)?;
         let service_locator = Arc::new(ServiceLocator::new(config).await?);
+        spawn_orphan_storage_sweeper(Arc::clone(&service_locator));
+        spawn_email_outbox_worker(Arc::clone(&service_locator));
 
         Ok(Self { service_locator })
     }
@@ -36,6 +44,8 @@ impl CoreServer {
     /// Create a new QkonsServer with custom configuration
     pub async fn with_config(config: Config) -> Result<Self, Box<dyn std::error::Error>> {
         let service_locator = Arc::new(ServiceLocator::new(config).await?);
+        spawn_orphan_storage_sweeper(Arc::clone(&service_locator));
+        spawn_email_outbox_worker(Arc::clone(&service_locator));
         Ok(Self { service_locator })
     }
 
@@ -71,11 +81,26 @@ impl CoreServer {
             .with(self.cors_routes())
             .with(self.log_routes())
             .recover(handle_app_rejection);
+        // Messages are translated into the user's language once the response is built
+        let api_routes = localize_responses(
+            api_routes,
+            self.service_locator.authenticator(),
+            self.service_locator.i18n(),
+        );
 
         // let landing_page = warp::fs::dir("src/static");
         // warp::path("api").and(api_routes).or(landing_page)
 
-        warp::path("api").and(api_routes)
+        // Discovery documents are expected at the root of the server. The other requests out of
+        // `/api` are left to the routes of the application
+        let well_known_routes = warp::path(".well-known").and(
+            Arc::new(AuthTokenFeature::new(Arc::clone(&self.service_locator)))
+                .well_known_routes()
+                .with(self.cors_routes())
+                .with(self.log_routes()),
+        );
+
+        warp::path("api").and(api_routes).or(well_known_routes)
     }
 
     fn base_routes<E: CoreEventHandler + 'static>(
@@ -91,20 +116,33 @@ impl CoreServer {
         let ws_route = warp::path("ws")
             .and(warp::ws())
             .and(clients_filter.clone())
-            .and(auth_middleware(self.service_locator.jwt_service()))
+            // A user session, the API keys are not meant for the websocket
+            .and(auth_middleware(self.service_locator.authenticator()).and_then(session_middleware))
             .map(|ws: warp::ws::Ws, clients, claims: Claims| {
                 ws.on_upgrade(move |socket| ws_handler::handle_ws_client(socket, clients, claims))
             });
 
         /* ······································································· [ API ROUTES ] */
-        let auth_routes =
-            Arc::new(UserFeature::new(Arc::clone(&self.service_locator))).routes(event_handler);
-        let auth_token_routes =
-            Arc::new(AuthTokenFeature::new(Arc::clone(&self.service_locator))).routes();
+        let auth_routes = Arc::new(UserFeature::new(Arc::clone(&self.service_locator)))
+            .routes(event_handler.clone());
+        let auth_token_routes = Arc::new(AuthTokenFeature::new(Arc::clone(&self.service_locator)))
+            .routes(event_handler);
+        let api_key_routes =
+            Arc::new(ApiKeyFeature::new(Arc::clone(&self.service_locator))).routes();
+        let storage_routes =
+            Arc::new(StorageFeature::new(Arc::clone(&self.service_locator))).routes();
+        let email_outbox_routes =
+            Arc::new(EmailOutboxFeature::new(Arc::clone(&self.service_locator))).routes();
+        let dev_mailbox_routes =
+            Arc::new(DevMailboxFeature::new(Arc::clone(&self.service_locator))).routes();
 
         check_server_status(self.service_locator.jwt_service())
             .or(auth_routes)
             .or(auth_token_routes)
+            .or(api_key_routes)
+            .or(storage_routes)
+            .or(email_outbox_routes)
+            .or(dev_mailbox_routes)
             .or(ws_route)
     }
 
@@ -119,6 +157,7 @@ impl CoreServer {
                 "Access-Control-Allow-Headers",
                 "X-App-Version",
                 "X-API-TOKEN",
+                "X-API-KEY",
             ])
             .allow_methods(&[
                 warp::http::Method::GET,